@@ -1,12 +1,5 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::{
-    commitment_config::CommitmentConfig, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
-    signature::Keypair, signer::Signer, system_instruction, transaction::Transaction,
-};
+use solana_sdk::{signer::Signer, system_instruction};
 use solana_transaction_status::UiTransactionEncoding;
-use spl_associated_token_account::{
-    get_associated_token_address_with_program_id, instruction::create_associated_token_account,
-};
 use spl_token_2022::{
     extension::{
         memo_transfer::instruction::{
@@ -14,52 +7,44 @@ use spl_token_2022::{
         },
         ExtensionType,
     },
-    instruction::{initialize_account, initialize_mint, mint_to, transfer_checked},
-    state::{Account, Mint},
+    instruction::{initialize_account, mint_to, transfer_checked},
+    state::Account,
+};
+use token_extensions_common::{
+    account::{associated_token_address, create_associated_token_account},
+    mint::MintBuilder,
+    validation::validate_extensions,
+    Config, Result,
 };
 
-fn main() {
-    let mint_authority = Keypair::new();
-    let mint_account = Keypair::new();
+fn main() -> Result<()> {
+    let config = Config::from_args()?;
+    let client = config.client();
+
+    let mint_authority = config.keypair()?;
+    let mint_account = config.signer_or_new("mint")?;
 
     println!("MINT AUTHORITY: {}", mint_authority.pubkey());
     println!("MINT ACCOUNT: {}", mint_account.pubkey());
     let decimals = 0u8;
 
-    let client = RpcClient::new("http://localhost:8899".to_string());
-
-    let extensions = [];
-    let mint_len = ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap();
-    let rent = client
-        .get_minimum_balance_for_rent_exemption(mint_len)
-        .unwrap();
-
-    let create_account_instr = system_instruction::create_account(
+    let mint_builder = MintBuilder::new(mint_authority.pubkey(), decimals);
+    let mut instructions = client.create_mint_instructions(
         &mint_authority.pubkey(),
         &mint_account.pubkey(),
-        rent,
-        mint_len as u64,
-        &spl_token_2022::id(),
-    );
-
-    let init_mint_instr = initialize_mint(
-        &spl_token_2022::id(),
-        &mint_account.pubkey(),
-        &mint_authority.pubkey(),
-        Some(&mint_authority.pubkey()),
-        decimals,
-    )
-    .unwrap();
+        &mint_builder,
+    )?;
 
-    let token_account = Keypair::new();
+    let token_account = config.signer_or_new("token-account")?;
     println!("TOKEN ACCOUNT: {}", &token_account.pubkey());
 
     let token_account_ext = [ExtensionType::MemoTransfer];
+    validate_extensions(&mint_builder.extension_types(), &token_account_ext).check()?;
     let token_account_len =
-        ExtensionType::try_calculate_account_len::<Account>(&token_account_ext).unwrap();
+        ExtensionType::try_calculate_account_len::<Account>(&token_account_ext)?;
     let token_account_rent = client
-        .get_minimum_balance_for_rent_exemption(token_account_len)
-        .unwrap();
+        .rpc_client()
+        .get_minimum_balance_for_rent_exemption(token_account_len)?;
 
     let token_account_instr = system_instruction::create_account(
         &mint_authority.pubkey(),
@@ -73,50 +58,36 @@ fn main() {
         &token_account.pubkey(),
         &mint_account.pubkey(),
         &token_account.pubkey(),
-    )
-    .unwrap();
+    )?;
 
     let enable_memo_instr = enable_required_transfer_memos(
         &spl_token_2022::id(),
         &token_account.pubkey(),
         &token_account.pubkey(),
         &[&mint_authority.pubkey(), &token_account.pubkey()],
-    )
-    .unwrap();
+    )?;
+
+    instructions.extend([
+        token_account_instr,
+        init_token_account_instr,
+        enable_memo_instr,
+    ]);
 
-    check_request_airdrop(&client, &mint_authority.pubkey(), 2);
+    client.check_request_airdrop(&mint_authority.pubkey(), 2)?;
 
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let tx = Transaction::new_signed_with_payer(
-        &[
-            create_account_instr,
-            init_mint_instr,
-            token_account_instr,
-            init_token_account_instr,
-            enable_memo_instr,
-        ],
-        Some(&mint_authority.pubkey()),
+    client.send_transaction(
+        &instructions,
+        &mint_authority.pubkey(),
         &[&mint_authority, &mint_account, &token_account],
-        recent_blockhash,
-    );
-    client
-        .send_and_confirm_transaction_with_spinner_and_commitment(
-            &tx,
-            CommitmentConfig::finalized(),
-        )
-        .unwrap();
+    )?;
 
     let source_account = create_associated_token_account(
         &mint_authority.pubkey(),
         &mint_authority.pubkey(),
         &mint_account.pubkey(),
-        &spl_token_2022::id(),
-    );
-    let mint_authority_ata = get_associated_token_address_with_program_id(
-        &mint_authority.pubkey(),
-        &mint_account.pubkey(),
-        &spl_token_2022::id(),
     );
+    let mint_authority_ata =
+        associated_token_address(&mint_authority.pubkey(), &mint_account.pubkey());
     println!("MINT AUTHORITY ATA: {}", mint_authority_ata);
     let mint_to_instr = mint_to(
         &spl_token_2022::id(),
@@ -125,22 +96,13 @@ fn main() {
         &mint_authority.pubkey(),
         &[&mint_authority.pubkey()],
         200,
-    )
-    .unwrap();
+    )?;
 
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let tx = Transaction::new_signed_with_payer(
+    client.send_transaction(
         &[source_account, mint_to_instr],
-        Some(&mint_authority.pubkey()),
+        &mint_authority.pubkey(),
         &[&mint_authority],
-        recent_blockhash,
-    );
-    client
-        .send_and_confirm_transaction_with_spinner_and_commitment(
-            &tx,
-            CommitmentConfig::finalized(),
-        )
-        .unwrap();
+    )?;
 
     let transfer_instr = transfer_checked(
         &spl_token_2022::id(),
@@ -151,53 +113,38 @@ fn main() {
         &[&mint_authority.pubkey()],
         12,
         decimals,
-    )
-    .unwrap();
+    )?;
 
     let memo_instr =
         spl_memo::build_memo(b"Learning Token Extensions", &[&mint_authority.pubkey()]);
 
     println!("Transferring Without Memo");
     //Attempt to transfer without a memo
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let tx = Transaction::new_signed_with_payer(
-        &[transfer_instr.clone()],
-        Some(&mint_authority.pubkey()),
+    if let Err(error) = client.send_transaction(
+        std::slice::from_ref(&transfer_instr),
+        &mint_authority.pubkey(),
         &[&mint_authority],
-        recent_blockhash,
-    );
-    if let Err(error) = client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &tx,
-        CommitmentConfig::finalized(),
     ) {
         dbg!(&error);
     }
 
     println!("Transferring With Memo");
-    let tx = Transaction::new_signed_with_payer(
+    match client.send_transaction(
         &[memo_instr, transfer_instr.clone()],
-        Some(&mint_authority.pubkey()),
+        &mint_authority.pubkey(),
         &[&mint_authority],
-        recent_blockhash,
-    );
-    match client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &tx,
-        CommitmentConfig::finalized(),
     ) {
         Err(error) => {
             dbg!(&error);
         }
         Ok(sig) => {
             println!("TRANSFERRED WITH MEMO");
-            dbg!(
-                client
-                    .get_transaction(&sig, UiTransactionEncoding::Json)
-                    .unwrap()
-                    .transaction
-                    .meta
-                    .unwrap()
-                    .log_messages
-            );
+            dbg!(client
+                .rpc_client()
+                .get_transaction(&sig, UiTransactionEncoding::Json)?
+                .transaction
+                .meta
+                .map(|meta| meta.log_messages));
         }
     }
 
@@ -207,69 +154,36 @@ fn main() {
         &token_account.pubkey(),
         &token_account.pubkey(),
         &[&mint_authority.pubkey(), &token_account.pubkey()],
-    )
-    .unwrap();
+    )?;
 
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let tx = Transaction::new_signed_with_payer(
+    if let Err(error) = client.send_transaction(
         &[disable_memo_instr],
-        Some(&mint_authority.pubkey()),
+        &mint_authority.pubkey(),
         &[&mint_authority, &token_account],
-        recent_blockhash,
-    );
-    if let Err(error) = client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &tx,
-        CommitmentConfig::finalized(),
     ) {
         dbg!(&error);
     }
 
     println!("Transferring Without Memo After Disabling Memo Requirement");
     //Attempt to transfer without a memo
-    let recent_blockhash = client.get_latest_blockhash().unwrap();
-    let tx = Transaction::new_signed_with_payer(
-        &[transfer_instr.clone()],
-        Some(&mint_authority.pubkey()),
+    match client.send_transaction(
+        &[transfer_instr],
+        &mint_authority.pubkey(),
         &[&mint_authority],
-        recent_blockhash,
-    );
-    match client.send_and_confirm_transaction_with_spinner_and_commitment(
-        &tx,
-        CommitmentConfig::finalized(),
     ) {
         Err(error) => {
             dbg!(&error);
         }
         Ok(sig) => {
             println!("TRANSFERRED WITHOUT MEMO");
-            dbg!(
-                client
-                    .get_transaction(&sig, UiTransactionEncoding::Json)
-                    .unwrap()
-                    .transaction
-                    .meta
-                    .unwrap()
-                    .log_messages
-            );
+            dbg!(client
+                .rpc_client()
+                .get_transaction(&sig, UiTransactionEncoding::Json)?
+                .transaction
+                .meta
+                .map(|meta| meta.log_messages));
         }
     }
-}
 
-fn check_request_airdrop(client: &RpcClient, account: &Pubkey, amount: u64) {
-    if client.get_balance(&account).unwrap().eq(&0) {
-        client
-            .request_airdrop(&account, LAMPORTS_PER_SOL * amount)
-            .unwrap();
-
-        loop {
-            if (LAMPORTS_PER_SOL).gt(&client.get_balance(&account).unwrap()) {
-                println!("Airdrop for {} has not reflected ...", account);
-                std::thread::sleep(std::time::Duration::from_secs(1));
-            } else {
-                println!("\nAirdrop for {} has reflected!\n", account);
-
-                break;
-            }
-        }
-    }
+    Ok(())
 }
@@ -0,0 +1,293 @@
+//! Command line arguments
+
+use {
+    clap::{Args, Parser, Subcommand, ValueEnum},
+    solana_sdk::pubkey::Pubkey,
+    spl_token_2022::{extension::ExtensionType, state::AccountState},
+    std::path::PathBuf,
+    token_extensions_common::config::ConfigArgs,
+};
+
+/// Create and operate Token-2022 mints with extensions
+#[derive(Debug, Parser)]
+#[command(name = "token-ext", version)]
+pub struct Cli {
+    /// The keypair pays for every transaction and acts as every authority
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create a mint with any combination of extensions
+    CreateMint(CreateMintArgs),
+    /// Create the associated token account of an owner
+    CreateAccount {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner of the account [default: the keypair]
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Mint tokens to the associated token account of an owner, creating it
+    /// if needed
+    MintTo {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Amount in base units
+        #[arg(long)]
+        amount: u64,
+        /// Owner of the receiving account [default: the keypair]
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Transfer tokens, paying any transfer fee and resolving the accounts
+    /// required by any transfer hook
+    Transfer {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner of the receiving associated token account
+        #[arg(long)]
+        to: Pubkey,
+        /// Amount in base units
+        #[arg(long)]
+        amount: u64,
+        /// Memo to send along, required by accounts with required memos
+        #[arg(long)]
+        memo: Option<String>,
+    },
+    /// Burn tokens from the keypair's associated token account
+    Burn {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Amount in base units
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Move withheld transfer fees from token accounts to the mint
+    Harvest {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Token accounts to harvest [default: every account withholding
+        /// fees]
+        accounts: Vec<Pubkey>,
+    },
+    /// Withdraw withheld transfer fees to the keypair's associated token
+    /// account
+    WithdrawWithheld {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Withdraw from every token account withholding fees instead of
+        /// from the mint
+        #[arg(long)]
+        from_accounts: bool,
+    },
+    /// Change the transfer fee, effective two epochs later
+    SetTransferFee {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Fee in basis points (100 = 1%)
+        #[arg(long)]
+        fee_bps: u16,
+        /// Maximum fee in base units
+        #[arg(long)]
+        max_fee: u64,
+    },
+    /// Change the interest rate
+    UpdateRate {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Rate in basis points
+        #[arg(long, allow_negative_numbers = true)]
+        rate: i16,
+    },
+    /// Freeze the associated token account of an owner
+    Freeze {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        owner: Pubkey,
+    },
+    /// Thaw the associated token account of an owner
+    Thaw {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        owner: Pubkey,
+    },
+    /// Change the state new token accounts start in
+    SetDefaultState {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long, value_enum)]
+        state: AccountStateArg,
+    },
+    /// Require memos on transfers into the keypair's associated token
+    /// account
+    RequiredMemo {
+        #[command(subcommand)]
+        command: RequiredMemoCommand,
+    },
+    /// Add extensions to the keypair's associated token account
+    Reallocate {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Extensions to make room for
+        #[arg(long = "ext", value_enum, required = true)]
+        extensions: Vec<AccountExtensionArg>,
+    },
+    /// Token metadata stored in the mint
+    Metadata {
+        #[command(subcommand)]
+        command: MetadataCommand,
+    },
+}
+
+/// Arguments of `create-mint`
+#[derive(Debug, Args)]
+pub struct CreateMintArgs {
+    /// Decimals of the mint
+    #[arg(long, default_value_t = 0)]
+    pub decimals: u8,
+    /// Keypair of the new mint [default: the `mint` signer or a new
+    /// keypair]
+    #[arg(long)]
+    pub mint_keypair: Option<PathBuf>,
+    /// Extensions to initialize
+    #[arg(long = "ext", value_enum)]
+    pub extensions: Vec<MintExtensionArg>,
+    /// Transfer fee in basis points (100 = 1%)
+    #[arg(long, required_if_eq("extensions", "transfer-fee"))]
+    pub fee_bps: Option<u16>,
+    /// Maximum transfer fee in base units
+    #[arg(long, required_if_eq("extensions", "transfer-fee"))]
+    pub max_fee: Option<u64>,
+    /// Interest rate in basis points
+    #[arg(
+        long,
+        allow_negative_numbers = true,
+        required_if_eq("extensions", "interest-bearing")
+    )]
+    pub rate: Option<i16>,
+    /// State new token accounts start in
+    #[arg(
+        long,
+        value_enum,
+        required_if_eq("extensions", "default-account-state")
+    )]
+    pub default_state: Option<AccountStateArg>,
+    /// Permanent delegate [default: the keypair]
+    #[arg(long)]
+    pub delegate: Option<Pubkey>,
+    /// Program invoked on every transfer
+    #[arg(long, required_if_eq("extensions", "transfer-hook"))]
+    pub hook_program_id: Option<Pubkey>,
+    /// Token name
+    #[arg(long, required_if_eq("extensions", "metadata"))]
+    pub name: Option<String>,
+    /// Token symbol
+    #[arg(long, required_if_eq("extensions", "metadata"))]
+    pub symbol: Option<String>,
+    /// URI of the off-chain metadata
+    #[arg(long, required_if_eq("extensions", "metadata"))]
+    pub uri: Option<String>,
+}
+
+/// Mint extensions `create-mint` can initialize
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MintExtensionArg {
+    TransferFee,
+    TransferHook,
+    /// Metadata stored in the mint along with a pointer to it
+    Metadata,
+    InterestBearing,
+    DefaultAccountState,
+    PermanentDelegate,
+    NonTransferable,
+}
+
+/// Token account extensions `reallocate` can make room for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AccountExtensionArg {
+    MemoTransfer,
+    CpiGuard,
+}
+
+impl From<AccountExtensionArg> for ExtensionType {
+    fn from(extension: AccountExtensionArg) -> Self {
+        match extension {
+            AccountExtensionArg::MemoTransfer => ExtensionType::MemoTransfer,
+            AccountExtensionArg::CpiGuard => ExtensionType::CpiGuard,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AccountStateArg {
+    Initialized,
+    Frozen,
+}
+
+impl From<AccountStateArg> for AccountState {
+    fn from(state: AccountStateArg) -> Self {
+        match state {
+            AccountStateArg::Initialized => AccountState::Initialized,
+            AccountStateArg::Frozen => AccountState::Frozen,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RequiredMemoCommand {
+    /// Reject transfers without a memo
+    Enable {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Accept transfers without a memo
+    Disable {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MetadataCommand {
+    /// Initialize the metadata of a mint pointing to itself
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long)]
+        uri: String,
+    },
+    /// Set `name`, `symbol`, `uri` or any additional field
+    SetField {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        field: String,
+        #[arg(long)]
+        value: String,
+    },
+    /// Remove an additional field
+    RemoveKey {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        key: String,
+        /// Succeed even if the key does not exist
+        #[arg(long)]
+        idempotent: bool,
+    },
+    /// Print the metadata
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
@@ -0,0 +1,86 @@
+//! Token account management
+
+use {
+    super::Context,
+    solana_sdk::pubkey::Pubkey,
+    spl_token_2022::{
+        extension::{
+            memo_transfer::instruction::{
+                disable_required_transfer_memos, enable_required_transfer_memos,
+            },
+            ExtensionType,
+        },
+        instruction::{freeze_account, reallocate as reallocate_instruction, thaw_account},
+    },
+    token_extensions_common::{
+        account::{associated_token_address, create_associated_token_account},
+        Result,
+    },
+};
+
+pub fn create_account(context: &Context, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let owner = owner.unwrap_or_else(|| context.pubkey());
+    let instruction = create_associated_token_account(&context.pubkey(), &owner, mint);
+    context.send(&[instruction], &[])?;
+    println!("Token account: {}", associated_token_address(&owner, mint));
+
+    Ok(())
+}
+
+pub fn freeze(context: &Context, mint: &Pubkey, owner: &Pubkey) -> Result<()> {
+    let instruction = freeze_account(
+        &spl_token_2022::id(),
+        &associated_token_address(owner, mint),
+        mint,
+        &context.pubkey(),
+        &[],
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn thaw(context: &Context, mint: &Pubkey, owner: &Pubkey) -> Result<()> {
+    let instruction = thaw_account(
+        &spl_token_2022::id(),
+        &associated_token_address(owner, mint),
+        mint,
+        &context.pubkey(),
+        &[],
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn required_memo(context: &Context, mint: &Pubkey, enable: bool) -> Result<()> {
+    let token_account = context.token_account(mint);
+    let toggle = if enable {
+        enable_required_transfer_memos
+    } else {
+        disable_required_transfer_memos
+    };
+    let instruction = toggle(
+        &spl_token_2022::id(),
+        &token_account,
+        &context.pubkey(),
+        &[],
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn reallocate(context: &Context, mint: &Pubkey, extensions: &[ExtensionType]) -> Result<()> {
+    let instruction = reallocate_instruction(
+        &spl_token_2022::id(),
+        &context.token_account(mint),
+        &context.pubkey(),
+        &context.pubkey(),
+        &[],
+        extensions,
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Transfer fee management
+
+use {
+    super::{Context, MAX_ACCOUNTS_PER_INSTRUCTION},
+    solana_sdk::pubkey::Pubkey,
+    spl_token_2022::{
+        extension::{
+            transfer_fee::{
+                instruction::{
+                    harvest_withheld_tokens_to_mint,
+                    set_transfer_fee as set_transfer_fee_instruction,
+                    withdraw_withheld_tokens_from_accounts, withdraw_withheld_tokens_from_mint,
+                },
+                TransferFeeAmount,
+            },
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+        state::Account,
+    },
+    token_extensions_common::{Error, Result},
+};
+
+/// Token accounts of `mint` withholding fees
+fn accounts_withholding_fees(context: &Context, mint: &Pubkey) -> Result<Vec<Pubkey>> {
+    let mut accounts = Vec::new();
+
+    for (address, account) in context.client.get_token_accounts(mint)? {
+        let token_account = StateWithExtensions::<Account>::unpack(&account.data)
+            .map_err(Error::unpack(address))?;
+        let withheld_amount = token_account
+            .get_extension::<TransferFeeAmount>()
+            .map(|transfer_fee_amount| u64::from(transfer_fee_amount.withheld_amount))
+            .unwrap_or_default();
+
+        if withheld_amount > 0 {
+            accounts.push(address);
+        }
+    }
+
+    Ok(accounts)
+}
+
+pub fn harvest(context: &Context, mint: &Pubkey, accounts: Vec<Pubkey>) -> Result<()> {
+    let accounts = if accounts.is_empty() {
+        accounts_withholding_fees(context, mint)?
+    } else {
+        accounts
+    };
+
+    for chunk in accounts.chunks(MAX_ACCOUNTS_PER_INSTRUCTION) {
+        let sources = chunk.iter().collect::<Vec<_>>();
+        let instruction = harvest_withheld_tokens_to_mint(&spl_token_2022::id(), mint, &sources)?;
+        context.send(&[instruction], &[])?;
+    }
+    println!("Harvested {} accounts", accounts.len());
+
+    Ok(())
+}
+
+pub fn withdraw_withheld(context: &Context, mint: &Pubkey, from_accounts: bool) -> Result<()> {
+    let destination = context.token_account(mint);
+
+    if !from_accounts {
+        let instruction = withdraw_withheld_tokens_from_mint(
+            &spl_token_2022::id(),
+            mint,
+            &destination,
+            &context.pubkey(),
+            &[],
+        )?;
+        context.send(&[instruction], &[])?;
+
+        return Ok(());
+    }
+
+    let accounts = accounts_withholding_fees(context, mint)?;
+    for chunk in accounts.chunks(MAX_ACCOUNTS_PER_INSTRUCTION) {
+        let sources = chunk.iter().collect::<Vec<_>>();
+        let instruction = withdraw_withheld_tokens_from_accounts(
+            &spl_token_2022::id(),
+            mint,
+            &destination,
+            &context.pubkey(),
+            &[],
+            &sources,
+        )?;
+        context.send(&[instruction], &[])?;
+    }
+    println!("Withdrew from {} accounts", accounts.len());
+
+    Ok(())
+}
+
+pub fn set_transfer_fee(
+    context: &Context,
+    mint: &Pubkey,
+    fee_basis_points: u16,
+    max_fee: u64,
+) -> Result<()> {
+    let instruction = set_transfer_fee_instruction(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        &[],
+        fee_basis_points,
+        max_fee,
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Token metadata stored in the mint
+
+use {
+    super::Context,
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction},
+    spl_token_2022::{
+        error::TokenError,
+        extension::{BaseStateWithExtensions, StateWithExtensions},
+        state::Mint,
+    },
+    spl_token_metadata_interface::{
+        instruction::{initialize, remove_key as remove_key_instruction, update_field},
+        state::{Field, TokenMetadata},
+    },
+    spl_type_length_value::variable_len_pack::VariableLenPack,
+    token_extensions_common::{Error, Result},
+};
+
+/// Size of a TLV entry header: 2 bytes for type, 2 bytes for length
+const TLV_HEADER_LEN: usize = 4;
+
+/// The metadata currently stored in `mint`, if any, along with the instruction
+/// topping the mint up to the rent of holding `metadata` instead
+fn fund_metadata(
+    context: &Context,
+    mint: &Pubkey,
+    metadata: impl FnOnce(Option<TokenMetadata>) -> Result<TokenMetadata>,
+) -> Result<(TokenMetadata, Option<Instruction>)> {
+    let account = context.client.get_account(mint)?;
+    let mint_state =
+        StateWithExtensions::<Mint>::unpack(&account.data).map_err(Error::unpack(*mint))?;
+    let current = mint_state.get_extension_bytes::<TokenMetadata>().ok();
+
+    let current_len = current.map_or(0, |bytes| bytes.len() + TLV_HEADER_LEN);
+    let current_metadata = current
+        .map(TokenMetadata::unpack_from_slice)
+        .transpose()
+        .map_err(Error::unpack(*mint))?;
+
+    let metadata = metadata(current_metadata)?;
+    let new_len = account.data.len() - current_len + TLV_HEADER_LEN + metadata.get_packed_len()?;
+    let rent = context
+        .client
+        .rpc_client()
+        .get_minimum_balance_for_rent_exemption(new_len)?;
+    let top_up = (rent > account.lamports)
+        .then(|| system_instruction::transfer(&context.pubkey(), mint, rent - account.lamports));
+
+    Ok((metadata, top_up))
+}
+
+pub fn init(
+    context: &Context,
+    mint: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    let (metadata, top_up) = fund_metadata(context, mint, |_| {
+        Ok(TokenMetadata {
+            mint: *mint,
+            name,
+            symbol,
+            uri,
+            ..Default::default()
+        })
+    })?;
+
+    let mut instructions = Vec::from_iter(top_up);
+    instructions.push(initialize(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        mint,
+        &context.pubkey(),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    ));
+    context.send(&instructions, &[])?;
+
+    Ok(())
+}
+
+pub fn set_field(context: &Context, mint: &Pubkey, field: &str, value: String) -> Result<()> {
+    let field = match field {
+        "name" => Field::Name,
+        "symbol" => Field::Symbol,
+        "uri" => Field::Uri,
+        key => Field::Key(key.into()),
+    };
+
+    let (_, top_up) = fund_metadata(context, mint, |current| {
+        let mut metadata =
+            current.ok_or_else(|| Error::unpack(*mint)(TokenError::ExtensionNotFound.into()))?;
+        metadata.update(field.clone(), value.clone());
+        Ok(metadata)
+    })?;
+
+    let mut instructions = Vec::from_iter(top_up);
+    instructions.push(update_field(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        field,
+        value,
+    ));
+    context.send(&instructions, &[])?;
+
+    Ok(())
+}
+
+pub fn remove_key(context: &Context, mint: &Pubkey, key: String, idempotent: bool) -> Result<()> {
+    let instruction = remove_key_instruction(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        key,
+        idempotent,
+    );
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn show(context: &Context, mint: &Pubkey) -> Result<()> {
+    let data = context.client.get_account_data(mint)?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&data).map_err(Error::unpack(*mint))?;
+    let metadata_bytes = mint_state
+        .get_extension_bytes::<TokenMetadata>()
+        .map_err(Error::unpack(*mint))?;
+    let metadata =
+        TokenMetadata::unpack_from_slice(metadata_bytes).map_err(Error::unpack(*mint))?;
+
+    println!("Name: {}", metadata.name);
+    println!("Symbol: {}", metadata.symbol);
+    println!("URI: {}", metadata.uri);
+    println!(
+        "Update authority: {:?}",
+        Option::<Pubkey>::from(metadata.update_authority)
+    );
+    for (key, value) in &metadata.additional_metadata {
+        println!("{key}: {value}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+//! Mint creation and mint-wide settings
+
+use {
+    super::Context,
+    crate::cli::{CreateMintArgs, MintExtensionArg},
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::{
+        extension::{default_account_state, interest_bearing_mint},
+        instruction::{burn_checked, mint_to as mint_to_instruction},
+        state::AccountState,
+    },
+    spl_token_metadata_interface::state::TokenMetadata,
+    spl_type_length_value::variable_len_pack::VariableLenPack,
+    token_extensions_common::{
+        account::create_associated_token_account_idempotent, config::read_keypair,
+        mint::MintBuilder, Error, Result,
+    },
+};
+
+pub fn create_mint(context: &Context, args: CreateMintArgs) -> Result<()> {
+    let mint_account = match &args.mint_keypair {
+        Some(path) => read_keypair(path)?,
+        None => context.config.signer_or_new("mint")?,
+    };
+    let mint = mint_account.pubkey();
+    let authority = context.pubkey();
+
+    // clap already requires the values an extension needs, this only guards
+    // callers that build the arguments by hand
+    let mut builder = MintBuilder::new(authority, args.decimals);
+    let mut metadata = None;
+    for extension in &args.extensions {
+        builder = match extension {
+            MintExtensionArg::TransferFee => builder.transfer_fee(
+                required(args.fee_bps, "--fee-bps")?,
+                required(args.max_fee, "--max-fee")?,
+            ),
+            MintExtensionArg::TransferHook => {
+                builder.transfer_hook(required(args.hook_program_id, "--hook-program-id")?)
+            }
+            MintExtensionArg::Metadata => {
+                let mut token_metadata = TokenMetadata {
+                    mint,
+                    name: required(args.name.clone(), "--name")?,
+                    symbol: required(args.symbol.clone(), "--symbol")?,
+                    uri: required(args.uri.clone(), "--uri")?,
+                    ..Default::default()
+                };
+                token_metadata.update_authority.0 = authority;
+                metadata = Some(token_metadata);
+
+                builder.metadata_pointer(mint)
+            }
+            MintExtensionArg::InterestBearing => {
+                builder.interest_bearing(required(args.rate, "--rate")?)
+            }
+            MintExtensionArg::DefaultAccountState => builder
+                .default_account_state(required(args.default_state, "--default-state")?.into()),
+            MintExtensionArg::PermanentDelegate => {
+                builder.permanent_delegate(args.delegate.unwrap_or(authority))
+            }
+            MintExtensionArg::NonTransferable => builder.non_transferable(),
+        };
+    }
+
+    let mut account_len = builder.mint_len()?;
+    if let Some(metadata) = &metadata {
+        // Metadata is written after the mint is initialized, so fund its
+        // TLV entry (2 bytes for type, 2 bytes for length) up front
+        account_len += 4 + metadata.get_packed_len()?;
+    }
+    let rent = context
+        .client
+        .rpc_client()
+        .get_minimum_balance_for_rent_exemption(account_len)?;
+
+    let mut instructions = builder.instructions(&authority, &mint, rent)?;
+    if let Some(metadata) = metadata {
+        instructions.push(spl_token_metadata_interface::instruction::initialize(
+            &spl_token_2022::id(),
+            &mint,
+            &authority,
+            &mint,
+            &authority,
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ));
+    }
+
+    context.send(&instructions, &[&mint_account])?;
+    println!("Mint: {mint}");
+
+    Ok(())
+}
+
+fn required<T>(value: Option<T>, flag: &str) -> Result<T> {
+    value.ok_or_else(|| Error::InvalidInput(format!("{flag} is required")))
+}
+
+pub fn mint_to(context: &Context, mint: &Pubkey, amount: u64, owner: Option<Pubkey>) -> Result<()> {
+    let owner = owner.unwrap_or_else(|| context.pubkey());
+    let token_account = token_extensions_common::account::associated_token_address(&owner, mint);
+
+    let instructions = [
+        create_associated_token_account_idempotent(&context.pubkey(), &owner, mint),
+        mint_to_instruction(
+            &spl_token_2022::id(),
+            mint,
+            &token_account,
+            &context.pubkey(),
+            &[],
+            amount,
+        )?,
+    ];
+    context.send(&instructions, &[])?;
+    println!("Minted {amount} to {token_account}");
+
+    Ok(())
+}
+
+pub fn burn(context: &Context, mint: &Pubkey, amount: u64) -> Result<()> {
+    let decimals = context.mint_decimals(mint)?;
+    let instruction = burn_checked(
+        &spl_token_2022::id(),
+        &context.token_account(mint),
+        mint,
+        &context.pubkey(),
+        &[],
+        amount,
+        decimals,
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn update_rate(context: &Context, mint: &Pubkey, rate: i16) -> Result<()> {
+    let instruction = interest_bearing_mint::instruction::update_rate(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        &[],
+        rate,
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
+
+pub fn set_default_state(context: &Context, mint: &Pubkey, state: AccountState) -> Result<()> {
+    let instruction = default_account_state::instruction::update_default_account_state(
+        &spl_token_2022::id(),
+        mint,
+        &context.pubkey(),
+        &[],
+        &state,
+    )?;
+    context.send(&[instruction], &[])?;
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Subcommand implementations
+
+mod account;
+mod fee;
+mod metadata;
+mod mint;
+mod transfer;
+
+use {
+    crate::cli::{Cli, Command, MetadataCommand, RequiredMemoCommand},
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+        signer::Signer,
+    },
+    spl_token_2022::{extension::StateWithExtensions, state::Mint},
+    token_extensions_common::{
+        account::associated_token_address, ClientContext, Config, Error, Result,
+    },
+};
+
+/// Token accounts passed to a single harvest or withdraw instruction
+const MAX_ACCOUNTS_PER_INSTRUCTION: usize = 20;
+
+/// The cluster connection and the keypair every command signs with
+pub struct Context {
+    config: Config,
+    client: ClientContext,
+    payer: Keypair,
+}
+
+impl Context {
+    fn pubkey(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Associated token account of the keypair for `mint`
+    fn token_account(&self, mint: &Pubkey) -> Pubkey {
+        associated_token_address(&self.pubkey(), mint)
+    }
+
+    /// Decimals of `mint`
+    fn mint_decimals(&self, mint: &Pubkey) -> Result<u8> {
+        let data = self.client.get_account_data(mint)?;
+        let state = StateWithExtensions::<Mint>::unpack(&data).map_err(Error::unpack(*mint))?;
+
+        Ok(state.base.decimals)
+    }
+
+    /// Send `instructions` signed by the keypair and any `extra_signers`
+    fn send(&self, instructions: &[Instruction], extra_signers: &[&Keypair]) -> Result<Signature> {
+        let mut signers = vec![&self.payer];
+        signers.extend_from_slice(extra_signers);
+
+        let signature = self
+            .client
+            .send_transaction(instructions, &self.pubkey(), &signers)?;
+        println!("Signature: {signature}");
+
+        Ok(signature)
+    }
+}
+
+/// Run the parsed command
+pub async fn run(cli: Cli) -> Result<()> {
+    let config = cli.config.load()?;
+    let context = Context {
+        client: config.client(),
+        payer: config.keypair()?,
+        config,
+    };
+
+    match cli.command {
+        Command::CreateMint(args) => mint::create_mint(&context, args),
+        Command::CreateAccount { mint, owner } => account::create_account(&context, &mint, owner),
+        Command::MintTo {
+            mint,
+            amount,
+            owner,
+        } => mint::mint_to(&context, &mint, amount, owner),
+        Command::Transfer {
+            mint,
+            to,
+            amount,
+            memo,
+        } => transfer::transfer(&context, &mint, &to, amount, memo).await,
+        Command::Burn { mint, amount } => mint::burn(&context, &mint, amount),
+        Command::Harvest { mint, accounts } => fee::harvest(&context, &mint, accounts),
+        Command::WithdrawWithheld {
+            mint,
+            from_accounts,
+        } => fee::withdraw_withheld(&context, &mint, from_accounts),
+        Command::SetTransferFee {
+            mint,
+            fee_bps,
+            max_fee,
+        } => fee::set_transfer_fee(&context, &mint, fee_bps, max_fee),
+        Command::UpdateRate { mint, rate } => mint::update_rate(&context, &mint, rate),
+        Command::Freeze { mint, owner } => account::freeze(&context, &mint, &owner),
+        Command::Thaw { mint, owner } => account::thaw(&context, &mint, &owner),
+        Command::SetDefaultState { mint, state } => {
+            mint::set_default_state(&context, &mint, state.into())
+        }
+        Command::RequiredMemo { command } => match command {
+            RequiredMemoCommand::Enable { mint } => account::required_memo(&context, &mint, true),
+            RequiredMemoCommand::Disable { mint } => account::required_memo(&context, &mint, false),
+        },
+        Command::Reallocate { mint, extensions } => account::reallocate(
+            &context,
+            &mint,
+            &extensions.into_iter().map(Into::into).collect::<Vec<_>>(),
+        ),
+        Command::Metadata { command } => match command {
+            MetadataCommand::Init {
+                mint,
+                name,
+                symbol,
+                uri,
+            } => metadata::init(&context, &mint, name, symbol, uri),
+            MetadataCommand::SetField { mint, field, value } => {
+                metadata::set_field(&context, &mint, &field, value)
+            }
+            MetadataCommand::RemoveKey {
+                mint,
+                key,
+                idempotent,
+            } => metadata::remove_key(&context, &mint, key, idempotent),
+            MetadataCommand::Show { mint } => metadata::show(&context, &mint),
+        },
+    }
+}
@@ -0,0 +1,92 @@
+//! Transfers
+
+use {
+    super::Context,
+    solana_sdk::{program_error::ProgramError, pubkey::Pubkey},
+    spl_token_2022::{
+        extension::{
+            transfer_fee::{instruction::transfer_checked_with_fee, TransferFeeConfig},
+            BaseStateWithExtensions, StateWithExtensions,
+        },
+        instruction::transfer_checked,
+        state::Mint,
+    },
+    token_extensions_common::{account::associated_token_address, Error, Result},
+};
+
+/// Transfer from the keypair's associated token account to the one of `to`.
+///
+/// Mints with a transfer fee use `TransferCheckedWithFee` with the fee of the
+/// current epoch, as in the `transfer-fees` example, and the accounts
+/// required by a transfer hook are resolved as in the `transfer-hooks`
+/// example.
+pub async fn transfer(
+    context: &Context,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    memo: Option<String>,
+) -> Result<()> {
+    let mint_data = context.client.get_account_data(mint)?;
+    let mint_state =
+        StateWithExtensions::<Mint>::unpack(&mint_data).map_err(Error::unpack(*mint))?;
+    let decimals = mint_state.base.decimals;
+
+    let source = context.token_account(mint);
+    let destination = associated_token_address(to, mint);
+    let authority = context.pubkey();
+
+    let mut instruction = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => {
+            let epoch = context.client.rpc_client().get_epoch_info()?.epoch;
+            let fee = transfer_fee_config
+                .calculate_epoch_fee(epoch, amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            println!("Fee: {fee}");
+
+            transfer_checked_with_fee(
+                &spl_token_2022::id(),
+                &source,
+                mint,
+                &destination,
+                &authority,
+                &[],
+                amount,
+                decimals,
+                fee,
+            )?
+        }
+        Err(_) => transfer_checked(
+            &spl_token_2022::id(),
+            &source,
+            mint,
+            &destination,
+            &authority,
+            &[],
+            amount,
+            decimals,
+        )?,
+    };
+    context
+        .client
+        .add_transfer_hook_accounts(
+            &mut instruction,
+            &source,
+            mint,
+            &destination,
+            &authority,
+            amount,
+        )
+        .await?;
+
+    let mut instructions = Vec::with_capacity(2);
+    if let Some(memo) = memo {
+        // Token-2022 looks for the memo in the instruction right before the
+        // transfer
+        instructions.push(spl_memo::build_memo(memo.as_bytes(), &[&authority]));
+    }
+    instructions.push(instruction);
+    context.send(&instructions, &[])?;
+
+    Ok(())
+}
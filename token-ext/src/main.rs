@@ -0,0 +1,26 @@
+//! `token-ext` drives the flows demonstrated by the examples from the command
+//! line, so amounts, rates and authorities no longer need to be edited in
+//! source.
+
+mod cli;
+mod command;
+
+use {clap::Parser, cli::Cli, std::process::ExitCode, token_extensions_common::Error};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match command::run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            if let Error::Program(failure) = &error {
+                for log in &failure.logs {
+                    eprintln!("  {log}");
+                }
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
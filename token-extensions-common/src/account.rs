@@ -0,0 +1,41 @@
+//! Associated token accounts
+
+use {
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey},
+    spl_associated_token_account::get_associated_token_address_with_program_id,
+};
+
+/// Address of the Token-2022 associated token account of `owner` for `mint`
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::id())
+}
+
+/// Create the Token-2022 associated token account of `owner` for `mint`,
+/// paid for by `funder`
+pub fn create_associated_token_account(
+    funder: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account(
+        funder,
+        owner,
+        mint,
+        &spl_token_2022::id(),
+    )
+}
+
+/// Create the Token-2022 associated token account of `owner` for `mint` if
+/// it does not exist yet, paid for by `funder`
+pub fn create_associated_token_account_idempotent(
+    funder: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        funder,
+        owner,
+        mint,
+        &spl_token_2022::id(),
+    )
+}
@@ -0,0 +1,217 @@
+//! RPC client context
+
+use {
+    crate::{account, mint::MintBuilder, Error, Result},
+    solana_client::{
+        client_error::ClientError,
+        rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{
+        account::Account,
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+        signer::Signer,
+        signers::Signers,
+        transaction::Transaction,
+    },
+    spl_token_2022::offchain,
+    std::{thread, time::Duration},
+};
+
+/// URL of a local `solana-test-validator`
+pub const LOCALNET_URL: &str = "http://localhost:8899";
+
+/// Connection to a cluster along with the commitment every transaction is
+/// confirmed at
+pub struct ClientContext {
+    rpc_client: RpcClient,
+}
+
+impl ClientContext {
+    /// Connect to `url` with the default commitment
+    pub fn new(url: impl ToString) -> Self {
+        Self::new_with_commitment(url, CommitmentConfig::default())
+    }
+
+    /// Connect to `url` confirming at `commitment`
+    pub fn new_with_commitment(url: impl ToString, commitment: CommitmentConfig) -> Self {
+        Self {
+            rpc_client: RpcClient::new_with_commitment(url.to_string(), commitment),
+        }
+    }
+
+    /// Connect to a local `solana-test-validator`
+    pub fn localnet() -> Self {
+        Self::new(LOCALNET_URL)
+    }
+
+    /// The underlying RPC client
+    pub fn rpc_client(&self) -> &RpcClient {
+        &self.rpc_client
+    }
+
+    /// The commitment transactions are confirmed at
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.rpc_client.commitment()
+    }
+
+    /// Airdrop `amount` SOL to `account` if it has no balance and wait until
+    /// the airdrop is reflected
+    pub fn check_request_airdrop(&self, account: &Pubkey, amount: u64) -> Result<()> {
+        if self.rpc_client.get_balance(account)? != 0 {
+            return Ok(());
+        }
+
+        self.rpc_client
+            .request_airdrop(account, LAMPORTS_PER_SOL * amount)?;
+
+        while self.rpc_client.get_balance(account)? < LAMPORTS_PER_SOL {
+            println!("Airdrop for {} has not reflected ...", account);
+            thread::sleep(Duration::from_secs(1));
+        }
+        println!("\nAirdrop for {} has reflected!\n", account);
+
+        Ok(())
+    }
+
+    /// Sign `instructions` with a recent blockhash, send them and wait for
+    /// confirmation
+    pub fn send_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+    ) -> Result<Signature> {
+        let recent_blockhash = self.rpc_client.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(payer),
+            signers,
+            recent_blockhash,
+        );
+
+        self.rpc_client
+            .send_and_confirm_transaction_with_spinner_and_commitment(&tx, self.commitment())
+            .map_err(|error| Error::from_client_error(error, instructions))
+    }
+
+    /// Fetch `address`, failing with [`Error::AccountNotFound`] if it does
+    /// not exist
+    pub fn get_account(&self, address: &Pubkey) -> Result<Account> {
+        self.rpc_client
+            .get_account_with_commitment(address, self.commitment())?
+            .value
+            .ok_or(Error::AccountNotFound(*address))
+    }
+
+    /// Data of `address`, failing with [`Error::AccountNotFound`] if it does
+    /// not exist
+    pub fn get_account_data(&self, address: &Pubkey) -> Result<Vec<u8>> {
+        self.get_account(address).map(|account| account.data)
+    }
+
+    /// Every Token-2022 token account holding `mint`
+    pub fn get_token_accounts(&self, mint: &Pubkey) -> Result<Vec<(Pubkey, Account)>> {
+        // The mint is the first field of a token account
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                mint.as_ref(),
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                commitment: Some(self.commitment()),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        Ok(self
+            .rpc_client
+            .get_program_accounts_with_config(&spl_token_2022::id(), config)?)
+    }
+
+    /// Append the accounts required by the transfer hook of `mint`, if it has
+    /// one, to a `TransferChecked` or `TransferCheckedWithFee` instruction
+    pub async fn add_transfer_hook_accounts(
+        &self,
+        instruction: &mut Instruction,
+        source: &Pubkey,
+        mint: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let rpc_client = &self.rpc_client;
+        let commitment = self.commitment();
+        // Only a missing account is `None`, any other RPC failure aborts
+        let fetch_account_data_fn = |address: Pubkey| async move {
+            let account = rpc_client
+                .get_account_with_commitment(&address, commitment)?
+                .value;
+            Ok(account.map(|account| account.data))
+        };
+
+        offchain::add_extra_account_metas(
+            instruction,
+            source,
+            mint,
+            destination,
+            authority,
+            amount,
+            fetch_account_data_fn,
+        )
+        .await
+        .map_err(|error| match error.downcast::<ClientError>() {
+            Ok(error) => Error::from(*error),
+            Err(error) => Error::AccountResolution(error),
+        })
+    }
+
+    /// Instructions creating the mint described by `builder`, funded with
+    /// the rent for its size
+    pub fn create_mint_instructions(
+        &self,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        builder: &MintBuilder,
+    ) -> Result<Vec<Instruction>> {
+        let rent = self
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(builder.mint_len()?)?;
+
+        builder.instructions(payer, mint, rent)
+    }
+
+    /// Create the mint described by `builder` in a single transaction paid for
+    /// by `payer`
+    pub fn create_mint(
+        &self,
+        payer: &Keypair,
+        mint_account: &Keypair,
+        builder: &MintBuilder,
+    ) -> Result<Signature> {
+        let instructions =
+            self.create_mint_instructions(&payer.pubkey(), &mint_account.pubkey(), builder)?;
+
+        self.send_transaction(&instructions, &payer.pubkey(), &[payer, mint_account])
+    }
+
+    /// Create the associated token account of `owner` for `mint`, paid for
+    /// and signed by `owner`
+    pub fn create_associated_token_account(
+        &self,
+        owner: &Keypair,
+        mint: &Pubkey,
+    ) -> Result<Pubkey> {
+        let instruction =
+            account::create_associated_token_account(&owner.pubkey(), &owner.pubkey(), mint);
+        self.send_transaction(&[instruction], &owner.pubkey(), &[owner])?;
+
+        Ok(account::associated_token_address(&owner.pubkey(), mint))
+    }
+}
@@ -0,0 +1,561 @@
+//! Cluster and keypair configuration
+//!
+//! Settings are resolved from, in order of precedence, command line flags,
+//! `TOKEN_EXT_*` environment variables, a YAML config file and defaults. The
+//! config file uses the same format as the Solana CLI's `config.yml`, whose
+//! default location is also used here, so an existing Solana CLI setup works
+//! as is. It may additionally name keypairs under `signers`:
+//!
+//! ```yaml
+//! json_rpc_url: http://localhost:8899
+//! commitment: confirmed
+//! keypair_path: ~/.config/solana/id.json
+//! signers:
+//!   mint: ~/keys/mint.json
+//! ```
+
+use {
+    crate::{client::LOCALNET_URL, ClientContext, Error, Result},
+    clap::Parser,
+    serde::Deserialize,
+    solana_sdk::{
+        commitment_config::{CommitmentConfig, CommitmentLevel},
+        signature::{read_keypair_file, Keypair},
+    },
+    std::{
+        collections::BTreeMap,
+        fs, io,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Contents of the config file
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ConfigFile {
+    /// URL of the cluster's RPC endpoint
+    pub json_rpc_url: String,
+    /// Commitment level transactions are confirmed at
+    pub commitment: String,
+    /// Keypair paying for transactions
+    pub keypair_path: String,
+    /// Additional keypairs by name
+    pub signers: BTreeMap<String, String>,
+}
+
+impl Default for ConfigFile {
+    fn default() -> Self {
+        Self {
+            json_rpc_url: LOCALNET_URL.into(),
+            commitment: CommitmentLevel::Confirmed.to_string(),
+            keypair_path: "~/.config/solana/id.json".into(),
+            signers: BTreeMap::new(),
+        }
+    }
+}
+
+impl ConfigFile {
+    /// The Solana CLI config file
+    pub fn default_path() -> PathBuf {
+        expand_home("~/.config/solana/cli/config.yml")
+    }
+
+    /// Load the config file at `path`
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| Error::Config(format!("{}: {error}", path.display())))?;
+
+        Self::parse(path, &contents)
+    }
+
+    /// Parse the contents of the config file at `path`
+    pub fn parse(path: &Path, contents: &str) -> Result<Self> {
+        serde_yaml::from_str(contents)
+            .map_err(|error| Error::Config(format!("{}: {error}", path.display())))
+    }
+}
+
+/// Environment variable naming the config file
+pub const CONFIG_ENV: &str = "TOKEN_EXT_CONFIG";
+/// Environment variable overriding the RPC endpoint
+pub const URL_ENV: &str = "TOKEN_EXT_URL";
+/// Environment variable overriding the commitment level
+pub const COMMITMENT_ENV: &str = "TOKEN_EXT_COMMITMENT";
+/// Environment variable overriding the fee payer keypair
+pub const KEYPAIR_ENV: &str = "TOKEN_EXT_KEYPAIR";
+
+/// Command line flags overriding the config file, each of which may also be
+/// set through an environment variable
+#[derive(Clone, Debug, Default, Parser)]
+#[command(about = None, long_about = None)]
+pub struct ConfigArgs {
+    /// Config file [env: TOKEN_EXT_CONFIG] [default:
+    /// ~/.config/solana/cli/config.yml]
+    #[arg(long, short = 'C', global = true)]
+    pub config: Option<PathBuf>,
+    /// URL of the cluster's RPC endpoint [env: TOKEN_EXT_URL]
+    #[arg(long, short = 'u', global = true)]
+    pub url: Option<String>,
+    /// Commitment level transactions are confirmed at [env:
+    /// TOKEN_EXT_COMMITMENT]
+    #[arg(long, global = true)]
+    pub commitment: Option<CommitmentLevel>,
+    /// Keypair paying for transactions, in the Solana CLI JSON format [env:
+    /// TOKEN_EXT_KEYPAIR]
+    #[arg(long, short = 'k', global = true)]
+    pub keypair: Option<PathBuf>,
+    /// Additional keypair by name, as NAME=PATH
+    #[arg(long = "signer", global = true, value_parser = parse_signer)]
+    pub signers: Vec<(String, PathBuf)>,
+}
+
+impl ConfigArgs {
+    /// Resolve the configuration from the process environment, reading the
+    /// config file if there is one.
+    ///
+    /// A config file given explicitly must exist, while the default one is
+    /// skipped if missing.
+    pub fn load(&self) -> Result<Config> {
+        self.resolve(
+            |name| std::env::var(name).ok(),
+            |path| match fs::read_to_string(path) {
+                Ok(contents) => Ok(Some(contents)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(error),
+            },
+        )
+    }
+
+    /// Resolve the configuration with environment variables looked up
+    /// through `env` and files read through `read_file`, which returns `None`
+    /// for a file that does not exist
+    pub fn resolve(
+        &self,
+        env: impl Fn(&str) -> Option<String>,
+        read_file: impl Fn(&Path) -> io::Result<Option<String>>,
+    ) -> Result<Config> {
+        let read_config = |path: &Path| {
+            read_file(path).map_err(|error| Error::Config(format!("{}: {error}", path.display())))
+        };
+        let file = match self
+            .config
+            .clone()
+            .or_else(|| env(CONFIG_ENV).map(PathBuf::from))
+        {
+            Some(path) => {
+                let contents = read_config(&path)?.ok_or_else(|| {
+                    Error::Config(format!("{}: config file not found", path.display()))
+                })?;
+                ConfigFile::parse(&path, &contents)?
+            }
+            None => {
+                let path = ConfigFile::default_path();
+                match read_config(&path)? {
+                    Some(contents) => ConfigFile::parse(&path, &contents)?,
+                    None => ConfigFile::default(),
+                }
+            }
+        };
+
+        let commitment = match self.commitment {
+            Some(commitment) => commitment,
+            None => {
+                let (value, source) = match env(COMMITMENT_ENV) {
+                    Some(value) => (value, COMMITMENT_ENV),
+                    None => (file.commitment.clone(), "config file"),
+                };
+                value.parse().map_err(|_| {
+                    Error::Config(format!("invalid commitment `{value}` in {source}"))
+                })?
+            }
+        };
+
+        let mut signers = file
+            .signers
+            .iter()
+            .map(|(name, path)| (name.clone(), expand_home(path)))
+            .collect::<BTreeMap<_, _>>();
+        signers.extend(self.signers.iter().cloned());
+
+        Ok(Config {
+            json_rpc_url: self
+                .url
+                .clone()
+                .or_else(|| env(URL_ENV))
+                .unwrap_or(file.json_rpc_url),
+            commitment: CommitmentConfig { commitment },
+            keypair_path: self
+                .keypair
+                .clone()
+                .or_else(|| env(KEYPAIR_ENV).map(PathBuf::from))
+                .unwrap_or_else(|| expand_home(&file.keypair_path)),
+            signers,
+        })
+    }
+}
+
+/// Resolved configuration
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// URL of the cluster's RPC endpoint
+    pub json_rpc_url: String,
+    /// Commitment transactions are confirmed at
+    pub commitment: CommitmentConfig,
+    /// Keypair paying for transactions
+    pub keypair_path: PathBuf,
+    /// Additional keypairs by name
+    pub signers: BTreeMap<String, PathBuf>,
+}
+
+impl Config {
+    /// Parse the configuration flags of a binary that takes no other
+    /// arguments and resolve them
+    pub fn from_args() -> Result<Self> {
+        ConfigArgs::parse().load()
+    }
+
+    /// Connect to the configured cluster
+    pub fn client(&self) -> ClientContext {
+        ClientContext::new_with_commitment(&self.json_rpc_url, self.commitment)
+    }
+
+    /// The keypair paying for transactions
+    pub fn keypair(&self) -> Result<Keypair> {
+        read_keypair(&self.keypair_path)
+    }
+
+    /// The keypair configured as `name`, if any
+    pub fn signer(&self, name: &str) -> Result<Option<Keypair>> {
+        self.signers
+            .get(name)
+            .map(|path| read_keypair(path))
+            .transpose()
+    }
+
+    /// The keypair configured as `name`, or a new one if there is none
+    pub fn signer_or_new(&self, name: &str) -> Result<Keypair> {
+        Ok(self.signer(name)?.unwrap_or_else(Keypair::new))
+    }
+}
+
+/// Load a keypair in the Solana CLI JSON format
+pub fn read_keypair(path: &Path) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|error| Error::Keypair(format!("{}: {error}", path.display())))
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => {
+            PathBuf::from(home).join(rest.trim_start_matches(std::path::MAIN_SEPARATOR))
+        }
+        _ => PathBuf::from(path),
+    }
+}
+
+fn parse_signer(value: &str) -> std::result::Result<(String, PathBuf), String> {
+    let (name, path) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=PATH, found `{value}`"))?;
+
+    Ok((name.into(), expand_home(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashMap};
+
+    const FILE: &str = "/config.yml";
+    const FILE_CONTENTS: &str = "
+json_rpc_url: http://file:8899
+commitment: finalized
+keypair_path: /file/id.json
+signers:
+  mint: /file/mint.json
+";
+
+    fn resolve(args: &[&str], env: &[(&str, &str)], files: &[(&str, &str)]) -> Result<Config> {
+        let args = ConfigArgs::try_parse_from(std::iter::once("test").chain(args.iter().copied()))
+            .map_err(|error| Error::InvalidInput(error.to_string()))?;
+        let env = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect::<HashMap<_, _>>();
+        let files = files
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+            .collect::<HashMap<_, _>>();
+
+        args.resolve(
+            |name| env.get(name).cloned(),
+            |path| Ok(files.get(path).cloned()),
+        )
+    }
+
+    fn config(
+        json_rpc_url: &str,
+        commitment: CommitmentLevel,
+        keypair_path: &str,
+        signers: &[(&str, &str)],
+    ) -> Config {
+        Config {
+            json_rpc_url: json_rpc_url.into(),
+            commitment: CommitmentConfig { commitment },
+            keypair_path: keypair_path.into(),
+            signers: signers
+                .iter()
+                .map(|(name, path)| (name.to_string(), PathBuf::from(path)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn flags_override_env_override_file_override_defaults() {
+        let defaults = ConfigFile::default();
+        let file = [(FILE, FILE_CONTENTS)];
+        let env = [
+            (URL_ENV, "http://env:8899"),
+            (COMMITMENT_ENV, "processed"),
+            (KEYPAIR_ENV, "/env/id.json"),
+        ];
+        let flags = [
+            "--url",
+            "http://flag:8899",
+            "--commitment",
+            "confirmed",
+            "--keypair",
+            "/flag/id.json",
+            "--signer",
+            "mint=/flag/mint.json",
+        ];
+
+        for (args, env, expected) in [
+            (
+                vec![],
+                vec![],
+                config(
+                    &defaults.json_rpc_url,
+                    CommitmentLevel::Confirmed,
+                    expand_home(&defaults.keypair_path).to_str().unwrap(),
+                    &[],
+                ),
+            ),
+            (
+                vec!["--config", FILE],
+                vec![],
+                config(
+                    "http://file:8899",
+                    CommitmentLevel::Finalized,
+                    "/file/id.json",
+                    &[("mint", "/file/mint.json")],
+                ),
+            ),
+            (
+                vec![],
+                vec![(CONFIG_ENV, FILE)],
+                config(
+                    "http://file:8899",
+                    CommitmentLevel::Finalized,
+                    "/file/id.json",
+                    &[("mint", "/file/mint.json")],
+                ),
+            ),
+            (
+                vec!["--config", FILE],
+                env.to_vec(),
+                config(
+                    "http://env:8899",
+                    CommitmentLevel::Processed,
+                    "/env/id.json",
+                    &[("mint", "/file/mint.json")],
+                ),
+            ),
+            (
+                [&["--config", FILE][..], &flags].concat(),
+                env.to_vec(),
+                config(
+                    "http://flag:8899",
+                    CommitmentLevel::Confirmed,
+                    "/flag/id.json",
+                    &[("mint", "/flag/mint.json")],
+                ),
+            ),
+        ] {
+            assert_eq!(
+                resolve(&args, &env, &file).unwrap(),
+                expected,
+                "{args:?} {env:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn each_setting_overrides_on_its_own() {
+        let file = [(FILE, FILE_CONTENTS)];
+        let from_file = || {
+            config(
+                "http://file:8899",
+                CommitmentLevel::Finalized,
+                "/file/id.json",
+                &[("mint", "/file/mint.json")],
+            )
+        };
+
+        for (args, env, expected) in [
+            (
+                vec!["--url", "http://flag:8899"],
+                vec![],
+                Config {
+                    json_rpc_url: "http://flag:8899".into(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec![],
+                vec![(URL_ENV, "http://env:8899")],
+                Config {
+                    json_rpc_url: "http://env:8899".into(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec!["--commitment", "processed"],
+                vec![],
+                Config {
+                    commitment: CommitmentConfig::processed(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec![],
+                vec![(COMMITMENT_ENV, "confirmed")],
+                Config {
+                    commitment: CommitmentConfig::confirmed(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec!["--keypair", "/flag/id.json"],
+                vec![],
+                Config {
+                    keypair_path: "/flag/id.json".into(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec![],
+                vec![(KEYPAIR_ENV, "/env/id.json")],
+                Config {
+                    keypair_path: "/env/id.json".into(),
+                    ..from_file()
+                },
+            ),
+            (
+                vec!["--signer", "owner=/flag/owner.json"],
+                vec![],
+                Config {
+                    signers: [("mint", "/file/mint.json"), ("owner", "/flag/owner.json")]
+                        .into_iter()
+                        .map(|(name, path)| (name.to_string(), PathBuf::from(path)))
+                        .collect(),
+                    ..from_file()
+                },
+            ),
+        ] {
+            let args = [&["--config", FILE][..], &args].concat();
+            assert_eq!(
+                resolve(&args, &env, &file).unwrap(),
+                expected,
+                "{args:?} {env:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let bad_commitment = "commitment: eventually\n";
+        let files = [(FILE, FILE_CONTENTS), ("/bad.yml", bad_commitment)];
+
+        for (args, env, expected) in [
+            (
+                vec!["--config", "/bad.yml"],
+                vec![],
+                "invalid configuration: invalid commitment `eventually` in config file",
+            ),
+            (
+                vec!["--config", FILE],
+                vec![(COMMITMENT_ENV, "soon")],
+                "invalid configuration: invalid commitment `soon` in TOKEN_EXT_COMMITMENT",
+            ),
+            (
+                vec!["--config", "/missing.yml"],
+                vec![],
+                "invalid configuration: /missing.yml: config file not found",
+            ),
+            (
+                vec![],
+                vec![(CONFIG_ENV, "/missing.yml")],
+                "invalid configuration: /missing.yml: config file not found",
+            ),
+        ] {
+            assert_eq!(
+                resolve(&args, &env, &files).unwrap_err().to_string(),
+                expected,
+                "{args:?} {env:?}"
+            );
+        }
+
+        // Flag values are parsed by clap
+        for args in [
+            ["--commitment", "eventually"],
+            ["--signer", "mint"],
+            ["--signer", "/keys/mint.json"],
+        ] {
+            assert!(
+                matches!(resolve(&args, &[], &[]), Err(Error::InvalidInput(_))),
+                "{args:?}"
+            );
+        }
+
+        // A flag still wins over an invalid setting further down
+        let config = resolve(
+            &["--config", "/bad.yml", "--commitment", "processed"],
+            &[(COMMITMENT_ENV, "soon")],
+            &files,
+        )
+        .unwrap();
+        assert_eq!(config.commitment, CommitmentConfig::processed());
+
+        assert!(matches!(
+            resolve(
+                &["--config", "/invalid.yml"],
+                &[],
+                &[("/invalid.yml", "signers: [")]
+            ),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_keypairs_are_rejected() {
+        let path = std::env::temp_dir().join(format!("token-ext-keypair-{}", std::process::id()));
+        for contents in ["", "not a keypair", "[1, 2, 3]"] {
+            fs::write(&path, contents).unwrap();
+            assert!(
+                matches!(read_keypair(&path), Err(Error::Keypair(_))),
+                "{contents:?}"
+            );
+        }
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(read_keypair(&path), Err(Error::Keypair(_))));
+
+        let config = Config {
+            signers: [("mint".to_string(), path.clone())].into(),
+            ..config("", CommitmentLevel::Confirmed, path.to_str().unwrap(), &[])
+        };
+        assert!(matches!(config.keypair(), Err(Error::Keypair(_))));
+        assert!(matches!(config.signer("mint"), Err(Error::Keypair(_))));
+        assert!(matches!(config.signer("owner"), Ok(None)));
+    }
+}
@@ -0,0 +1,399 @@
+//! Error types
+
+use {
+    crate::validation::ExtensionDiagnostics,
+    num_traits::FromPrimitive,
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        transaction::TransactionError,
+    },
+    spl_token_2022::error::TokenError,
+    spl_transfer_hook_interface::error::TransferHookError,
+    std::{fmt, str::FromStr},
+    thiserror::Error,
+};
+
+/// Errors returned by the shared flows
+#[derive(Debug, Error)]
+pub enum Error {
+    /// An RPC request failed
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    /// A program rejected the transaction
+    #[error("transaction failed: {0}")]
+    Program(Box<ProgramFailure>),
+    /// An instruction could not be built
+    #[error("failed to build instruction: {0}")]
+    Instruction(#[from] ProgramError),
+    /// The configuration could not be loaded
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    /// A file or argument given by the user could not be parsed
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    /// A keypair could not be loaded
+    #[error("invalid keypair: {0}")]
+    Keypair(String),
+    /// An account does not exist
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// An account exists but should not
+    #[error("account {0} already exists")]
+    AccountAlreadyExists(Pubkey),
+    /// An account holds data that could not be unpacked
+    #[error("failed to unpack account {account}: {error}")]
+    Unpack {
+        /// The account
+        account: Pubkey,
+        /// Why it could not be unpacked
+        error: ProgramError,
+    },
+    /// The extra accounts required by a transfer hook could not be resolved
+    #[error("failed to resolve transfer hook accounts: {0}")]
+    AccountResolution(Box<dyn std::error::Error + Send + Sync>),
+    /// The requested extensions cannot be used together
+    #[error("incompatible extensions: {0}")]
+    IncompatibleExtensions(ExtensionDiagnostics),
+}
+
+impl Error {
+    /// Closure turning an unpack error for `account` into [`Error::Unpack`]
+    pub fn unpack(account: Pubkey) -> impl FnOnce(ProgramError) -> Self {
+        move |error| Self::Unpack { account, error }
+    }
+
+    /// Build the error for a failed transaction made of `instructions`,
+    /// decoding any program error it carries
+    pub fn from_client_error(error: ClientError, instructions: &[Instruction]) -> Self {
+        let (transaction_error, logs) = match error.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+                ..
+            }) => (simulation.err.clone(), simulation.logs.clone()),
+            ClientErrorKind::TransactionError(transaction_error) => {
+                (Some(transaction_error.clone()), None)
+            }
+            _ => (None, None),
+        };
+
+        match transaction_error {
+            Some(TransactionError::InstructionError(instruction_index, error)) => {
+                Self::Program(Box::new(ProgramFailure::new(
+                    instruction_index,
+                    error,
+                    logs.unwrap_or_default(),
+                    instructions,
+                )))
+            }
+            _ => Self::Rpc(Box::new(error)),
+        }
+    }
+
+    /// The decoded program error, if a program rejected the transaction
+    pub fn program_error(&self) -> Option<&DecodedProgramError> {
+        match self {
+            Self::Program(failure) => failure.decoded.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<ClientError> for Error {
+    fn from(error: ClientError) -> Self {
+        Self::from_client_error(error, &[])
+    }
+}
+
+/// Result alias for the shared flows
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A custom program error code decoded into the error enum of the program
+/// that returned it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedProgramError {
+    /// Returned by Token-2022
+    Token(TokenError),
+    /// Returned by a transfer hook program
+    TransferHook(TransferHookError),
+}
+
+impl DecodedProgramError {
+    /// Decode `code` returned by `program_id`.
+    ///
+    /// Transfer hook errors are recognized from any program since their
+    /// codes are derived from a hash, while token errors are only decoded
+    /// when Token-2022 is known to have returned them.
+    pub fn decode(program_id: Option<&Pubkey>, code: u32) -> Option<Self> {
+        if let Some(error) = TransferHookError::from_u32(code) {
+            return Some(Self::TransferHook(error));
+        }
+
+        if program_id == Some(&spl_token_2022::id()) {
+            return TokenError::from_u32(code).map(Self::Token);
+        }
+
+        None
+    }
+}
+
+impl fmt::Display for DecodedProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token(error) => write!(f, "TokenError::{error:?}: {error}"),
+            Self::TransferHook(error) => write!(f, "TransferHookError::{error:?}: {error}"),
+        }
+    }
+}
+
+/// An instruction error returned while processing a transaction
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramFailure {
+    /// Index of the failed instruction in the transaction
+    pub instruction_index: u8,
+    /// The program that returned the error.
+    ///
+    /// Taken from the logs where available, so an error returned by a
+    /// transfer hook invoked by Token-2022 is attributed to the hook.
+    pub program_id: Option<Pubkey>,
+    /// The raw error
+    pub error: InstructionError,
+    /// The custom error code decoded, if the program is known
+    pub decoded: Option<DecodedProgramError>,
+    /// Logs of the simulated transaction, empty if preflight was skipped
+    pub logs: Vec<String>,
+}
+
+impl ProgramFailure {
+    fn new(
+        instruction_index: u8,
+        error: InstructionError,
+        logs: Vec<String>,
+        instructions: &[Instruction],
+    ) -> Self {
+        let program_id = failed_program_in_logs(&logs).or_else(|| {
+            instructions
+                .get(instruction_index as usize)
+                .map(|instruction| instruction.program_id)
+        });
+        let decoded = match error {
+            InstructionError::Custom(code) => {
+                DecodedProgramError::decode(program_id.as_ref(), code)
+            }
+            _ => None,
+        };
+
+        Self {
+            instruction_index,
+            program_id,
+            error,
+            decoded,
+            logs,
+        }
+    }
+}
+
+impl fmt::Display for ProgramFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction {}", self.instruction_index)?;
+        if let Some(program_id) = &self.program_id {
+            write!(f, " in program {program_id}")?;
+        }
+        match &self.decoded {
+            Some(decoded) => write!(f, " failed with {decoded}"),
+            None => write!(f, " failed: {}", self.error),
+        }
+    }
+}
+
+/// The program that failed first, which is the innermost one when the error
+/// was returned from a cross-program invocation
+fn failed_program_in_logs(logs: &[String]) -> Option<Pubkey> {
+    logs.iter().find_map(|log| {
+        let (program_id, _) = log.strip_prefix("Program ")?.split_once(" failed: ")?;
+        Pubkey::from_str(program_id).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_client::rpc_response::RpcSimulateTransactionResult,
+        solana_sdk::instruction::AccountMeta,
+    };
+
+    const HOOK_PROGRAM_ID: &str = "4cT6hxSHqyDVEAu6ZWrqJnxWr9d4CFJi9t4Y9mAzXjTs";
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn failed_program_is_the_innermost_one() {
+        let token = spl_token_2022::id().to_string();
+        let hook = HOOK_PROGRAM_ID;
+        let invoke = |program: &str, depth| format!("Program {program} invoke [{depth}]");
+        let failed =
+            |program: &str| format!("Program {program} failed: custom program error: 0x7d0");
+
+        for (lines, expected) in [
+            (vec![], None),
+            (
+                vec![invoke(&token, 1), format!("Program {token} success")],
+                None,
+            ),
+            (
+                vec![invoke(&token, 1), failed(&token)],
+                Some(spl_token_2022::id()),
+            ),
+            (
+                vec![
+                    invoke(&token, 1),
+                    invoke(hook, 2),
+                    "Program log: Instruction: Execute".to_string(),
+                    failed(hook),
+                    failed(&token),
+                ],
+                Some(Pubkey::from_str(hook).unwrap()),
+            ),
+            (vec!["Program not-a-key failed: oops".to_string()], None),
+        ] {
+            assert_eq!(failed_program_in_logs(&lines), expected, "{lines:?}");
+        }
+    }
+
+    #[test]
+    fn custom_codes_decode_by_program() {
+        let token = spl_token_2022::id();
+        let other = Pubkey::new_unique();
+        let hook_code = TransferHookError::ProgramCalledOutsideOfTransfer as u32;
+        let token_code = TokenError::InsufficientFunds as u32;
+
+        for (program_id, code, expected) in [
+            (
+                Some(&other),
+                hook_code,
+                Some(DecodedProgramError::TransferHook(
+                    TransferHookError::ProgramCalledOutsideOfTransfer,
+                )),
+            ),
+            (
+                None,
+                hook_code,
+                Some(DecodedProgramError::TransferHook(
+                    TransferHookError::ProgramCalledOutsideOfTransfer,
+                )),
+            ),
+            (
+                Some(&token),
+                token_code,
+                Some(DecodedProgramError::Token(TokenError::InsufficientFunds)),
+            ),
+            (Some(&other), token_code, None),
+            (None, token_code, None),
+            (Some(&token), u32::MAX, None),
+        ] {
+            assert_eq!(
+                DecodedProgramError::decode(program_id, code),
+                expected,
+                "{program_id:?} {code}"
+            );
+        }
+    }
+
+    fn preflight_failure(err: TransactionError, logs: Vec<String>) -> ClientError {
+        RpcError::RpcResponseError {
+            code: -32002,
+            message: "Transaction simulation failed".to_string(),
+            data: RpcResponseErrorData::SendTransactionPreflightFailure(
+                RpcSimulateTransactionResult {
+                    err: Some(err),
+                    logs: Some(logs),
+                    accounts: None,
+                    units_consumed: None,
+                    loaded_accounts_data_size: None,
+                    return_data: None,
+                    inner_instructions: None,
+                    replacement_blockhash: None,
+                },
+            ),
+        }
+        .into()
+    }
+
+    #[test]
+    fn client_errors_become_program_failures() {
+        let hook = Pubkey::from_str(HOOK_PROGRAM_ID).unwrap();
+        let instructions = [
+            Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]),
+            Instruction::new_with_bytes(
+                spl_token_2022::id(),
+                &[],
+                vec![AccountMeta::new(Pubkey::new_unique(), false)],
+            ),
+        ];
+        let token_error =
+            |code: u32| TransactionError::InstructionError(1, InstructionError::Custom(code));
+
+        // The hook named in the logs takes precedence over the instruction
+        let error = Error::from_client_error(
+            preflight_failure(
+                token_error(TransferHookError::IncorrectAccount as u32),
+                logs(&[&format!(
+                    "Program {hook} failed: custom program error: 0x..."
+                )]),
+            ),
+            &instructions,
+        );
+        let Error::Program(failure) = &error else {
+            panic!("expected a program failure, found {error:?}");
+        };
+        assert_eq!(failure.instruction_index, 1);
+        assert_eq!(failure.program_id, Some(hook));
+        assert_eq!(
+            error.program_error(),
+            Some(&DecodedProgramError::TransferHook(
+                TransferHookError::IncorrectAccount
+            ))
+        );
+
+        // Without logs the failed instruction names the program
+        let error = Error::from_client_error(
+            token_error(TokenError::AccountFrozen as u32).into(),
+            &instructions,
+        );
+        assert_eq!(
+            error.program_error(),
+            Some(&DecodedProgramError::Token(TokenError::AccountFrozen))
+        );
+
+        let error = Error::from_client_error(
+            TransactionError::InstructionError(0, InstructionError::InvalidSeeds).into(),
+            &instructions,
+        );
+        assert!(matches!(&error, Error::Program(failure) if failure.decoded.is_none()));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "transaction failed: instruction 0 in program {} failed: {}",
+                instructions[0].program_id,
+                InstructionError::InvalidSeeds
+            )
+        );
+
+        for error in [
+            TransactionError::BlockhashNotFound.into(),
+            RpcError::RpcRequestError("timed out".to_string()).into(),
+        ] {
+            assert!(matches!(
+                Error::from_client_error(error, &instructions),
+                Error::Rpc(_)
+            ));
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Helpers shared by the Token-2022 extension examples
+//!
+//! Every example follows the same shape: fund the payers, create a mint
+//! account sized for its extensions, initialize the extensions followed by
+//! the mint, create the associated token accounts and then exercise the
+//! extension. The pieces of that flow live here so they can be reused outside
+//! of the example binaries.
+
+pub mod account;
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod mint;
+pub mod validation;
+
+pub use {
+    client::ClientContext,
+    config::Config,
+    error::{Error, Result},
+};
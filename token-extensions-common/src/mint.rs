@@ -0,0 +1,324 @@
+//! Mint account creation
+
+use {
+    crate::validation::{validate_extensions, ExtensionDiagnostics},
+    solana_sdk::{
+        instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, system_instruction,
+    },
+    spl_token_2022::{
+        extension::{
+            default_account_state, interest_bearing_mint, metadata_pointer,
+            transfer_fee::{self, MAX_FEE_BASIS_POINTS},
+            transfer_hook, ExtensionType,
+        },
+        instruction::{initialize_non_transferable_mint, initialize_permanent_delegate},
+        state::{AccountState, Mint},
+    },
+};
+
+/// Size of a mint account holding the given extensions
+pub fn mint_len(extensions: &[ExtensionType]) -> Result<usize, ProgramError> {
+    ExtensionType::try_calculate_account_len::<Mint>(extensions)
+}
+
+/// Create an account owned by Token-2022 to hold a mint of `space` bytes
+pub fn create_mint_account(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    lamports: u64,
+    space: usize,
+) -> Instruction {
+    system_instruction::create_account(payer, mint, lamports, space as u64, &spl_token_2022::id())
+}
+
+/// A mint extension along with the parameters it is initialized with
+#[derive(Clone, Debug, PartialEq)]
+pub enum MintExtension {
+    /// Fee withheld on every transfer
+    TransferFee {
+        /// Authority allowed to change the fee
+        transfer_fee_config_authority: Option<Pubkey>,
+        /// Authority allowed to withdraw withheld fees
+        withdraw_withheld_authority: Option<Pubkey>,
+        /// Fee in basis points (100 = 1%)
+        fee_basis_points: u16,
+        /// Maximum fee in token base units
+        max_fee: u64,
+    },
+    /// Program invoked on every transfer
+    TransferHook {
+        /// Authority allowed to change the hook program
+        authority: Option<Pubkey>,
+        /// The hook program
+        program_id: Option<Pubkey>,
+    },
+    /// Pointer to the account holding the token metadata
+    MetadataPointer {
+        /// Authority allowed to change the pointer
+        authority: Option<Pubkey>,
+        /// The account holding the metadata, usually the mint itself
+        metadata_address: Option<Pubkey>,
+    },
+    /// Interest accrued on the UI amount
+    InterestBearing {
+        /// Authority allowed to change the rate
+        rate_authority: Option<Pubkey>,
+        /// Rate in basis points
+        rate: i16,
+    },
+    /// State new token accounts start in
+    DefaultAccountState(AccountState),
+    /// Delegate with unlimited authority over every token account of the mint
+    PermanentDelegate(Pubkey),
+    /// Tokens can only be minted, burned or closed
+    NonTransferable,
+}
+
+impl MintExtension {
+    /// The extension type initialized by this extension
+    pub fn extension_type(&self) -> ExtensionType {
+        match self {
+            Self::TransferFee { .. } => ExtensionType::TransferFeeConfig,
+            Self::TransferHook { .. } => ExtensionType::TransferHook,
+            Self::MetadataPointer { .. } => ExtensionType::MetadataPointer,
+            Self::InterestBearing { .. } => ExtensionType::InterestBearingConfig,
+            Self::DefaultAccountState(_) => ExtensionType::DefaultAccountState,
+            Self::PermanentDelegate(_) => ExtensionType::PermanentDelegate,
+            Self::NonTransferable => ExtensionType::NonTransferable,
+        }
+    }
+
+    /// Instruction initializing the extension on `mint`
+    pub fn instruction(&self, mint: &Pubkey) -> Result<Instruction, ProgramError> {
+        let token_program_id = spl_token_2022::id();
+
+        match self {
+            Self::TransferFee {
+                transfer_fee_config_authority,
+                withdraw_withheld_authority,
+                fee_basis_points,
+                max_fee,
+            } => transfer_fee::instruction::initialize_transfer_fee_config(
+                &token_program_id,
+                mint,
+                transfer_fee_config_authority.as_ref(),
+                withdraw_withheld_authority.as_ref(),
+                *fee_basis_points,
+                *max_fee,
+            ),
+            Self::TransferHook {
+                authority,
+                program_id,
+            } => transfer_hook::instruction::initialize(
+                &token_program_id,
+                mint,
+                *authority,
+                *program_id,
+            ),
+            Self::MetadataPointer {
+                authority,
+                metadata_address,
+            } => metadata_pointer::instruction::initialize(
+                &token_program_id,
+                mint,
+                *authority,
+                *metadata_address,
+            ),
+            Self::InterestBearing {
+                rate_authority,
+                rate,
+            } => interest_bearing_mint::instruction::initialize(
+                &token_program_id,
+                mint,
+                *rate_authority,
+                *rate,
+            ),
+            Self::DefaultAccountState(state) => {
+                default_account_state::instruction::initialize_default_account_state(
+                    &token_program_id,
+                    mint,
+                    state,
+                )
+            }
+            Self::PermanentDelegate(delegate) => {
+                initialize_permanent_delegate(&token_program_id, mint, delegate)
+            }
+            Self::NonTransferable => initialize_non_transferable_mint(&token_program_id, mint),
+        }
+    }
+}
+
+/// Builds the instructions creating a mint with any combination of
+/// extensions.
+///
+/// The helper methods use the mint authority for every extension authority,
+/// which is what the examples do; use [`MintBuilder::extension`] for full
+/// control. Adding an extension that is already present replaces it.
+#[derive(Clone, Debug)]
+pub struct MintBuilder {
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+    decimals: u8,
+    extensions: Vec<MintExtension>,
+}
+
+impl MintBuilder {
+    /// A mint without extensions, with `mint_authority` also acting as the
+    /// freeze authority
+    pub fn new(mint_authority: Pubkey, decimals: u8) -> Self {
+        Self {
+            mint_authority,
+            freeze_authority: Some(mint_authority),
+            decimals,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Set the freeze authority
+    pub fn freeze_authority(mut self, freeze_authority: Option<Pubkey>) -> Self {
+        self.freeze_authority = freeze_authority;
+        self
+    }
+
+    /// Add an extension, replacing any extension of the same type
+    pub fn extension(mut self, extension: MintExtension) -> Self {
+        let extension_type = extension.extension_type();
+        self.extensions
+            .retain(|existing| existing.extension_type() != extension_type);
+        self.extensions.push(extension);
+        self
+    }
+
+    /// Add a transfer fee of `fee_basis_points` capped at `max_fee`
+    pub fn transfer_fee(self, fee_basis_points: u16, max_fee: u64) -> Self {
+        let authority = Some(self.mint_authority);
+        self.extension(MintExtension::TransferFee {
+            transfer_fee_config_authority: authority,
+            withdraw_withheld_authority: authority,
+            fee_basis_points,
+            max_fee,
+        })
+    }
+
+    /// Invoke `program_id` on every transfer
+    pub fn transfer_hook(self, program_id: Pubkey) -> Self {
+        let authority = Some(self.mint_authority);
+        self.extension(MintExtension::TransferHook {
+            authority,
+            program_id: Some(program_id),
+        })
+    }
+
+    /// Point the token metadata at `metadata_address`
+    pub fn metadata_pointer(self, metadata_address: Pubkey) -> Self {
+        let authority = Some(self.mint_authority);
+        self.extension(MintExtension::MetadataPointer {
+            authority,
+            metadata_address: Some(metadata_address),
+        })
+    }
+
+    /// Accrue interest at `rate` basis points
+    pub fn interest_bearing(self, rate: i16) -> Self {
+        let rate_authority = Some(self.mint_authority);
+        self.extension(MintExtension::InterestBearing {
+            rate_authority,
+            rate,
+        })
+    }
+
+    /// Create new token accounts in `state`
+    pub fn default_account_state(self, state: AccountState) -> Self {
+        self.extension(MintExtension::DefaultAccountState(state))
+    }
+
+    /// Give `delegate` unlimited authority over every token account
+    pub fn permanent_delegate(self, delegate: Pubkey) -> Self {
+        self.extension(MintExtension::PermanentDelegate(delegate))
+    }
+
+    /// Make the tokens non-transferable
+    pub fn non_transferable(self) -> Self {
+        self.extension(MintExtension::NonTransferable)
+    }
+
+    /// The extensions added so far, in the order they are initialized
+    pub fn extensions(&self) -> Vec<&MintExtension> {
+        let mut extensions = self.extensions.iter().collect::<Vec<_>>();
+        extensions.sort_by_key(|extension| u16::from(extension.extension_type()));
+        extensions
+    }
+
+    /// The extension types added so far, in the order they are initialized
+    pub fn extension_types(&self) -> Vec<ExtensionType> {
+        self.extensions()
+            .into_iter()
+            .map(MintExtension::extension_type)
+            .collect()
+    }
+
+    /// Size of the mint account
+    pub fn mint_len(&self) -> Result<usize, ProgramError> {
+        mint_len(&self.extension_types())
+    }
+
+    /// Check the extensions for compatibility and for the authorities they
+    /// rely on
+    pub fn validate(&self) -> ExtensionDiagnostics {
+        let mut diagnostics = validate_extensions(&self.extension_types(), &[]);
+
+        for extension in &self.extensions {
+            match extension {
+                MintExtension::TransferFee {
+                    fee_basis_points, ..
+                } if *fee_basis_points > MAX_FEE_BASIS_POINTS => diagnostics.error(
+                    &[ExtensionType::TransferFeeConfig],
+                    format!("fee cannot exceed {MAX_FEE_BASIS_POINTS} basis points"),
+                ),
+                MintExtension::DefaultAccountState(AccountState::Frozen)
+                    if self.freeze_authority.is_none() =>
+                {
+                    diagnostics.error(
+                        &[ExtensionType::DefaultAccountState],
+                        "accounts created frozen can never be thawed without a freeze authority",
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Instructions to create the mint account, initialize every extension and
+    /// then initialize the mint itself.
+    ///
+    /// The extensions are validated first and any error is returned as
+    /// [`Error::IncompatibleExtensions`](crate::Error::IncompatibleExtensions).
+    /// Token-2022 requires extensions to be initialized before
+    /// `InitializeMint`, so that instruction always comes last. `lamports`
+    /// may exceed the rent for [`MintBuilder::mint_len`] to pre-fund
+    /// variable-length extensions added after initialization.
+    pub fn instructions(
+        &self,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        lamports: u64,
+    ) -> crate::Result<Vec<Instruction>> {
+        self.validate().check()?;
+
+        let mut instructions = vec![create_mint_account(payer, mint, lamports, self.mint_len()?)];
+        for extension in self.extensions() {
+            instructions.push(extension.instruction(mint)?);
+        }
+        instructions.push(spl_token_2022::instruction::initialize_mint(
+            &spl_token_2022::id(),
+            mint,
+            &self.mint_authority,
+            self.freeze_authority.as_ref(),
+            self.decimals,
+        )?);
+
+        Ok(instructions)
+    }
+}
@@ -0,0 +1,438 @@
+//! Extension compatibility checks run before any transaction is built
+//!
+//! Token-2022 rejects some extension combinations with a bare
+//! `InvalidExtensionCombination`, accepts others that can never work, and
+//! leaves it to the caller to configure the authorities each extension needs.
+//! [`validate_extensions`] reports all of that up front.
+
+use {
+    crate::{Error, Result},
+    spl_token_2022::{
+        error::TokenError,
+        extension::{AccountType, ExtensionType},
+    },
+    std::fmt,
+};
+
+/// A single finding about a set of extensions
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// The extensions involved
+    pub extensions: Vec<ExtensionType>,
+    /// What is wrong with them
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(extensions: &[ExtensionType], message: impl Into<String>) -> Self {
+        Self {
+            extensions: extensions.to_vec(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:?}", self.message, self.extensions)
+    }
+}
+
+/// An authority an extension can be configured with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityRole {
+    /// Freezes and thaws token accounts
+    Freeze,
+    /// Closes the mint
+    CloseMint,
+    /// Updates the transfer fee
+    TransferFeeConfig,
+    /// Withdraws withheld transfer fees
+    WithdrawWithheld,
+    /// Approves confidential transfer accounts
+    ConfidentialTransfer,
+    /// Changes the transfer hook program
+    TransferHook,
+    /// Updates the interest rate
+    InterestRate,
+    /// Transfers or burns from any token account
+    PermanentDelegate,
+    /// Changes the metadata pointer
+    MetadataPointer,
+    /// Changes the group pointer
+    GroupPointer,
+    /// Changes the group member pointer
+    GroupMemberPointer,
+}
+
+/// An authority the mint should be configured with
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuggestedAuthority {
+    /// The authority
+    pub role: AuthorityRole,
+    /// Whether the extension is unusable without it
+    pub required: bool,
+    /// Why it is suggested
+    pub reason: &'static str,
+}
+
+/// Everything found while validating a set of mint and account extensions
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExtensionDiagnostics {
+    /// Combinations that Token-2022 rejects or that can never work
+    pub errors: Vec<Diagnostic>,
+    /// Combinations that work but are probably not what was intended
+    pub warnings: Vec<Diagnostic>,
+    /// Authorities the mint extensions need
+    pub authorities: Vec<SuggestedAuthority>,
+}
+
+impl ExtensionDiagnostics {
+    /// Whether no errors were found
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Turn any errors into [`Error::IncompatibleExtensions`]
+    pub fn check(self) -> Result<Self> {
+        if self.is_valid() {
+            Ok(self)
+        } else {
+            Err(Error::IncompatibleExtensions(self))
+        }
+    }
+
+    pub(crate) fn error(&mut self, extensions: &[ExtensionType], message: impl Into<String>) {
+        self.errors.push(Diagnostic::new(extensions, message));
+    }
+
+    pub(crate) fn warning(&mut self, extensions: &[ExtensionType], message: impl Into<String>) {
+        self.warnings.push(Diagnostic::new(extensions, message));
+    }
+
+    fn authority(&mut self, role: AuthorityRole, required: bool, reason: &'static str) {
+        self.authorities.push(SuggestedAuthority {
+            role,
+            required,
+            reason,
+        });
+    }
+}
+
+impl fmt::Display for ExtensionDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, error) in self.errors.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Validate the extensions proposed for a mint and for the token accounts
+/// that will hold it
+pub fn validate_extensions(
+    mint_extensions: &[ExtensionType],
+    account_extensions: &[ExtensionType],
+) -> ExtensionDiagnostics {
+    let mut diagnostics = ExtensionDiagnostics::default();
+
+    check_account_types(&mut diagnostics, mint_extensions, AccountType::Mint);
+    check_account_types(&mut diagnostics, account_extensions, AccountType::Account);
+    check_mint_combinations(&mut diagnostics, mint_extensions);
+    check_account_combinations(&mut diagnostics, mint_extensions, account_extensions);
+    suggest_authorities(&mut diagnostics, mint_extensions);
+
+    diagnostics
+}
+
+fn check_account_types(
+    diagnostics: &mut ExtensionDiagnostics,
+    extensions: &[ExtensionType],
+    account_type: AccountType,
+) {
+    for (index, extension) in extensions.iter().enumerate() {
+        if extensions[..index].contains(extension) {
+            diagnostics.error(&[*extension], "extension is listed more than once");
+        }
+
+        match extension.get_account_type() {
+            AccountType::Uninitialized => {
+                diagnostics.error(&[*extension], "not an extension");
+            }
+            found if found != account_type => {
+                diagnostics.error(
+                    &[*extension],
+                    format!("{found:?} extension cannot be applied to a {account_type:?}"),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_mint_combinations(diagnostics: &mut ExtensionDiagnostics, mint: &[ExtensionType]) {
+    let has = |extension| mint.contains(&extension);
+
+    match ExtensionType::check_for_invalid_mint_extension_combinations(mint) {
+        Ok(()) => {}
+        Err(TokenError::InvalidExtensionCombination) => diagnostics.error(
+            &[
+                ExtensionType::TransferFeeConfig,
+                ExtensionType::ConfidentialTransferMint,
+                ExtensionType::ConfidentialTransferFeeConfig,
+            ],
+            "confidential transfers with fees need all three extensions",
+        ),
+        Err(error) => diagnostics.error(mint, error.to_string()),
+    }
+
+    for (other, message) in [
+        (
+            ExtensionType::TransferFeeConfig,
+            "fees can never be charged on a non-transferable mint",
+        ),
+        (
+            ExtensionType::TransferHook,
+            "the hook can never be invoked on a non-transferable mint",
+        ),
+        (
+            ExtensionType::ConfidentialTransferMint,
+            "confidential transfers are impossible on a non-transferable mint",
+        ),
+    ] {
+        if has(ExtensionType::NonTransferable) && has(other) {
+            diagnostics.error(&[ExtensionType::NonTransferable, other], message);
+        }
+    }
+
+    if has(ExtensionType::TokenMetadata) {
+        diagnostics.error(
+            &[ExtensionType::TokenMetadata],
+            "token metadata is variable-length: size the mint for the metadata pointer and \
+             initialize the metadata after the mint",
+        );
+        if !has(ExtensionType::MetadataPointer) {
+            diagnostics.warning(
+                &[ExtensionType::TokenMetadata, ExtensionType::MetadataPointer],
+                "wallets look up token metadata through the metadata pointer",
+            );
+        }
+    }
+
+    if has(ExtensionType::InterestBearingConfig) && has(ExtensionType::TransferFeeConfig) {
+        diagnostics.warning(
+            &[
+                ExtensionType::InterestBearingConfig,
+                ExtensionType::TransferFeeConfig,
+            ],
+            "fees are charged on the raw amount, not the interest-adjusted UI amount",
+        );
+    }
+}
+
+fn check_account_combinations(
+    diagnostics: &mut ExtensionDiagnostics,
+    mint: &[ExtensionType],
+    account: &[ExtensionType],
+) {
+    let required = ExtensionType::get_required_init_account_extensions(mint);
+
+    for extension in account {
+        if required.contains(extension) {
+            diagnostics.warning(
+                &[*extension],
+                "added automatically when the token account is initialized",
+            );
+        }
+    }
+
+    for (account_extension, mint_extension) in [
+        (
+            ExtensionType::TransferFeeAmount,
+            ExtensionType::TransferFeeConfig,
+        ),
+        (
+            ExtensionType::NonTransferableAccount,
+            ExtensionType::NonTransferable,
+        ),
+        (
+            ExtensionType::TransferHookAccount,
+            ExtensionType::TransferHook,
+        ),
+        (
+            ExtensionType::ConfidentialTransferAccount,
+            ExtensionType::ConfidentialTransferMint,
+        ),
+        (
+            ExtensionType::ConfidentialTransferFeeAmount,
+            ExtensionType::ConfidentialTransferFeeConfig,
+        ),
+    ] {
+        if account.contains(&account_extension) && !mint.contains(&mint_extension) {
+            diagnostics.error(
+                &[account_extension, mint_extension],
+                "account extension needs the matching mint extension",
+            );
+        }
+    }
+
+    let immutable_owner = account.contains(&ExtensionType::ImmutableOwner)
+        || required.contains(&ExtensionType::ImmutableOwner);
+    if immutable_owner && mint.contains(&ExtensionType::PermanentDelegate) {
+        diagnostics.warning(
+            &[
+                ExtensionType::PermanentDelegate,
+                ExtensionType::ImmutableOwner,
+            ],
+            "an immutable owner does not stop the permanent delegate from transferring or \
+             burning tokens",
+        );
+    }
+
+    if account.contains(&ExtensionType::MemoTransfer)
+        && mint.contains(&ExtensionType::NonTransferable)
+    {
+        diagnostics.warning(
+            &[ExtensionType::MemoTransfer, ExtensionType::NonTransferable],
+            "required memos are never checked because tokens cannot be transferred in",
+        );
+    }
+}
+
+fn suggest_authorities(diagnostics: &mut ExtensionDiagnostics, mint: &[ExtensionType]) {
+    for extension in mint {
+        match extension {
+            ExtensionType::DefaultAccountState => diagnostics.authority(
+                AuthorityRole::Freeze,
+                true,
+                "accounts created frozen can only be thawed by the freeze authority",
+            ),
+            ExtensionType::MintCloseAuthority => diagnostics.authority(
+                AuthorityRole::CloseMint,
+                true,
+                "the mint can only be closed by its close authority",
+            ),
+            ExtensionType::TransferFeeConfig => {
+                diagnostics.authority(
+                    AuthorityRole::TransferFeeConfig,
+                    false,
+                    "without it the fee can never change",
+                );
+                diagnostics.authority(
+                    AuthorityRole::WithdrawWithheld,
+                    true,
+                    "withheld fees can only be withdrawn by this authority",
+                );
+            }
+            ExtensionType::ConfidentialTransferMint => diagnostics.authority(
+                AuthorityRole::ConfidentialTransfer,
+                false,
+                "needed to approve accounts unless they are auto-approved",
+            ),
+            ExtensionType::TransferHook => diagnostics.authority(
+                AuthorityRole::TransferHook,
+                false,
+                "without it the hook program can never be replaced or removed",
+            ),
+            ExtensionType::InterestBearingConfig => diagnostics.authority(
+                AuthorityRole::InterestRate,
+                false,
+                "without it the rate can never change",
+            ),
+            ExtensionType::PermanentDelegate => diagnostics.authority(
+                AuthorityRole::PermanentDelegate,
+                true,
+                "the delegate is the whole point of the extension",
+            ),
+            ExtensionType::MetadataPointer => diagnostics.authority(
+                AuthorityRole::MetadataPointer,
+                false,
+                "without it the metadata can never be moved",
+            ),
+            ExtensionType::GroupPointer => diagnostics.authority(
+                AuthorityRole::GroupPointer,
+                false,
+                "without it the group can never be moved",
+            ),
+            ExtensionType::GroupMemberPointer => diagnostics.authority(
+                AuthorityRole::GroupMemberPointer,
+                false,
+                "without it the group membership can never be moved",
+            ),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ExtensionType::*};
+
+    fn errors(mint: &[ExtensionType]) -> Vec<Diagnostic> {
+        let mut diagnostics = ExtensionDiagnostics::default();
+        check_mint_combinations(&mut diagnostics, mint);
+        diagnostics.errors
+    }
+
+    #[test]
+    fn allowed_mint_combinations() {
+        for mint in [
+            &[][..],
+            &[TransferFeeConfig],
+            &[ConfidentialTransferMint],
+            &[
+                TransferFeeConfig,
+                ConfidentialTransferMint,
+                ConfidentialTransferFeeConfig,
+            ],
+            &[TransferHook, PermanentDelegate, MetadataPointer],
+            &[NonTransferable, PermanentDelegate],
+            &[InterestBearingConfig, TransferFeeConfig],
+        ] {
+            assert_eq!(errors(mint), vec![], "{mint:?}");
+        }
+    }
+
+    #[test]
+    fn rejected_mint_combinations() {
+        let confidential_fees = &[
+            TransferFeeConfig,
+            ConfidentialTransferMint,
+            ConfidentialTransferFeeConfig,
+        ][..];
+        for (mint, extensions) in [
+            (
+                &[TransferFeeConfig, ConfidentialTransferMint][..],
+                confidential_fees,
+            ),
+            (&[ConfidentialTransferFeeConfig], confidential_fees),
+            (
+                &[TransferFeeConfig, ConfidentialTransferFeeConfig],
+                confidential_fees,
+            ),
+            (
+                &[NonTransferable, TransferFeeConfig],
+                &[NonTransferable, TransferFeeConfig],
+            ),
+            (
+                &[NonTransferable, TransferHook],
+                &[NonTransferable, TransferHook],
+            ),
+            (
+                &[NonTransferable, ConfidentialTransferMint],
+                &[NonTransferable, ConfidentialTransferMint],
+            ),
+            (&[MetadataPointer, TokenMetadata], &[TokenMetadata]),
+        ] {
+            let found = errors(mint)
+                .into_iter()
+                .map(|error| error.extensions)
+                .collect::<Vec<_>>();
+            assert_eq!(found, vec![extensions.to_vec()], "{mint:?}");
+        }
+    }
+}
@@ -0,0 +1,64 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::{
+        error::TokenError,
+        extension::default_account_state::instruction::update_default_account_state,
+        instruction::thaw_account, state::AccountState,
+    },
+    token_extensions_common::mint::MintBuilder,
+};
+
+#[tokio::test]
+async fn accounts_start_frozen_until_thawed() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let builder = MintBuilder::new(payer, 0).default_account_state(AccountState::Frozen);
+    let mint = create_mint(&context, &builder).await.pubkey();
+
+    let account = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    assert_eq!(
+        get_token_account(&context, &account).await.base.state,
+        AccountState::Frozen
+    );
+
+    let error = mint_tokens(&context, &mint, &account, 100)
+        .await
+        .unwrap_err();
+    assert_eq!(custom_error(error), TokenError::AccountFrozen as u32);
+
+    let thaw = thaw_account(&spl_token_2022::id(), &account, &mint, &payer, &[]).unwrap();
+    process(&context, &[thaw], &[]).await.unwrap();
+    mint_tokens(&context, &mint, &account, 50).await.unwrap();
+
+    let account = get_token_account(&context, &account).await;
+    assert_eq!(account.base.state, AccountState::Initialized);
+    assert_eq!(account.base.amount, 50);
+}
+
+#[tokio::test]
+async fn updated_default_state_applies_to_new_accounts() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let builder = MintBuilder::new(payer, 0).default_account_state(AccountState::Frozen);
+    let mint = create_mint(&context, &builder).await.pubkey();
+
+    let update = update_default_account_state(
+        &spl_token_2022::id(),
+        &mint,
+        &payer,
+        &[],
+        &AccountState::Initialized,
+    )
+    .unwrap();
+    process(&context, &[update], &[]).await.unwrap();
+
+    let account = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    assert_eq!(
+        get_token_account(&context, &account).await.base.state,
+        AccountState::Initialized
+    );
+    mint_tokens(&context, &mint, &account, 100).await.unwrap();
+}
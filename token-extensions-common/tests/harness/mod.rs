@@ -0,0 +1,146 @@
+//! In-process bank shared by the integration tests
+//!
+//! Token-2022, the associated token account program and the memo program
+//! are the builds bundled with `solana-program-test`.
+
+#![allow(dead_code)]
+
+use {
+    solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext},
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_token_2022::{
+        extension::StateWithExtensionsOwned,
+        instruction::mint_to,
+        state::{Account, Mint},
+    },
+    token_extensions_common::{
+        account::{associated_token_address, create_associated_token_account},
+        mint::MintBuilder,
+    },
+};
+
+/// Start a bank with the bundled programs loaded
+pub async fn start() -> ProgramTestContext {
+    ProgramTest::default().start_with_context().await
+}
+
+/// Sign `instructions` with the payer and `signers` and process them
+pub async fn process(
+    context: &ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let transaction = transaction(context, instructions, signers).await;
+
+    context.banks_client.process_transaction(transaction).await
+}
+
+async fn transaction(
+    context: &ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Transaction {
+    // Blockhashes only change as slots pass, so a test sending the same
+    // instructions twice has to vary them to avoid a duplicate signature
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    )
+}
+
+/// The custom error code a failed transaction returned
+pub fn custom_error(error: BanksClientError) -> u32 {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        error => panic!("expected a custom program error, found {error:?}"),
+    }
+}
+
+/// Create the mint described by `builder`, paid for by the payer
+pub async fn create_mint(context: &ProgramTestContext, builder: &MintBuilder) -> Keypair {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let lamports = rent.minimum_balance(builder.mint_len().unwrap());
+    let instructions = builder
+        .instructions(&context.payer.pubkey(), &mint.pubkey(), lamports)
+        .unwrap();
+
+    process(context, &instructions, &[&mint]).await.unwrap();
+
+    mint
+}
+
+/// Create the associated token account of `owner`, paid for by the payer
+pub async fn create_token_account(
+    context: &ProgramTestContext,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    let instruction = create_associated_token_account(&context.payer.pubkey(), owner, mint);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    associated_token_address(owner, mint)
+}
+
+/// Mint `amount` to `account` with the payer as mint authority
+pub async fn mint_tokens(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = mint_to(
+        &spl_token_2022::id(),
+        mint,
+        account,
+        &context.payer.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+
+    process(context, &[instruction], &[]).await
+}
+
+/// Fetch and unpack a token account
+pub async fn get_token_account(
+    context: &ProgramTestContext,
+    address: &Pubkey,
+) -> StateWithExtensionsOwned<Account> {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("token account exists");
+
+    StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap()
+}
+
+/// Fetch and unpack a mint
+pub async fn get_mint(
+    context: &ProgramTestContext,
+    address: &Pubkey,
+) -> StateWithExtensionsOwned<Mint> {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("mint exists");
+
+    StateWithExtensionsOwned::<Mint>::unpack(account.data).unwrap()
+}
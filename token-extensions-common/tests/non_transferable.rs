@@ -0,0 +1,61 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::{
+        error::TokenError,
+        instruction::{burn_checked, close_account, transfer_checked},
+    },
+    token_extensions_common::mint::MintBuilder,
+};
+
+#[tokio::test]
+async fn transfers_are_rejected() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let builder = MintBuilder::new(payer, 0).non_transferable();
+    let mint = create_mint(&context, &builder).await.pubkey();
+
+    let source = create_token_account(&context, &payer, &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 100).await.unwrap();
+
+    let transfer = transfer_checked(
+        &spl_token_2022::id(),
+        &source,
+        &mint,
+        &destination,
+        &payer,
+        &[],
+        10,
+        0,
+    )
+    .unwrap();
+    let error = process(&context, &[transfer], &[]).await.unwrap_err();
+    assert_eq!(custom_error(error), TokenError::NonTransferable as u32);
+
+    assert_eq!(get_token_account(&context, &source).await.base.amount, 100);
+}
+
+#[tokio::test]
+async fn tokens_can_be_burned_and_account_closed() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let builder = MintBuilder::new(payer, 0).non_transferable();
+    let mint = create_mint(&context, &builder).await.pubkey();
+
+    let account = create_token_account(&context, &payer, &mint).await;
+    mint_tokens(&context, &mint, &account, 100).await.unwrap();
+
+    let burn = burn_checked(&spl_token_2022::id(), &account, &mint, &payer, &[], 100, 0).unwrap();
+    let close = close_account(&spl_token_2022::id(), &account, &payer, &payer, &[]).unwrap();
+    process(&context, &[burn, close], &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(account)
+        .await
+        .unwrap()
+        .is_none());
+}
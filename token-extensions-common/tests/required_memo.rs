@@ -0,0 +1,134 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair, signer::Signer},
+    spl_token_2022::{
+        error::TokenError,
+        extension::{
+            memo_transfer::instruction::{
+                disable_required_transfer_memos, enable_required_transfer_memos,
+            },
+            ExtensionType,
+        },
+        instruction::{reallocate, transfer_checked},
+    },
+    token_extensions_common::mint::MintBuilder,
+};
+
+/// A mint with the payer holding 100 tokens and a recipient whose account
+/// requires memos
+async fn setup(context: &ProgramTestContext) -> (Pubkey, Pubkey, Pubkey, Keypair) {
+    let payer = context.payer.pubkey();
+    let mint = create_mint(context, &MintBuilder::new(payer, 0))
+        .await
+        .pubkey();
+
+    let source = create_token_account(context, &payer, &mint).await;
+    mint_tokens(context, &mint, &source, 100).await.unwrap();
+
+    let recipient = Keypair::new();
+    let destination = create_token_account(context, &recipient.pubkey(), &mint).await;
+    let instructions = [
+        reallocate(
+            &spl_token_2022::id(),
+            &destination,
+            &payer,
+            &recipient.pubkey(),
+            &[],
+            &[ExtensionType::MemoTransfer],
+        )
+        .unwrap(),
+        enable_required_transfer_memos(
+            &spl_token_2022::id(),
+            &destination,
+            &recipient.pubkey(),
+            &[],
+        )
+        .unwrap(),
+    ];
+    process(context, &instructions, &[&recipient])
+        .await
+        .unwrap();
+
+    (mint, source, destination, recipient)
+}
+
+fn transfer(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    transfer_checked(
+        &spl_token_2022::id(),
+        source,
+        mint,
+        destination,
+        &context.payer.pubkey(),
+        &[],
+        10,
+        0,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn transfer_without_memo_is_rejected() {
+    let context = start().await;
+    let (mint, source, destination, _) = setup(&context).await;
+
+    let error = process(
+        &context,
+        &[transfer(&context, &mint, &source, &destination)],
+        &[],
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(custom_error(error), TokenError::NoMemo as u32);
+}
+
+#[tokio::test]
+async fn transfer_with_memo_succeeds() {
+    let context = start().await;
+    let (mint, source, destination, _) = setup(&context).await;
+
+    let instructions = [
+        spl_memo::build_memo(b"invoice 42", &[]),
+        transfer(&context, &mint, &source, &destination),
+    ];
+    process(&context, &instructions, &[]).await.unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        10
+    );
+}
+
+#[tokio::test]
+async fn transfer_without_memo_succeeds_once_disabled() {
+    let context = start().await;
+    let (mint, source, destination, recipient) = setup(&context).await;
+
+    let disable = disable_required_transfer_memos(
+        &spl_token_2022::id(),
+        &destination,
+        &recipient.pubkey(),
+        &[],
+    )
+    .unwrap();
+    process(&context, &[disable], &[&recipient]).await.unwrap();
+    process(
+        &context,
+        &[transfer(&context, &mint, &source, &destination)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        10
+    );
+}
@@ -0,0 +1,157 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::extension::{
+        transfer_fee::{
+            instruction::{
+                harvest_withheld_tokens_to_mint, transfer_checked_with_fee,
+                withdraw_withheld_tokens_from_accounts, withdraw_withheld_tokens_from_mint,
+            },
+            TransferFeeAmount, TransferFeeConfig,
+        },
+        BaseStateWithExtensions,
+    },
+    token_extensions_common::mint::MintBuilder,
+};
+
+const DECIMALS: u8 = 2;
+const FEE_BASIS_POINTS: u16 = 100;
+const MAX_FEE: u64 = 10_000;
+
+/// A transfer-fee mint with the payer holding 10_000 tokens and a
+/// recipient's account that received `amount` of them
+async fn setup(context: &ProgramTestContext, amount: u64) -> (Pubkey, Pubkey, Pubkey, u64) {
+    let payer = context.payer.pubkey();
+    let builder = MintBuilder::new(payer, DECIMALS).transfer_fee(FEE_BASIS_POINTS, MAX_FEE);
+    let mint = create_mint(context, &builder).await.pubkey();
+
+    let source = create_token_account(context, &payer, &mint).await;
+    let destination = create_token_account(context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(context, &mint, &source, 10_000).await.unwrap();
+
+    let fee = get_mint(context, &mint)
+        .await
+        .get_extension::<TransferFeeConfig>()
+        .unwrap()
+        .calculate_epoch_fee(0, amount)
+        .unwrap();
+    let instruction = transfer_checked_with_fee(
+        &spl_token_2022::id(),
+        &source,
+        &mint,
+        &destination,
+        &payer,
+        &[],
+        amount,
+        DECIMALS,
+        fee,
+    )
+    .unwrap();
+    process(context, &[instruction], &[]).await.unwrap();
+
+    (mint, source, destination, fee)
+}
+
+fn withheld_in_account(
+    account: &impl BaseStateWithExtensions<spl_token_2022::state::Account>,
+) -> u64 {
+    account
+        .get_extension::<TransferFeeAmount>()
+        .unwrap()
+        .withheld_amount
+        .into()
+}
+
+#[tokio::test]
+async fn transfer_withholds_fee_in_destination() {
+    let context = start().await;
+    let (_, source, destination, fee) = setup(&context, 1_000).await;
+
+    assert_eq!(fee, 10);
+    assert_eq!(
+        get_token_account(&context, &source).await.base.amount,
+        9_000
+    );
+
+    let destination = get_token_account(&context, &destination).await;
+    assert_eq!(destination.base.amount, 1_000 - fee);
+    assert_eq!(withheld_in_account(&destination), fee);
+}
+
+#[tokio::test]
+async fn fee_is_capped_at_maximum() {
+    let context = start().await;
+    let (_, _, destination, fee) = setup(&context, 10_000).await;
+
+    assert_eq!(fee, 100);
+    assert_eq!(
+        withheld_in_account(&get_token_account(&context, &destination).await),
+        fee
+    );
+}
+
+#[tokio::test]
+async fn withdraw_withheld_from_accounts() {
+    let context = start().await;
+    let (mint, source, destination, fee) = setup(&context, 1_000).await;
+
+    let instruction = withdraw_withheld_tokens_from_accounts(
+        &spl_token_2022::id(),
+        &mint,
+        &source,
+        &context.payer.pubkey(),
+        &[],
+        &[&destination],
+    )
+    .unwrap();
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &source).await.base.amount,
+        9_000 + fee
+    );
+    assert_eq!(
+        withheld_in_account(&get_token_account(&context, &destination).await),
+        0
+    );
+}
+
+#[tokio::test]
+async fn harvest_then_withdraw_from_mint() {
+    let context = start().await;
+    let (mint, source, destination, fee) = setup(&context, 1_000).await;
+
+    let harvest =
+        harvest_withheld_tokens_to_mint(&spl_token_2022::id(), &mint, &[&destination]).unwrap();
+    process(&context, &[harvest], &[]).await.unwrap();
+
+    let withheld_in_mint: u64 = get_mint(&context, &mint)
+        .await
+        .get_extension::<TransferFeeConfig>()
+        .unwrap()
+        .withheld_amount
+        .into();
+    assert_eq!(withheld_in_mint, fee);
+    assert_eq!(
+        withheld_in_account(&get_token_account(&context, &destination).await),
+        0
+    );
+
+    let withdraw = withdraw_withheld_tokens_from_mint(
+        &spl_token_2022::id(),
+        &mint,
+        &source,
+        &context.payer.pubkey(),
+        &[],
+    )
+    .unwrap();
+    process(&context, &[withdraw], &[]).await.unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &source).await.base.amount,
+        9_000 + fee
+    );
+}
@@ -0,0 +1,34 @@
+//! Program entrypoint
+
+use {
+    crate::error::HookPolicyError,
+    num_traits::FromPrimitive,
+    solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        program_error::{PrintProgramError, ProgramError},
+        pubkey::Pubkey,
+    },
+    spl_transfer_hook_interface::error::TransferHookError,
+};
+
+solana_program::entrypoint!(process_instruction);
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    if let Err(error) = crate::processor::process(program_id, accounts, instruction_data) {
+        // catch the error so we can print it, policy codes are small while
+        // the interface's are derived from a hash so the two never overlap
+        match error {
+            ProgramError::Custom(code) if HookPolicyError::from_u32(code).is_some() => {
+                error.print::<HookPolicyError>()
+            }
+            _ => error.print::<TransferHookError>(),
+        }
+        return Err(error);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Error types
+
+use {
+    num_derive::FromPrimitive,
+    num_traits::FromPrimitive,
+    solana_program::{
+        decode_error::DecodeError,
+        msg,
+        program_error::{PrintProgramError, ProgramError},
+    },
+    thiserror::Error,
+};
+
+/// Errors returned when a transfer breaks a policy or a policy account is
+/// misused
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+#[repr(u32)]
+pub enum HookPolicyError {
+    /// The signer is not the authority of the policy account
+    #[error("Incorrect policy authority")]
+    IncorrectAuthority,
+    /// The destination owner is not on the mint's allowlist
+    #[error("Destination owner is not on the allowlist")]
+    DestinationNotAllowlisted,
+    /// The source owner is on the mint's blocklist
+    #[error("Source owner is on the blocklist")]
+    SourceBlocklisted,
+    /// The destination owner is on the mint's blocklist
+    #[error("Destination owner is on the blocklist")]
+    DestinationBlocklisted,
+    /// The transfer would take the source owner over the mint's velocity
+    /// limit
+    #[error("Transfer exceeds the velocity limit")]
+    VelocityLimitExceeded,
+    /// The transfer amount is below the mint's minimum
+    #[error("Transfer amount is below the minimum")]
+    AmountBelowMinimum,
+    /// The transfer amount is above the mint's maximum
+    #[error("Transfer amount is above the maximum")]
+    AmountAboveMaximum,
+    /// The mint's trading schedule has not unlocked transfers yet
+    #[error("Transfers are locked until the unlock time")]
+    TransfersLocked,
+    /// The transfer falls outside the daily windows of the mint's trading
+    /// schedule
+    #[error("Transfer is outside the trading windows")]
+    OutsideTradingWindow,
+    /// The transfer would leave the source with less than the amount its
+    /// owner's vesting schedule still locks
+    #[error("Transfer would spend tokens that are still vesting")]
+    VestingLocked,
+    /// The transaction does not pay the creator vault the royalty the mint
+    /// requires
+    #[error("Royalty was not paid to the creator vault")]
+    RoyaltyNotPaid,
+    /// The mint requires a memo before every transfer and the transaction
+    /// has none
+    #[error("Transfer is not preceded by a memo")]
+    MemoRequired,
+    /// The memo before the transfer is longer than the mint allows
+    #[error("Memo is too long")]
+    MemoTooLong,
+    /// The memo before the transfer does not match the mint's pattern
+    #[error("Memo does not match the pattern")]
+    MemoPatternMismatch,
+    /// The mint's guardian has paused transfers
+    #[error("Transfers are paused")]
+    TransfersPaused,
+    /// The source owner holds no attestation from the mint's attester
+    #[error("Source owner is not attested")]
+    SourceNotAttested,
+    /// The destination owner holds no attestation from the mint's attester
+    #[error("Destination owner is not attested")]
+    DestinationNotAttested,
+    /// The attestation of the source or destination owner has expired
+    #[error("Attestation has expired")]
+    AttestationExpired,
+    /// The transfer amount is above the limit of the source or destination
+    /// owner's tier
+    #[error("Transfer amount is above the tier limit")]
+    AmountAboveTierLimit,
+    /// The transfer would give the mint more holders than its cap
+    #[error("Transfer exceeds the maximum number of holders")]
+    HolderLimitExceeded,
+}
+
+impl From<HookPolicyError> for ProgramError {
+    fn from(error: HookPolicyError) -> Self {
+        ProgramError::Custom(error as u32)
+    }
+}
+
+impl<T> DecodeError<T> for HookPolicyError {
+    fn type_of() -> &'static str {
+        "HookPolicyError"
+    }
+}
+
+impl PrintProgramError for HookPolicyError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!("HookPolicyError::{:?}: {}", self, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, spl_transfer_hook_interface::error::TransferHookError};
+
+    #[test]
+    fn policy_error_codes_decode_apart_from_the_interface() {
+        let codes = (0..)
+            .map_while(|code| HookPolicyError::from_u32(code).map(|error| (code, error)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            codes.last().unwrap().1,
+            HookPolicyError::HolderLimitExceeded
+        );
+        for (code, error) in codes {
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+            assert!(TransferHookError::from_u32(code).is_none());
+        }
+    }
+}
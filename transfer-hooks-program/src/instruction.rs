@@ -0,0 +1,1985 @@
+//! Instructions managing the policy accounts
+//!
+//! Each instruction is packed as the 8-byte discriminator of its data type
+//! followed by the borsh-serialized data, like `TransferHookInstruction`, so
+//! both can be told apart by their first 8 bytes.
+//!
+//! A new instruction needs a data type deriving `SplDiscriminate` with a
+//! `transfer_hooks_program:` hash input, a [`PolicyInstruction`] variant
+//! listing its accounts, an arm in both `try_unpack` and `pack`, and a
+//! builder function here for clients to call.
+
+use {
+    crate::state::{
+        find_amount_limits_address, find_attestation_address, find_holder_count_address,
+        find_hook_config_address, find_kyc_config_address, find_memo_config_address,
+        find_owner_stats_address, find_pause_address, find_royalty_config_address,
+        find_trading_schedule_address, find_transfer_stats_address, find_velocity_limit_address,
+        find_velocity_tracker_address, find_vesting_schedule_address, Allowlist, Blocklist,
+        DailyWindow, OwnerList,
+    },
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        system_program,
+    },
+    spl_discriminator::{ArrayDiscriminator, SplDiscriminate},
+    spl_transfer_hook_interface::get_extra_account_metas_address,
+};
+
+/// Close the extra account metas of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:close_extra_account_meta_list")]
+pub struct CloseExtraAccountMetaList;
+
+/// Create the allowlist of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_allowlist")]
+pub struct InitializeAllowlist {
+    /// Authority allowed to add and remove owners
+    pub authority: Pubkey,
+}
+
+/// Add owners to an allowlist
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:add_to_allowlist")]
+pub struct AddToAllowlist {
+    /// Owners to add, ignoring those already listed
+    pub owners: Vec<Pubkey>,
+}
+
+/// Remove owners from an allowlist
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:remove_from_allowlist")]
+pub struct RemoveFromAllowlist {
+    /// Owners to remove, ignoring those not listed
+    pub owners: Vec<Pubkey>,
+}
+
+/// Create the blocklist of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_blocklist")]
+pub struct InitializeBlocklist {
+    /// Authority allowed to add and remove owners
+    pub authority: Pubkey,
+}
+
+/// Add owners to a blocklist
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:add_to_blocklist")]
+pub struct AddToBlocklist {
+    /// Owners to add, ignoring those already listed
+    pub owners: Vec<Pubkey>,
+}
+
+/// Remove owners from a blocklist
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:remove_from_blocklist")]
+pub struct RemoveFromBlocklist {
+    /// Owners to remove, ignoring those not listed
+    pub owners: Vec<Pubkey>,
+}
+
+/// Create the velocity limit of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_velocity_limit")]
+pub struct InitializeVelocityLimit {
+    /// Authority allowed to change the limit
+    pub authority: Pubkey,
+    /// Length of a window in seconds, greater than zero
+    pub window_seconds: i64,
+    /// Most an owner may send within a window, in base units
+    pub max_amount: u64,
+}
+
+/// Change a velocity limit
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_velocity_limit")]
+pub struct SetVelocityLimit {
+    /// Length of a window in seconds, greater than zero
+    pub window_seconds: i64,
+    /// Most an owner may send within a window, in base units
+    pub max_amount: u64,
+}
+
+/// Create the velocity tracker of an owner
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_velocity_tracker")]
+pub struct InitializeVelocityTracker;
+
+/// Create the amount limits of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_amount_limits")]
+pub struct InitializeAmountLimits {
+    /// Authority allowed to change the limits
+    pub authority: Pubkey,
+    /// Smallest amount a transfer may move, in base units
+    pub min_amount: u64,
+    /// Largest amount a transfer may move, at least `min_amount`
+    pub max_amount: u64,
+}
+
+/// Change amount limits
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_amount_limits")]
+pub struct SetAmountLimits {
+    /// Smallest amount a transfer may move, in base units
+    pub min_amount: u64,
+    /// Largest amount a transfer may move, at least `min_amount`
+    pub max_amount: u64,
+}
+
+/// Create the trading schedule of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_trading_schedule")]
+pub struct InitializeTradingSchedule {
+    /// Authority allowed to change the schedule and its exemptions
+    pub authority: Pubkey,
+    /// Unix timestamp before which transfers are locked
+    pub unlock_timestamp: i64,
+    /// Spans of each day transfers are allowed in, or every day if empty
+    pub daily_windows: Vec<DailyWindow>,
+}
+
+/// Change a trading schedule, keeping its exemptions
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_trading_schedule")]
+pub struct SetTradingSchedule {
+    /// Unix timestamp before which transfers are locked
+    pub unlock_timestamp: i64,
+    /// Spans of each day transfers are allowed in, or every day if empty
+    pub daily_windows: Vec<DailyWindow>,
+}
+
+/// Exempt source token accounts from a trading schedule
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:add_schedule_exemptions")]
+pub struct AddScheduleExemptions {
+    /// Token accounts to exempt, ignoring those already exempt
+    pub accounts: Vec<Pubkey>,
+}
+
+/// Subject source token accounts to a trading schedule again
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:remove_schedule_exemptions")]
+pub struct RemoveScheduleExemptions {
+    /// Token accounts to stop exempting, ignoring those not exempt
+    pub accounts: Vec<Pubkey>,
+}
+
+/// Create the vesting schedule of a beneficiary
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_vesting_schedule")]
+pub struct InitializeVestingSchedule {
+    /// Authority allowed to revoke the schedule
+    pub authority: Pubkey,
+    /// Amount granted, in base units
+    pub total_amount: u64,
+    /// Unix timestamp the grant starts accruing at
+    pub start_timestamp: i64,
+    /// Unix timestamp before which nothing unlocks, from the start to the end
+    pub cliff_timestamp: i64,
+    /// Unix timestamp the whole grant is unlocked at
+    pub end_timestamp: i64,
+}
+
+/// Delete a vesting schedule, unlocking whatever it still locks
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:revoke_vesting_schedule")]
+pub struct RevokeVestingSchedule;
+
+/// Create the transfer stats of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_transfer_stats")]
+pub struct InitializeTransferStats;
+
+/// Create the stats of an owner
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_owner_stats")]
+pub struct InitializeOwnerStats;
+
+/// Create the hook config of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_hook_config")]
+pub struct InitializeHookConfig {
+    /// Authority allowed to manage the extra account metas
+    pub admin: Pubkey,
+}
+
+/// Propose a new hook admin
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:propose_hook_admin")]
+pub struct ProposeHookAdmin {
+    /// Admin taking over once they accept, or the default pubkey to withdraw
+    /// the proposal
+    pub new_admin: Pubkey,
+}
+
+/// Accept being the hook admin
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:accept_hook_admin")]
+pub struct AcceptHookAdmin;
+
+/// Create the royalty config of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_royalty")]
+pub struct InitializeRoyalty {
+    /// Authority allowed to change the royalty
+    pub authority: Pubkey,
+    /// Account the royalty is paid to
+    pub creator_vault: Pubkey,
+    /// Share of the sale price owed to the creator, at most 10 000
+    pub basis_points: u16,
+    /// Lamports owed on every transfer, including those without a sale
+    pub min_lamports: u64,
+}
+
+/// Change a royalty config
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_royalty")]
+pub struct SetRoyalty {
+    /// Account the royalty is paid to
+    pub creator_vault: Pubkey,
+    /// Share of the sale price owed to the creator, at most 10 000
+    pub basis_points: u16,
+    /// Lamports owed on every transfer, including those without a sale
+    pub min_lamports: u64,
+}
+
+/// Create the memo config of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_memo_config")]
+pub struct InitializeMemoConfig {
+    /// Authority allowed to change the config
+    pub authority: Pubkey,
+    /// Most bytes the memo may have, or 0 for no limit
+    pub max_length: u16,
+    /// Pattern the memo must match, or empty to accept any memo
+    pub pattern: String,
+}
+
+/// Change a memo config
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_memo_config")]
+pub struct SetMemoConfig {
+    /// Most bytes the memo may have, or 0 for no limit
+    pub max_length: u16,
+    /// Pattern the memo must match, or empty to accept any memo
+    pub pattern: String,
+}
+
+/// Create the pause state of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_pause")]
+pub struct InitializePause {
+    /// Key allowed to pause and unpause transfers
+    pub guardian: Pubkey,
+}
+
+/// Pause transfers
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:pause")]
+pub struct Pause;
+
+/// Resume transfers
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:unpause")]
+pub struct Unpause;
+
+/// Let source token accounts send while transfers are paused
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:add_pause_exemptions")]
+pub struct AddPauseExemptions {
+    /// Token accounts to exempt, ignoring those already exempt
+    pub accounts: Vec<Pubkey>,
+}
+
+/// Stop source token accounts from sending while transfers are paused
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:remove_pause_exemptions")]
+pub struct RemovePauseExemptions {
+    /// Token accounts to stop exempting, ignoring those not exempt
+    pub accounts: Vec<Pubkey>,
+}
+
+/// Create the KYC config of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_kyc_config")]
+pub struct InitializeKycConfig {
+    /// Authority allowed to change the config
+    pub authority: Pubkey,
+    /// Key issuing and revoking attestations
+    pub attester: Pubkey,
+    /// Largest amount a transfer may move for each tier, in base units
+    pub tier_limits: Vec<u64>,
+}
+
+/// Change a KYC config. Attestations issued by a replaced attester are no
+/// longer accepted.
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_kyc_config")]
+pub struct SetKycConfig {
+    /// Key issuing and revoking attestations
+    pub attester: Pubkey,
+    /// Largest amount a transfer may move for each tier, in base units
+    pub tier_limits: Vec<u64>,
+}
+
+/// Attest to an owner, or renew their attestation
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:issue_attestation")]
+pub struct IssueAttestation {
+    /// Tier the owner was verified at
+    pub tier: u8,
+    /// Unix timestamp the attestation expires at
+    pub expiry_timestamp: i64,
+}
+
+/// Revoke the attestation of an owner
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:revoke_attestation")]
+pub struct RevokeAttestation;
+
+/// Create the holder count of a mint
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:initialize_holder_count")]
+pub struct InitializeHolderCount {
+    /// Authority allowed to change the cap and correct the count
+    pub authority: Pubkey,
+    /// Token accounts already holding a balance
+    pub holder_count: u64,
+    /// Most token accounts that may hold a balance, or 0 for no cap
+    pub max_holders: u64,
+}
+
+/// Change the cap of a holder count, or correct the count after mints and
+/// burns
+#[derive(Clone, Debug, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:set_holder_count")]
+pub struct SetHolderCount {
+    /// Token accounts holding a balance
+    pub holder_count: u64,
+    /// Most token accounts that may hold a balance, or 0 for no cap
+    pub max_holders: u64,
+}
+
+/// Instructions supported by the program besides the transfer hook interface.
+///
+/// Instructions "authorized by the hook admin" are signed by the admin of the
+/// mint's hook config once it exists, or by the mint authority until then.
+/// They take the hook config, created or not, as their last account, so the
+/// mint authority cannot skip the admin by leaving it out.
+///
+/// The same goes for the interface's `InitializeExtraAccountMetaList` and
+/// `UpdateExtraAccountMetaList`, which this program expects with the hook
+/// config appended, at index 4 and 3 respectively. This breaks with the
+/// interface's account layout: instructions built for it alone fail with
+/// `NotEnoughAccountKeys` until passed through [`with_hook_config`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PolicyInstruction {
+    /// Close the extra account metas of a mint, returning their rent to the
+    /// destination. Authorized like `UpdateExtraAccountMetaList`, by the hook
+    /// admin once the hook config exists or by the mint authority until
+    /// then. Transfers of the mint fail until the metas are initialized
+    /// again, so close them once the mint's transfer hook points at another
+    /// program.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Extra account metas
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[w]` Destination
+    ///   4. `[]` Hook config, whether or not it exists
+    CloseExtraAccountMetaList(CloseExtraAccountMetaList),
+
+    /// Create the allowlist of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Allowlist
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeAllowlist(InitializeAllowlist),
+
+    /// Add owners to an allowlist, growing it as needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Allowlist
+    ///   1. `[s]` Allowlist authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    AddToAllowlist(AddToAllowlist),
+
+    /// Remove owners from an allowlist.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Allowlist
+    ///   1. `[s]` Allowlist authority
+    RemoveFromAllowlist(RemoveFromAllowlist),
+
+    /// Create the blocklist of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Blocklist
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeBlocklist(InitializeBlocklist),
+
+    /// Add owners to a blocklist, growing it as needed. The owners are
+    /// merged in a single pass, so a large list is bulk-loaded by sending as
+    /// many as fit in each transaction.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Blocklist
+    ///   1. `[s]` Blocklist authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    AddToBlocklist(AddToBlocklist),
+
+    /// Remove owners from a blocklist.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Blocklist
+    ///   1. `[s]` Blocklist authority
+    RemoveFromBlocklist(RemoveFromBlocklist),
+
+    /// Create the velocity limit of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Velocity limit
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeVelocityLimit(InitializeVelocityLimit),
+
+    /// Change a velocity limit. Amounts already sent count against the new
+    /// cap for as long as the new window covers them.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Velocity limit
+    ///   1. `[s]` Velocity limit authority
+    SetVelocityLimit(SetVelocityLimit),
+
+    /// Create the velocity tracker of an owner, which must exist for the
+    /// owner's transfers of a mint with a velocity limit to be counted.
+    /// Without it each transfer is only checked against the cap on its own.
+    /// Anyone may pay for it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Velocity tracker
+    ///   1. `[]` Mint
+    ///   2. `[]` Owner
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    InitializeVelocityTracker(InitializeVelocityTracker),
+
+    /// Create the amount limits of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Amount limits
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeAmountLimits(InitializeAmountLimits),
+
+    /// Change amount limits.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Amount limits
+    ///   1. `[s]` Amount limits authority
+    SetAmountLimits(SetAmountLimits),
+
+    /// Create the trading schedule of a mint without exemptions, authorized by
+    /// the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Trading schedule
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeTradingSchedule(InitializeTradingSchedule),
+
+    /// Change a trading schedule, resizing it as needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Trading schedule
+    ///   1. `[s]` Trading schedule authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    SetTradingSchedule(SetTradingSchedule),
+
+    /// Exempt source token accounts from a trading schedule, growing it as
+    /// needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Trading schedule
+    ///   1. `[s]` Trading schedule authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    AddScheduleExemptions(AddScheduleExemptions),
+
+    /// Subject source token accounts to a trading schedule again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Trading schedule
+    ///   1. `[s]` Trading schedule authority
+    RemoveScheduleExemptions(RemoveScheduleExemptions),
+
+    /// Create the vesting schedule of a beneficiary, authorized by the hook
+    /// admin. Until it is revoked, transfers may not take the beneficiary's
+    /// token accounts below the amount it still locks.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Vesting schedule
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[]` Beneficiary
+    ///   4. `[ws]` Payer
+    ///   5. `[]` System program
+    ///   6. `[]` Hook config, whether or not it exists
+    InitializeVestingSchedule(InitializeVestingSchedule),
+
+    /// Close a vesting schedule, returning its rent to the destination.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Vesting schedule
+    ///   1. `[s]` Vesting schedule authority
+    ///   2. `[w]` Destination
+    RevokeVestingSchedule(RevokeVestingSchedule),
+
+    /// Create the transfer stats of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Transfer stats
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeTransferStats(InitializeTransferStats),
+
+    /// Create the stats of an owner, counted from then on by mints whose
+    /// extra account metas include owner stats. Anyone may pay for them.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Owner stats
+    ///   1. `[]` Mint
+    ///   2. `[]` Owner
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    InitializeOwnerStats(InitializeOwnerStats),
+
+    /// Create the hook config of a mint, authorized by the mint authority or
+    /// the authority of the mint's transfer hook, so that a mint whose mint
+    /// authority is revoked can still get an admin. From then on its admin
+    /// signs the instructions authorized by the hook admin in place of the
+    /// mint authority. A mint with neither authority can never get one.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Hook config
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or transfer hook authority
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    InitializeHookConfig(InitializeHookConfig),
+
+    /// Propose a new hook admin, who takes over by accepting. Proposing
+    /// again replaces the pending admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Hook config
+    ///   1. `[s]` Hook admin
+    ProposeHookAdmin(ProposeHookAdmin),
+
+    /// Accept being the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Hook config
+    ///   1. `[s]` Pending hook admin
+    AcceptHookAdmin(AcceptHookAdmin),
+
+    /// Create the royalty config of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Royalty config
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeRoyalty(InitializeRoyalty),
+
+    /// Change a royalty config.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Royalty config
+    ///   1. `[s]` Royalty authority
+    SetRoyalty(SetRoyalty),
+
+    /// Create the memo config of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Memo config
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeMemoConfig(InitializeMemoConfig),
+
+    /// Change a memo config, resizing it as needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Memo config
+    ///   1. `[s]` Memo config authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    SetMemoConfig(SetMemoConfig),
+
+    /// Create the pause state of a mint, unpaused and without exemptions,
+    /// authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Pause state
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializePause(InitializePause),
+
+    /// Reject transfers from all but the exempt accounts.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Pause state
+    ///   1. `[s]` Guardian
+    Pause(Pause),
+
+    /// Allow transfers again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Pause state
+    ///   1. `[s]` Guardian
+    Unpause(Unpause),
+
+    /// Exempt source token accounts from a pause, growing the pause state as
+    /// needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Pause state
+    ///   1. `[s]` Guardian
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    AddPauseExemptions(AddPauseExemptions),
+
+    /// Subject source token accounts to a pause again.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Pause state
+    ///   1. `[s]` Guardian
+    RemovePauseExemptions(RemovePauseExemptions),
+
+    /// Create the KYC config of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` KYC config
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeKycConfig(InitializeKycConfig),
+
+    /// Change a KYC config, resizing it as needed.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` KYC config
+    ///   1. `[s]` KYC config authority
+    ///   2. `[ws]` Payer
+    ///   3. `[]` System program
+    SetKycConfig(SetKycConfig),
+
+    /// Create the attestation of an owner for a mint, or overwrite it,
+    /// signed by the attester of the mint's KYC config.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Attestation
+    ///   1. `[]` Mint
+    ///   2. `[]` KYC config
+    ///   3. `[s]` Attester
+    ///   4. `[]` Owner
+    ///   5. `[ws]` Payer
+    ///   6. `[]` System program
+    IssueAttestation(IssueAttestation),
+
+    /// Close an attestation, returning its rent to the destination. Signed
+    /// by the key that issued it.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Attestation
+    ///   1. `[s]` Attester
+    ///   2. `[w]` Destination
+    RevokeAttestation(RevokeAttestation),
+
+    /// Create the holder count of a mint, authorized by the hook admin.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Holder count
+    ///   1. `[]` Mint
+    ///   2. `[s]` Mint authority or hook admin
+    ///   3. `[ws]` Payer
+    ///   4. `[]` System program
+    ///   5. `[]` Hook config, whether or not it exists
+    InitializeHolderCount(InitializeHolderCount),
+
+    /// Change a holder count.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[w]` Holder count
+    ///   1. `[s]` Holder count authority
+    SetHolderCount(SetHolderCount),
+}
+
+impl PolicyInstruction {
+    /// Unpacks a byte buffer into a
+    /// [PolicyInstruction](enum.PolicyInstruction.html).
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_unpack(input)?.ok_or(ProgramError::InvalidInstructionData)
+    }
+
+    /// Unpacks a byte buffer into a
+    /// [PolicyInstruction](enum.PolicyInstruction.html), or `None` if it does
+    /// not start with the discriminator of one, such as a
+    /// `TransferHookInstruction`. Malformed data after a known discriminator
+    /// is an error.
+    pub fn try_unpack(input: &[u8]) -> Result<Option<Self>, ProgramError> {
+        let Some((discriminator, rest)) = input.split_at_checked(ArrayDiscriminator::LENGTH) else {
+            return Ok(None);
+        };
+        Ok(Some(match discriminator {
+            CloseExtraAccountMetaList::SPL_DISCRIMINATOR_SLICE => {
+                Self::CloseExtraAccountMetaList(CloseExtraAccountMetaList::try_from_slice(rest)?)
+            }
+            InitializeAllowlist::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeAllowlist(InitializeAllowlist::try_from_slice(rest)?)
+            }
+            AddToAllowlist::SPL_DISCRIMINATOR_SLICE => {
+                Self::AddToAllowlist(AddToAllowlist::try_from_slice(rest)?)
+            }
+            RemoveFromAllowlist::SPL_DISCRIMINATOR_SLICE => {
+                Self::RemoveFromAllowlist(RemoveFromAllowlist::try_from_slice(rest)?)
+            }
+            InitializeBlocklist::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeBlocklist(InitializeBlocklist::try_from_slice(rest)?)
+            }
+            AddToBlocklist::SPL_DISCRIMINATOR_SLICE => {
+                Self::AddToBlocklist(AddToBlocklist::try_from_slice(rest)?)
+            }
+            RemoveFromBlocklist::SPL_DISCRIMINATOR_SLICE => {
+                Self::RemoveFromBlocklist(RemoveFromBlocklist::try_from_slice(rest)?)
+            }
+            InitializeVelocityLimit::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeVelocityLimit(InitializeVelocityLimit::try_from_slice(rest)?)
+            }
+            SetVelocityLimit::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetVelocityLimit(SetVelocityLimit::try_from_slice(rest)?)
+            }
+            InitializeVelocityTracker::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeVelocityTracker(InitializeVelocityTracker::try_from_slice(rest)?)
+            }
+            InitializeAmountLimits::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeAmountLimits(InitializeAmountLimits::try_from_slice(rest)?)
+            }
+            SetAmountLimits::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetAmountLimits(SetAmountLimits::try_from_slice(rest)?)
+            }
+            InitializeTradingSchedule::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeTradingSchedule(InitializeTradingSchedule::try_from_slice(rest)?)
+            }
+            SetTradingSchedule::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetTradingSchedule(SetTradingSchedule::try_from_slice(rest)?)
+            }
+            AddScheduleExemptions::SPL_DISCRIMINATOR_SLICE => {
+                Self::AddScheduleExemptions(AddScheduleExemptions::try_from_slice(rest)?)
+            }
+            RemoveScheduleExemptions::SPL_DISCRIMINATOR_SLICE => {
+                Self::RemoveScheduleExemptions(RemoveScheduleExemptions::try_from_slice(rest)?)
+            }
+            InitializeVestingSchedule::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeVestingSchedule(InitializeVestingSchedule::try_from_slice(rest)?)
+            }
+            RevokeVestingSchedule::SPL_DISCRIMINATOR_SLICE => {
+                Self::RevokeVestingSchedule(RevokeVestingSchedule::try_from_slice(rest)?)
+            }
+            InitializeTransferStats::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeTransferStats(InitializeTransferStats::try_from_slice(rest)?)
+            }
+            InitializeOwnerStats::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeOwnerStats(InitializeOwnerStats::try_from_slice(rest)?)
+            }
+            InitializeHookConfig::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeHookConfig(InitializeHookConfig::try_from_slice(rest)?)
+            }
+            ProposeHookAdmin::SPL_DISCRIMINATOR_SLICE => {
+                Self::ProposeHookAdmin(ProposeHookAdmin::try_from_slice(rest)?)
+            }
+            AcceptHookAdmin::SPL_DISCRIMINATOR_SLICE => {
+                Self::AcceptHookAdmin(AcceptHookAdmin::try_from_slice(rest)?)
+            }
+            InitializeRoyalty::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeRoyalty(InitializeRoyalty::try_from_slice(rest)?)
+            }
+            SetRoyalty::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetRoyalty(SetRoyalty::try_from_slice(rest)?)
+            }
+            InitializeMemoConfig::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeMemoConfig(InitializeMemoConfig::try_from_slice(rest)?)
+            }
+            SetMemoConfig::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetMemoConfig(SetMemoConfig::try_from_slice(rest)?)
+            }
+            InitializePause::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializePause(InitializePause::try_from_slice(rest)?)
+            }
+            Pause::SPL_DISCRIMINATOR_SLICE => Self::Pause(Pause::try_from_slice(rest)?),
+            Unpause::SPL_DISCRIMINATOR_SLICE => Self::Unpause(Unpause::try_from_slice(rest)?),
+            AddPauseExemptions::SPL_DISCRIMINATOR_SLICE => {
+                Self::AddPauseExemptions(AddPauseExemptions::try_from_slice(rest)?)
+            }
+            RemovePauseExemptions::SPL_DISCRIMINATOR_SLICE => {
+                Self::RemovePauseExemptions(RemovePauseExemptions::try_from_slice(rest)?)
+            }
+            InitializeKycConfig::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeKycConfig(InitializeKycConfig::try_from_slice(rest)?)
+            }
+            SetKycConfig::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetKycConfig(SetKycConfig::try_from_slice(rest)?)
+            }
+            IssueAttestation::SPL_DISCRIMINATOR_SLICE => {
+                Self::IssueAttestation(IssueAttestation::try_from_slice(rest)?)
+            }
+            RevokeAttestation::SPL_DISCRIMINATOR_SLICE => {
+                Self::RevokeAttestation(RevokeAttestation::try_from_slice(rest)?)
+            }
+            InitializeHolderCount::SPL_DISCRIMINATOR_SLICE => {
+                Self::InitializeHolderCount(InitializeHolderCount::try_from_slice(rest)?)
+            }
+            SetHolderCount::SPL_DISCRIMINATOR_SLICE => {
+                Self::SetHolderCount(SetHolderCount::try_from_slice(rest)?)
+            }
+            _ => return Ok(None),
+        }))
+    }
+
+    /// Packs a [PolicyInstruction](enum.PolicyInstruction.html) into a byte
+    /// buffer.
+    pub fn pack(&self) -> Vec<u8> {
+        fn pack<T: SplDiscriminate + BorshSerialize>(data: &T) -> Vec<u8> {
+            let mut buf = T::SPL_DISCRIMINATOR_SLICE.to_vec();
+            // Writing to a `Vec` cannot fail
+            data.serialize(&mut buf).unwrap();
+            buf
+        }
+
+        match self {
+            Self::CloseExtraAccountMetaList(data) => pack(data),
+            Self::InitializeAllowlist(data) => pack(data),
+            Self::AddToAllowlist(data) => pack(data),
+            Self::RemoveFromAllowlist(data) => pack(data),
+            Self::InitializeBlocklist(data) => pack(data),
+            Self::AddToBlocklist(data) => pack(data),
+            Self::RemoveFromBlocklist(data) => pack(data),
+            Self::InitializeVelocityLimit(data) => pack(data),
+            Self::SetVelocityLimit(data) => pack(data),
+            Self::InitializeVelocityTracker(data) => pack(data),
+            Self::InitializeAmountLimits(data) => pack(data),
+            Self::SetAmountLimits(data) => pack(data),
+            Self::InitializeTradingSchedule(data) => pack(data),
+            Self::SetTradingSchedule(data) => pack(data),
+            Self::AddScheduleExemptions(data) => pack(data),
+            Self::RemoveScheduleExemptions(data) => pack(data),
+            Self::InitializeVestingSchedule(data) => pack(data),
+            Self::RevokeVestingSchedule(data) => pack(data),
+            Self::InitializeTransferStats(data) => pack(data),
+            Self::InitializeOwnerStats(data) => pack(data),
+            Self::InitializeHookConfig(data) => pack(data),
+            Self::ProposeHookAdmin(data) => pack(data),
+            Self::AcceptHookAdmin(data) => pack(data),
+            Self::InitializeRoyalty(data) => pack(data),
+            Self::SetRoyalty(data) => pack(data),
+            Self::InitializeMemoConfig(data) => pack(data),
+            Self::SetMemoConfig(data) => pack(data),
+            Self::InitializePause(data) => pack(data),
+            Self::Pause(data) => pack(data),
+            Self::Unpause(data) => pack(data),
+            Self::AddPauseExemptions(data) => pack(data),
+            Self::RemovePauseExemptions(data) => pack(data),
+            Self::InitializeKycConfig(data) => pack(data),
+            Self::SetKycConfig(data) => pack(data),
+            Self::IssueAttestation(data) => pack(data),
+            Self::RevokeAttestation(data) => pack(data),
+            Self::InitializeHolderCount(data) => pack(data),
+            Self::SetHolderCount(data) => pack(data),
+        }
+    }
+}
+
+/// Creates a `CloseExtraAccountMetaList` instruction, signed by the hook
+/// admin once the hook config exists or by the mint authority until then
+pub fn close_extra_account_meta_list(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let extra_account_metas = get_extra_account_metas_address(mint, program_id);
+    let (hook_config, _) = find_hook_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(extra_account_metas, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(hook_config, false),
+        ],
+        data: PolicyInstruction::CloseExtraAccountMetaList(CloseExtraAccountMetaList).pack(),
+    }
+}
+
+/// Accounts of an instruction creating the owner list `T` of `mint`
+fn initialize_owner_list_accounts<T: OwnerList>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (address, _) = T::find_address(mint, program_id);
+
+    vec![
+        AccountMeta::new(address, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*mint_authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+/// Accounts of an instruction adding owners to the owner list `T` of `mint`
+fn add_owners_accounts<T: OwnerList>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (address, _) = T::find_address(mint, program_id);
+
+    vec![
+        AccountMeta::new(address, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+/// Accounts of an instruction removing owners from the owner list `T` of
+/// `mint`
+fn remove_owners_accounts<T: OwnerList>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (address, _) = T::find_address(mint, program_id);
+
+    vec![
+        AccountMeta::new(address, false),
+        AccountMeta::new_readonly(*authority, true),
+    ]
+}
+
+/// Creates an `InitializeAllowlist` instruction
+pub fn initialize_allowlist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: initialize_owner_list_accounts::<Allowlist>(
+            program_id,
+            mint,
+            mint_authority,
+            payer,
+        ),
+        data: PolicyInstruction::InitializeAllowlist(InitializeAllowlist {
+            authority: *authority,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates an `AddToAllowlist` instruction
+pub fn add_to_allowlist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    owners: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: add_owners_accounts::<Allowlist>(program_id, mint, authority, payer),
+        data: PolicyInstruction::AddToAllowlist(AddToAllowlist { owners }).pack(),
+    }
+}
+
+/// Creates a `RemoveFromAllowlist` instruction
+pub fn remove_from_allowlist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    owners: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: remove_owners_accounts::<Allowlist>(program_id, mint, authority),
+        data: PolicyInstruction::RemoveFromAllowlist(RemoveFromAllowlist { owners }).pack(),
+    }
+}
+
+/// Creates an `InitializeBlocklist` instruction
+pub fn initialize_blocklist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: initialize_owner_list_accounts::<Blocklist>(
+            program_id,
+            mint,
+            mint_authority,
+            payer,
+        ),
+        data: PolicyInstruction::InitializeBlocklist(InitializeBlocklist {
+            authority: *authority,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates an `AddToBlocklist` instruction
+pub fn add_to_blocklist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    owners: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: add_owners_accounts::<Blocklist>(program_id, mint, authority, payer),
+        data: PolicyInstruction::AddToBlocklist(AddToBlocklist { owners }).pack(),
+    }
+}
+
+/// Creates a `RemoveFromBlocklist` instruction
+pub fn remove_from_blocklist(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    owners: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: remove_owners_accounts::<Blocklist>(program_id, mint, authority),
+        data: PolicyInstruction::RemoveFromBlocklist(RemoveFromBlocklist { owners }).pack(),
+    }
+}
+
+/// Creates an `InitializeVelocityLimit` instruction
+pub fn initialize_velocity_limit(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    window_seconds: i64,
+    max_amount: u64,
+) -> Instruction {
+    let (velocity_limit, _) = find_velocity_limit_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(velocity_limit, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeVelocityLimit(InitializeVelocityLimit {
+            authority: *authority,
+            window_seconds,
+            max_amount,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetVelocityLimit` instruction
+pub fn set_velocity_limit(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    window_seconds: i64,
+    max_amount: u64,
+) -> Instruction {
+    let (velocity_limit, _) = find_velocity_limit_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(velocity_limit, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: PolicyInstruction::SetVelocityLimit(SetVelocityLimit {
+            window_seconds,
+            max_amount,
+        })
+        .pack(),
+    }
+}
+
+/// Creates an `InitializeVelocityTracker` instruction
+pub fn initialize_velocity_tracker(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (velocity_tracker, _) = find_velocity_tracker_address(mint, owner, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(velocity_tracker, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeVelocityTracker(InitializeVelocityTracker).pack(),
+    }
+}
+
+/// Creates an `InitializeAmountLimits` instruction
+pub fn initialize_amount_limits(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    min_amount: u64,
+    max_amount: u64,
+) -> Instruction {
+    let (amount_limits, _) = find_amount_limits_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(amount_limits, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeAmountLimits(InitializeAmountLimits {
+            authority: *authority,
+            min_amount,
+            max_amount,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetAmountLimits` instruction
+pub fn set_amount_limits(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    min_amount: u64,
+    max_amount: u64,
+) -> Instruction {
+    let (amount_limits, _) = find_amount_limits_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(amount_limits, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: PolicyInstruction::SetAmountLimits(SetAmountLimits {
+            min_amount,
+            max_amount,
+        })
+        .pack(),
+    }
+}
+
+/// Creates an `InitializeTradingSchedule` instruction
+pub fn initialize_trading_schedule(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    authority: &Pubkey,
+    unlock_timestamp: i64,
+    daily_windows: Vec<DailyWindow>,
+) -> Instruction {
+    let (trading_schedule, _) = find_trading_schedule_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(trading_schedule, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeTradingSchedule(InitializeTradingSchedule {
+            authority: *authority,
+            unlock_timestamp,
+            daily_windows,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Accounts of an instruction resizing the trading schedule of `mint`
+fn resize_trading_schedule_accounts(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (trading_schedule, _) = find_trading_schedule_address(mint, program_id);
+
+    vec![
+        AccountMeta::new(trading_schedule, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]
+}
+
+/// Creates a `SetTradingSchedule` instruction
+pub fn set_trading_schedule(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    unlock_timestamp: i64,
+    daily_windows: Vec<DailyWindow>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: resize_trading_schedule_accounts(program_id, mint, authority, payer),
+        data: PolicyInstruction::SetTradingSchedule(SetTradingSchedule {
+            unlock_timestamp,
+            daily_windows,
+        })
+        .pack(),
+    }
+}
+
+/// Creates an `AddScheduleExemptions` instruction
+pub fn add_schedule_exemptions(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: resize_trading_schedule_accounts(program_id, mint, authority, payer),
+        data: PolicyInstruction::AddScheduleExemptions(AddScheduleExemptions { accounts }).pack(),
+    }
+}
+
+/// Creates a `RemoveScheduleExemptions` instruction
+pub fn remove_schedule_exemptions(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    let (trading_schedule, _) = find_trading_schedule_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(trading_schedule, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: PolicyInstruction::RemoveScheduleExemptions(RemoveScheduleExemptions { accounts })
+            .pack(),
+    }
+}
+
+/// Creates an `InitializeVestingSchedule` instruction
+pub fn initialize_vesting_schedule(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    beneficiary: &Pubkey,
+    data: InitializeVestingSchedule,
+) -> Instruction {
+    let (vesting_schedule, _) = find_vesting_schedule_address(mint, beneficiary, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(vesting_schedule, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new_readonly(*beneficiary, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeVestingSchedule(data).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `RevokeVestingSchedule` instruction
+pub fn revoke_vesting_schedule(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let (vesting_schedule, _) = find_vesting_schedule_address(mint, beneficiary, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(vesting_schedule, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*destination, false),
+        ],
+        data: PolicyInstruction::RevokeVestingSchedule(RevokeVestingSchedule).pack(),
+    }
+}
+
+/// Creates an `InitializeTransferStats` instruction
+pub fn initialize_transfer_stats(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (transfer_stats, _) = find_transfer_stats_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(transfer_stats, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeTransferStats(InitializeTransferStats).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates an `InitializeOwnerStats` instruction
+pub fn initialize_owner_stats(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (owner_stats, _) = find_owner_stats_address(mint, owner, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(owner_stats, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeOwnerStats(InitializeOwnerStats).pack(),
+    }
+}
+
+/// Creates an `InitializeHookConfig` instruction
+pub fn initialize_hook_config(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    admin: &Pubkey,
+) -> Instruction {
+    let (hook_config, _) = find_hook_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(hook_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeHookConfig(InitializeHookConfig { admin: *admin })
+            .pack(),
+    }
+}
+
+/// Creates a `ProposeHookAdmin` instruction
+pub fn propose_hook_admin(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    admin: &Pubkey,
+    new_admin: &Pubkey,
+) -> Instruction {
+    let (hook_config, _) = find_hook_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(hook_config, false),
+            AccountMeta::new_readonly(*admin, true),
+        ],
+        data: PolicyInstruction::ProposeHookAdmin(ProposeHookAdmin {
+            new_admin: *new_admin,
+        })
+        .pack(),
+    }
+}
+
+/// Creates an `AcceptHookAdmin` instruction
+pub fn accept_hook_admin(program_id: &Pubkey, mint: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let (hook_config, _) = find_hook_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(hook_config, false),
+            AccountMeta::new_readonly(*new_admin, true),
+        ],
+        data: PolicyInstruction::AcceptHookAdmin(AcceptHookAdmin).pack(),
+    }
+}
+
+/// Creates an `InitializeRoyalty` instruction
+pub fn initialize_royalty(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    data: InitializeRoyalty,
+) -> Instruction {
+    let (royalty_config, _) = find_royalty_config_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(royalty_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeRoyalty(data).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetRoyalty` instruction
+pub fn set_royalty(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    data: SetRoyalty,
+) -> Instruction {
+    let (royalty_config, _) = find_royalty_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(royalty_config, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: PolicyInstruction::SetRoyalty(data).pack(),
+    }
+}
+
+/// Creates an `InitializeMemoConfig` instruction
+pub fn initialize_memo_config(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    data: InitializeMemoConfig,
+) -> Instruction {
+    let (memo_config, _) = find_memo_config_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(memo_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeMemoConfig(data).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetMemoConfig` instruction
+pub fn set_memo_config(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    data: SetMemoConfig,
+) -> Instruction {
+    let (memo_config, _) = find_memo_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(memo_config, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::SetMemoConfig(data).pack(),
+    }
+}
+
+/// Creates an `InitializePause` instruction
+pub fn initialize_pause(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    guardian: &Pubkey,
+) -> Instruction {
+    let (pause, _) = find_pause_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(pause, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializePause(InitializePause {
+            guardian: *guardian,
+        })
+        .pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Accounts of an instruction the guardian of `mint` signs to flip its pause
+/// state
+fn guardian_accounts(program_id: &Pubkey, mint: &Pubkey, guardian: &Pubkey) -> Vec<AccountMeta> {
+    let (pause, _) = find_pause_address(mint, program_id);
+
+    vec![
+        AccountMeta::new(pause, false),
+        AccountMeta::new_readonly(*guardian, true),
+    ]
+}
+
+/// Creates a `Pause` instruction
+pub fn pause(program_id: &Pubkey, mint: &Pubkey, guardian: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: guardian_accounts(program_id, mint, guardian),
+        data: PolicyInstruction::Pause(Pause).pack(),
+    }
+}
+
+/// Creates an `Unpause` instruction
+pub fn unpause(program_id: &Pubkey, mint: &Pubkey, guardian: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: guardian_accounts(program_id, mint, guardian),
+        data: PolicyInstruction::Unpause(Unpause).pack(),
+    }
+}
+
+/// Creates an `AddPauseExemptions` instruction
+pub fn add_pause_exemptions(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    guardian: &Pubkey,
+    payer: &Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    let mut metas = guardian_accounts(program_id, mint, guardian);
+    metas.extend([
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ]);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: metas,
+        data: PolicyInstruction::AddPauseExemptions(AddPauseExemptions { accounts }).pack(),
+    }
+}
+
+/// Creates a `RemovePauseExemptions` instruction
+pub fn remove_pause_exemptions(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    guardian: &Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: guardian_accounts(program_id, mint, guardian),
+        data: PolicyInstruction::RemovePauseExemptions(RemovePauseExemptions { accounts }).pack(),
+    }
+}
+
+/// Creates an `InitializeKycConfig` instruction
+pub fn initialize_kyc_config(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    data: InitializeKycConfig,
+) -> Instruction {
+    let (kyc_config, _) = find_kyc_config_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(kyc_config, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeKycConfig(data).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetKycConfig` instruction
+pub fn set_kyc_config(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    data: SetKycConfig,
+) -> Instruction {
+    let (kyc_config, _) = find_kyc_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(kyc_config, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::SetKycConfig(data).pack(),
+    }
+}
+
+/// Creates an `IssueAttestation` instruction
+pub fn issue_attestation(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    attester: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+    data: IssueAttestation,
+) -> Instruction {
+    let (attestation, _) = find_attestation_address(mint, owner, program_id);
+    let (kyc_config, _) = find_kyc_config_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(attestation, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(kyc_config, false),
+            AccountMeta::new_readonly(*attester, true),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::IssueAttestation(data).pack(),
+    }
+}
+
+/// Creates a `RevokeAttestation` instruction
+pub fn revoke_attestation(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    attester: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let (attestation, _) = find_attestation_address(mint, owner, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(attestation, false),
+            AccountMeta::new_readonly(*attester, true),
+            AccountMeta::new(*destination, false),
+        ],
+        data: PolicyInstruction::RevokeAttestation(RevokeAttestation).pack(),
+    }
+}
+
+/// Creates an `InitializeHolderCount` instruction
+pub fn initialize_holder_count(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    data: InitializeHolderCount,
+) -> Instruction {
+    let (holder_count, _) = find_holder_count_address(mint, program_id);
+
+    let instruction = Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(holder_count, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: PolicyInstruction::InitializeHolderCount(data).pack(),
+    };
+
+    with_hook_config(instruction, program_id, mint)
+}
+
+/// Creates a `SetHolderCount` instruction
+pub fn set_holder_count(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    data: SetHolderCount,
+) -> Instruction {
+    let (holder_count, _) = find_holder_count_address(mint, program_id);
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(holder_count, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: PolicyInstruction::SetHolderCount(data).pack(),
+    }
+}
+
+/// Passes the hook config of `mint` after the accounts of an instruction
+/// authorized by the hook admin or the mint authority, which the program
+/// needs to tell which of them signs. The policy builders here pass it
+/// already, while `InitializeExtraAccountMetaList` and
+/// `UpdateExtraAccountMetaList` come from the interface without it.
+pub fn with_hook_config(
+    mut instruction: Instruction,
+    program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let (hook_config, _) = find_hook_config_address(mint, program_id);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(hook_config, false));
+    instruction
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        spl_transfer_hook_interface::instruction::{
+            ExecuteInstruction, InitializeExtraAccountMetaListInstruction, TransferHookInstruction,
+            UpdateExtraAccountMetaListInstruction,
+        },
+    };
+
+    #[test]
+    fn policy_discriminators_are_unique() {
+        let discriminators = [
+            ExecuteInstruction::SPL_DISCRIMINATOR_SLICE,
+            InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE,
+            UpdateExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE,
+            CloseExtraAccountMetaList::SPL_DISCRIMINATOR_SLICE,
+            InitializeAllowlist::SPL_DISCRIMINATOR_SLICE,
+            AddToAllowlist::SPL_DISCRIMINATOR_SLICE,
+            RemoveFromAllowlist::SPL_DISCRIMINATOR_SLICE,
+            InitializeBlocklist::SPL_DISCRIMINATOR_SLICE,
+            AddToBlocklist::SPL_DISCRIMINATOR_SLICE,
+            RemoveFromBlocklist::SPL_DISCRIMINATOR_SLICE,
+            InitializeVelocityLimit::SPL_DISCRIMINATOR_SLICE,
+            SetVelocityLimit::SPL_DISCRIMINATOR_SLICE,
+            InitializeVelocityTracker::SPL_DISCRIMINATOR_SLICE,
+            InitializeAmountLimits::SPL_DISCRIMINATOR_SLICE,
+            SetAmountLimits::SPL_DISCRIMINATOR_SLICE,
+            InitializeTradingSchedule::SPL_DISCRIMINATOR_SLICE,
+            SetTradingSchedule::SPL_DISCRIMINATOR_SLICE,
+            AddScheduleExemptions::SPL_DISCRIMINATOR_SLICE,
+            RemoveScheduleExemptions::SPL_DISCRIMINATOR_SLICE,
+            InitializeVestingSchedule::SPL_DISCRIMINATOR_SLICE,
+            RevokeVestingSchedule::SPL_DISCRIMINATOR_SLICE,
+            InitializeTransferStats::SPL_DISCRIMINATOR_SLICE,
+            InitializeOwnerStats::SPL_DISCRIMINATOR_SLICE,
+            InitializeHookConfig::SPL_DISCRIMINATOR_SLICE,
+            ProposeHookAdmin::SPL_DISCRIMINATOR_SLICE,
+            AcceptHookAdmin::SPL_DISCRIMINATOR_SLICE,
+            InitializeRoyalty::SPL_DISCRIMINATOR_SLICE,
+            SetRoyalty::SPL_DISCRIMINATOR_SLICE,
+            InitializeMemoConfig::SPL_DISCRIMINATOR_SLICE,
+            SetMemoConfig::SPL_DISCRIMINATOR_SLICE,
+            InitializePause::SPL_DISCRIMINATOR_SLICE,
+            Pause::SPL_DISCRIMINATOR_SLICE,
+            Unpause::SPL_DISCRIMINATOR_SLICE,
+            AddPauseExemptions::SPL_DISCRIMINATOR_SLICE,
+            RemovePauseExemptions::SPL_DISCRIMINATOR_SLICE,
+            InitializeKycConfig::SPL_DISCRIMINATOR_SLICE,
+            SetKycConfig::SPL_DISCRIMINATOR_SLICE,
+            IssueAttestation::SPL_DISCRIMINATOR_SLICE,
+            RevokeAttestation::SPL_DISCRIMINATOR_SLICE,
+            InitializeHolderCount::SPL_DISCRIMINATOR_SLICE,
+            SetHolderCount::SPL_DISCRIMINATOR_SLICE,
+        ];
+
+        for (index, discriminator) in discriminators.iter().enumerate() {
+            assert!(
+                !discriminators[index + 1..].contains(discriminator),
+                "{discriminator:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn policy_instructions_round_trip() {
+        let instructions = [
+            PolicyInstruction::CloseExtraAccountMetaList(CloseExtraAccountMetaList),
+            PolicyInstruction::AddToBlocklist(AddToBlocklist {
+                owners: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            }),
+            PolicyInstruction::SetTradingSchedule(SetTradingSchedule {
+                unlock_timestamp: -1,
+                daily_windows: vec![DailyWindow { start: 1, end: 2 }],
+            }),
+            PolicyInstruction::InitializeVestingSchedule(InitializeVestingSchedule {
+                authority: Pubkey::new_unique(),
+                total_amount: u64::MAX,
+                start_timestamp: 1,
+                cliff_timestamp: 2,
+                end_timestamp: 3,
+            }),
+        ];
+
+        for instruction in instructions {
+            assert_eq!(
+                PolicyInstruction::unpack(&instruction.pack()),
+                Ok(instruction)
+            );
+        }
+        let execute = TransferHookInstruction::Execute { amount: 1 }.pack();
+        assert_eq!(PolicyInstruction::try_unpack(&execute), Ok(None));
+        assert_eq!(PolicyInstruction::try_unpack(&[]), Ok(None));
+    }
+}
@@ -1,26 +1,13 @@
-//! Program entrypoint
-
-use {
-    solana_program::{
-        account_info::AccountInfo, entrypoint::ProgramResult, program_error::PrintProgramError,
-        pubkey::Pubkey,
-    },
-    spl_transfer_hook_interface::error::TransferHookError,
-};
-
-mod processor;
-
-solana_program::entrypoint!(process_instruction);
-fn process_instruction(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> ProgramResult {
-    if let Err(error) = processor::process(program_id, accounts, instruction_data) {
-        // catch the error so we can print it
-        error.print::<TransferHookError>();
-        return Err(error);
-    }
-
-    Ok(())
-}
+//! Transfer hook program invoked by Token-2022 on every transfer of a mint
+//! that points to it
+
+#[cfg(not(feature = "no-entrypoint"))]
+mod entrypoint;
+pub mod error;
+pub mod instruction;
+#[cfg(feature = "forbid-additional-mints")]
+pub mod mint;
+pub mod processor;
+pub mod state;
+
+solana_program::declare_id!("Arafvy1MtnvKJXif3dSE3PT2ZsFwW9qLmncJBh9d4G88");
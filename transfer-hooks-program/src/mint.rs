@@ -0,0 +1,34 @@
+//! The only mint the program serves when built with the
+//! `forbid-additional-mints` feature
+//!
+//! The address is read from the `TRANSFER_HOOK_MINT` environment variable at
+//! build time, so each deployment can be locked to its own mint:
+//!
+//! ```sh
+//! TRANSFER_HOOK_MINT=<mint address> cargo build-sbf --features forbid-additional-mints
+//! ```
+//!
+//! Without the variable the placeholder [`DEFAULT`] is used, which no real
+//! mint has.
+
+use solana_program::pubkey::Pubkey;
+
+/// Address used when `TRANSFER_HOOK_MINT` is not set
+pub const DEFAULT: &str = "Mint111111111111111111111111111111111111111";
+
+/// The mint extra account metas can be initialized for
+pub const ID: Pubkey = Pubkey::from_str_const(match option_env!("TRANSFER_HOOK_MINT") {
+    Some(address) => address,
+    None => DEFAULT,
+});
+
+/// Returns the mint extra account metas can be initialized for
+pub const fn id() -> Pubkey {
+    ID
+}
+
+/// Returns `true` if `id` is the mint extra account metas can be initialized
+/// for
+pub fn check_id(id: &Pubkey) -> bool {
+    *id == ID
+}
@@ -1,19 +1,55 @@
 //! Program state processor
 
 use {
+    crate::{
+        error::HookPolicyError,
+        instruction::{
+            InitializeAmountLimits, InitializeHolderCount, InitializeKycConfig,
+            InitializeMemoConfig, InitializeRoyalty, InitializeTradingSchedule,
+            InitializeVelocityLimit, InitializeVestingSchedule, IssueAttestation,
+            PolicyInstruction, SetAmountLimits, SetHolderCount, SetKycConfig, SetMemoConfig,
+            SetRoyalty, SetTradingSchedule, SetVelocityLimit,
+        },
+        state::{
+            find_amount_limits_address, find_attestation_address, find_holder_count_address,
+            find_hook_config_address, find_kyc_config_address, find_memo_config_address,
+            find_owner_stats_address, find_pause_address, find_royalty_config_address,
+            find_trading_schedule_address, find_transfer_stats_address,
+            find_velocity_limit_address, find_velocity_tracker_address,
+            find_vesting_schedule_address, Allowlist, AmountLimits, Attestation, Blocklist,
+            DailyWindow, HolderCount, HookConfig, KycConfig, MemoConfig, OwnerList, OwnerStats,
+            PauseState, PolicyAccount, RoyaltyConfig, TradingSchedule, TransferStats,
+            VelocityLimit, VelocityTracker, VestingSchedule, AMOUNT_LIMITS_SEED, ATTESTATION_SEED,
+            HOLDER_COUNT_SEED, HOOK_CONFIG_SEED, KYC_CONFIG_SEED, MEMO_CONFIG_SEED,
+            OWNER_STATS_SEED, PAUSE_SEED, ROYALTY_SEED, TRADING_SCHEDULE_SEED, TRANSFER_STATS_SEED,
+            VELOCITY_LIMIT_SEED, VELOCITY_TRACKER_SEED, VESTING_SCHEDULE_SEED,
+        },
+    },
     solana_program::{
         account_info::{next_account_info, AccountInfo},
+        clock::Clock,
         entrypoint::ProgramResult,
+        instruction::Instruction,
         msg,
-        program::invoke_signed,
+        program::{invoke, invoke_signed},
         program_error::ProgramError,
+        program_utils::limited_deserialize,
         pubkey::Pubkey,
-        system_instruction,
+        rent::Rent,
+        system_instruction::{self, SystemInstruction},
+        system_program,
+        sysvar::{
+            self,
+            instructions::{load_current_index_checked, load_instruction_at_checked},
+            Sysvar,
+        },
     },
     spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
     spl_token_2022::{
         extension::{
-            transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+            transfer_fee::TransferFeeConfig,
+            transfer_hook::{TransferHook, TransferHookAccount},
+            BaseStateWithExtensions, StateWithExtensions,
         },
         state::{Account, Mint},
     },
@@ -25,17 +61,492 @@ use {
     },
 };
 
-fn check_token_account_is_transferring(account_info: &AccountInfo) -> Result<(), ProgramError> {
+/// Checks that a token account is in the middle of a transfer and returns its
+/// base state
+fn check_token_account_is_transferring(
+    account_info: &AccountInfo,
+) -> Result<Account, ProgramError> {
     let account_data = account_info.try_borrow_data()?;
     let token_account = StateWithExtensions::<Account>::unpack(&account_data)?;
     let extension = token_account.get_extension::<TransferHookAccount>()?;
     if bool::from(extension.transferring) {
-        Ok(())
+        Ok(token_account.base)
     } else {
         Err(TransferHookError::ProgramCalledOutsideOfTransfer.into())
     }
 }
 
+/// Fee the token program withholds from a transfer of `amount` of the mint,
+/// which the destination does not receive
+fn transfer_fee(mint_info: &AccountInfo, amount: u64) -> Result<u64, ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let Ok(config) = mint.get_extension::<TransferFeeConfig>() else {
+        return Ok(0);
+    };
+
+    config
+        .calculate_epoch_fee(Clock::get()?.epoch, amount)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Checks that `authority_info` signed and is the mint authority of
+/// `mint_info`
+fn check_mint_authority(mint_info: &AccountInfo, authority_info: &AccountInfo) -> ProgramResult {
+    // check that the mint authority is valid without fully deserializing
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let mint_authority = mint
+        .base
+        .mint_authority
+        .ok_or(TransferHookError::MintHasNoMintAuthority)?;
+
+    // Check signers
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *authority_info.key != mint_authority {
+        return Err(TransferHookError::IncorrectMintAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `authority_info` signed and is the mint authority of
+/// `mint_info` or the authority of its transfer hook, so that a mint whose
+/// mint authority is revoked can still get a hook admin
+fn check_hook_config_authority(
+    mint_info: &AccountInfo,
+    authority_info: &AccountInfo,
+) -> ProgramResult {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let transfer_hook_authority = mint
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|transfer_hook| Option::<Pubkey>::from(transfer_hook.authority));
+    let authorities = [mint.base.mint_authority.into(), transfer_hook_authority];
+    if authorities.iter().all(Option::is_none) {
+        return Err(TransferHookError::MintHasNoMintAuthority.into());
+    }
+
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !authorities.contains(&Some(*authority_info.key)) {
+        return Err(TransferHookError::IncorrectMintAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `authority_info` signed and may manage the extra account metas
+/// of `mint_info`: the admin of the hook config once it exists, or the mint
+/// authority until then
+fn check_hook_authority(
+    program_id: &Pubkey,
+    mint_info: &AccountInfo,
+    authority_info: &AccountInfo,
+    hook_config_info: &AccountInfo,
+) -> ProgramResult {
+    let (expected_config_address, _) = find_hook_config_address(mint_info.key, program_id);
+    if expected_config_address != *hook_config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if hook_config_info.owner != program_id {
+        return check_mint_authority(mint_info, authority_info);
+    }
+    let config = unpack_policy_account::<HookConfig>(program_id, hook_config_info)?;
+
+    check_policy_authority(&config.admin, authority_info)
+}
+
+/// Checks that `authority_info` signed and is the authority of a policy
+/// account
+fn check_policy_authority(authority: &Pubkey, authority_info: &AccountInfo) -> ProgramResult {
+    if !authority_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if authority_info.key != authority {
+        return Err(HookPolicyError::IncorrectAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Deserializes a policy account, checking that the program owns it
+fn unpack_policy_account<T: PolicyAccount>(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+) -> Result<T, ProgramError> {
+    if account_info.owner != program_id {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    T::unpack(&account_info.try_borrow_data()?)
+}
+
+/// Serializes a policy account, resizing it to fit first if it grew
+fn pack_policy_account<'a, T: PolicyAccount>(
+    state: &T,
+    account_info: &AccountInfo<'a>,
+    payer_info: Option<&AccountInfo<'a>>,
+) -> ProgramResult {
+    let len = state.packed_len();
+    if len > account_info.data_len() {
+        let payer_info = payer_info.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let shortfall = Rent::get()?
+            .minimum_balance(len)
+            .saturating_sub(account_info.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(payer_info.key, account_info.key, shortfall),
+                &[payer_info.clone(), account_info.clone()],
+            )?;
+        }
+    }
+    account_info.realloc(len, false)?;
+
+    state.pack_into(&mut account_info.try_borrow_mut_data()?)
+}
+
+/// Creates a policy PDA holding `state`, funding any shortfall in rent from
+/// the payer
+fn create_policy_account<'a, T: PolicyAccount>(
+    program_id: &Pubkey,
+    state: &T,
+    account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let space = state.packed_len();
+    let shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account_info.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, shortfall),
+            &[payer_info.clone(), account_info.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account_info.key, space as u64),
+        std::slice::from_ref(account_info),
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account_info.key, program_id),
+        std::slice::from_ref(account_info),
+        &[signer_seeds],
+    )?;
+
+    state.pack_into(&mut account_info.try_borrow_mut_data()?)
+}
+
+/// Closes an account owned by the program, moving its lamports to the
+/// destination and handing it back to the system program
+fn close_program_account(
+    account_info: &AccountInfo,
+    destination_info: &AccountInfo,
+) -> ProgramResult {
+    let lamports = account_info.lamports();
+    **destination_info.try_borrow_mut_lamports()? = destination_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account_info.try_borrow_mut_lamports()? = 0;
+
+    account_info.realloc(0, false)?;
+    account_info.assign(&system_program::id());
+
+    Ok(())
+}
+
+/// The account at `address` among the extra accounts of an `Execute`
+/// instruction, present if the mint's extra account metas include it
+fn find_extra_account<'a, 'b>(
+    extra_account_infos: &'a [AccountInfo<'b>],
+    address: &Pubkey,
+) -> Option<&'a AccountInfo<'b>> {
+    extra_account_infos.iter().find(|info| info.key == address)
+}
+
+/// Whether `owner` is on the owner list `T` of `mint`, if the list is among
+/// the extra accounts
+fn is_listed<T: OwnerList>(
+    program_id: &Pubkey,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    extra_account_infos: &[AccountInfo],
+) -> Result<Option<bool>, ProgramError> {
+    let (address, _) = T::find_address(mint, program_id);
+    let Some(list_info) = find_extra_account(extra_account_infos, &address) else {
+        return Ok(None);
+    };
+    if list_info.owner != program_id {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    T::contains_packed(&list_info.try_borrow_data()?, owner).map(Some)
+}
+
+/// The transfer an `Execute` instruction is invoked for
+struct Transfer<'a> {
+    mint: &'a Pubkey,
+    source_address: &'a Pubkey,
+    destination_address: &'a Pubkey,
+    source: Account,
+    destination: Account,
+    amount: u64,
+    fee: u64,
+}
+
+/// Recipient and lamports of a system transfer
+fn system_transfer(instruction: &Instruction) -> Option<(Pubkey, u64)> {
+    if instruction.program_id != system_program::id() {
+        return None;
+    }
+
+    let data = &instruction.data;
+    match limited_deserialize(data, data.len() as u64).ok()? {
+        SystemInstruction::Transfer { lamports } => {
+            Some((instruction.accounts.get(1)?.pubkey, lamports))
+        }
+        SystemInstruction::TransferWithSeed { lamports, .. } => {
+            Some((instruction.accounts.get(2)?.pubkey, lamports))
+        }
+        _ => None,
+    }
+}
+
+/// Lamports the system transfers right before the current instruction send
+/// to the source owner, as the sale price, and to the creator vault, as the
+/// royalty paid.
+///
+/// Any other instruction ends the run of payments, so each hooked transfer
+/// in a transaction needs payments of its own and none counts twice. Only
+/// the top-level instructions are in the Instructions sysvar, so payments
+/// made through another program are not counted, and transfers one
+/// instruction makes through CPI share its payments.
+fn royalty_payments(
+    instructions_info: &AccountInfo,
+    seller: &Pubkey,
+    creator_vault: &Pubkey,
+) -> Result<(u64, u64), ProgramError> {
+    let mut price = 0u64;
+    let mut paid = 0u64;
+
+    let current = load_current_index_checked(instructions_info)?;
+    for index in (0..current).rev() {
+        let instruction = load_instruction_at_checked(index.into(), instructions_info)?;
+        let Some((recipient, lamports)) = system_transfer(&instruction) else {
+            break;
+        };
+        if recipient == *creator_vault {
+            paid = paid.saturating_add(lamports);
+        } else if recipient == *seller {
+            price = price.saturating_add(lamports);
+        }
+    }
+
+    Ok((price, paid))
+}
+
+/// Data of the last memo before the current instruction of the transaction
+fn preceding_memo(instructions_info: &AccountInfo) -> Result<Option<Vec<u8>>, ProgramError> {
+    let current = load_current_index_checked(instructions_info)?;
+    for index in (0..current).rev() {
+        let instruction = load_instruction_at_checked(index.into(), instructions_info)?;
+        if instruction.program_id == spl_memo::id() || instruction.program_id == spl_memo::v1::id()
+        {
+            return Ok(Some(instruction.data));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The Instructions sysvar, which policies inspecting the transaction add to
+/// the extra accounts
+fn find_instructions_sysvar<'a, 'b>(
+    extra_account_infos: &'a [AccountInfo<'b>],
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    find_extra_account(extra_account_infos, &sysvar::instructions::id())
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+/// Enforces the policies whose accounts are among the extra accounts
+fn enforce_policies(
+    program_id: &Pubkey,
+    transfer: &Transfer,
+    extra_account_infos: &[AccountInfo],
+) -> ProgramResult {
+    let mint = transfer.mint;
+    let source_owner = &transfer.source.owner;
+    let destination_owner = &transfer.destination.owner;
+
+    let (pause_address, _) = find_pause_address(mint, program_id);
+    if let Some(pause_info) = find_extra_account(extra_account_infos, &pause_address) {
+        unpack_policy_account::<PauseState>(program_id, pause_info)?
+            .check(transfer.source_address)?;
+    }
+
+    if is_listed::<Allowlist>(program_id, mint, destination_owner, extra_account_infos)?
+        == Some(false)
+    {
+        return Err(HookPolicyError::DestinationNotAllowlisted.into());
+    }
+
+    if is_listed::<Blocklist>(program_id, mint, source_owner, extra_account_infos)? == Some(true) {
+        return Err(HookPolicyError::SourceBlocklisted.into());
+    }
+    if is_listed::<Blocklist>(program_id, mint, destination_owner, extra_account_infos)?
+        == Some(true)
+    {
+        return Err(HookPolicyError::DestinationBlocklisted.into());
+    }
+
+    let (kyc_config_address, _) = find_kyc_config_address(mint, program_id);
+    if let Some(config_info) = find_extra_account(extra_account_infos, &kyc_config_address) {
+        let config = unpack_policy_account::<KycConfig>(program_id, config_info)?;
+        let attestation = |owner, error: HookPolicyError| {
+            let (address, _) = find_attestation_address(mint, owner, program_id);
+            let info = find_extra_account(extra_account_infos, &address)
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            // Owners that were never attested have no attestation account
+            if info.owner != program_id {
+                return Err(ProgramError::from(error));
+            }
+            Attestation::unpack(&info.try_borrow_data()?)
+        };
+        let source = attestation(source_owner, HookPolicyError::SourceNotAttested)?;
+        let destination = attestation(destination_owner, HookPolicyError::DestinationNotAttested)?;
+
+        config.check(
+            Clock::get()?.unix_timestamp,
+            transfer.amount,
+            &source,
+            &destination,
+        )?;
+    }
+
+    let (trading_schedule_address, _) = find_trading_schedule_address(mint, program_id);
+    if let Some(schedule_info) = find_extra_account(extra_account_infos, &trading_schedule_address)
+    {
+        let schedule = unpack_policy_account::<TradingSchedule>(program_id, schedule_info)?;
+        if !schedule.exempt_accounts.contains(transfer.source_address) {
+            schedule.check(Clock::get()?.unix_timestamp)?;
+        }
+    }
+
+    let (amount_limits_address, _) = find_amount_limits_address(mint, program_id);
+    if let Some(limits_info) = find_extra_account(extra_account_infos, &amount_limits_address) {
+        unpack_policy_account::<AmountLimits>(program_id, limits_info)?.check(transfer.amount)?;
+    }
+
+    let (royalty_address, _) = find_royalty_config_address(mint, program_id);
+    if let Some(royalty_info) = find_extra_account(extra_account_infos, &royalty_address) {
+        let royalty = unpack_policy_account::<RoyaltyConfig>(program_id, royalty_info)?;
+        let instructions_info = find_instructions_sysvar(extra_account_infos)?;
+        let (price, paid) =
+            royalty_payments(instructions_info, source_owner, &royalty.creator_vault)?;
+        royalty.check(price, paid)?;
+    }
+
+    let (memo_config_address, _) = find_memo_config_address(mint, program_id);
+    if let Some(config_info) = find_extra_account(extra_account_infos, &memo_config_address) {
+        let config = unpack_policy_account::<MemoConfig>(program_id, config_info)?;
+        let instructions_info = find_instructions_sysvar(extra_account_infos)?;
+        let memo = preceding_memo(instructions_info)?.ok_or(HookPolicyError::MemoRequired)?;
+        config.check(&memo)?;
+    }
+
+    let (vesting_address, _) = find_vesting_schedule_address(mint, source_owner, program_id);
+    if let Some(vesting_info) = find_extra_account(extra_account_infos, &vesting_address) {
+        // Owners without a vesting schedule have nothing locked
+        if vesting_info.owner == program_id {
+            let vesting = VestingSchedule::unpack(&vesting_info.try_borrow_data()?)?;
+            // The token program debits the source before invoking the hook,
+            // so its balance is what the transfer leaves
+            vesting.check(Clock::get()?.unix_timestamp, transfer.source.amount)?;
+        }
+    }
+
+    let (velocity_limit_address, _) = find_velocity_limit_address(mint, program_id);
+    if let Some(limit_info) = find_extra_account(extra_account_infos, &velocity_limit_address) {
+        let limit = unpack_policy_account::<VelocityLimit>(program_id, limit_info)?;
+        let (tracker_address, _) = find_velocity_tracker_address(mint, source_owner, program_id);
+        let tracker_info = find_extra_account(extra_account_infos, &tracker_address)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        if tracker_info.owner == program_id {
+            let mut tracker = unpack_policy_account::<VelocityTracker>(program_id, tracker_info)?;
+            tracker.record(&limit, Clock::get()?.unix_timestamp, transfer.amount)?;
+            pack_policy_account(&tracker, tracker_info, None)?;
+        } else if transfer.amount > limit.max_amount {
+            // An owner without a tracker has an empty window. The hook cannot
+            // create the tracker, so clients create it ahead of the transfer
+            // for the amount to be counted
+            return Err(HookPolicyError::VelocityLimitExceeded.into());
+        }
+    }
+
+    let (holder_count_address, _) = find_holder_count_address(mint, program_id);
+    if let Some(count_info) = find_extra_account(extra_account_infos, &holder_count_address) {
+        // A transfer to the same account changes no balance
+        if transfer.source_address != transfer.destination_address {
+            let mut count = unpack_policy_account::<HolderCount>(program_id, count_info)?;
+            // The token program moves the amount before invoking the hook,
+            // so both balances are what the transfer leaves
+            count.record(
+                transfer.amount,
+                transfer.fee,
+                transfer.source.amount,
+                transfer.destination.amount,
+            )?;
+            pack_policy_account(&count, count_info, None)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Counts the transfer in the stats whose accounts are among the extra
+/// accounts
+fn record_stats(
+    program_id: &Pubkey,
+    transfer: &Transfer,
+    extra_account_infos: &[AccountInfo],
+) -> ProgramResult {
+    let mint = transfer.mint;
+
+    let (transfer_stats_address, _) = find_transfer_stats_address(mint, program_id);
+    if let Some(stats_info) = find_extra_account(extra_account_infos, &transfer_stats_address) {
+        let mut stats = unpack_policy_account::<TransferStats>(program_id, stats_info)?;
+        stats.record(Clock::get()?.slot, transfer.amount);
+        pack_policy_account(&stats, stats_info, None)?;
+    }
+
+    // Owners without stats are not counted. Both sides of a transfer between
+    // accounts of the same owner resolve to the same stats, which are then
+    // updated twice.
+    let owner_stats_info = |owner| {
+        let (address, _) = find_owner_stats_address(mint, owner, program_id);
+        find_extra_account(extra_account_infos, &address).filter(|info| info.owner == program_id)
+    };
+    if let Some(stats_info) = owner_stats_info(&transfer.source.owner) {
+        let mut stats = OwnerStats::unpack(&stats_info.try_borrow_data()?)?;
+        stats.record_sent(transfer.amount);
+        pack_policy_account(&stats, stats_info, None)?;
+    }
+    if let Some(stats_info) = owner_stats_info(&transfer.destination.owner) {
+        let mut stats = OwnerStats::unpack(&stats_info.try_borrow_data()?)?;
+        stats.record_received(transfer.amount);
+        pack_policy_account(&stats, stats_info, None)?;
+    }
+
+    Ok(())
+}
+
 /// Processes an [Execute](enum.TransferHookInstruction.html) instruction.
 pub fn process_execute(
     program_id: &Pubkey,
@@ -50,9 +561,11 @@ pub fn process_execute(
     let _authority_info = next_account_info(account_info_iter)?;
     let extra_account_metas_info = next_account_info(account_info_iter)?;
 
+    let extra_account_infos = account_info_iter.as_slice();
+
     // Check that the accounts are properly in "transferring" mode
-    check_token_account_is_transferring(source_account_info)?;
-    check_token_account_is_transferring(destination_account_info)?;
+    let source_account = check_token_account_is_transferring(source_account_info)?;
+    let destination_account = check_token_account_is_transferring(destination_account_info)?;
 
     // For the example program, we just check that the correct pda and validation
     // pubkeys are provided
@@ -70,6 +583,18 @@ pub fn process_execute(
         &data,
     )?;
 
+    let transfer = Transfer {
+        mint: mint_info.key,
+        source_address: source_account_info.key,
+        destination_address: destination_account_info.key,
+        source: source_account,
+        destination: destination_account,
+        amount,
+        fee: transfer_fee(mint_info, amount)?,
+    };
+    enforce_policies(program_id, &transfer, extra_account_infos)?;
+    record_stats(program_id, &transfer, extra_account_infos)?;
+
     msg!("EXECUTED-----------------");
 
     Ok(())
@@ -90,28 +615,15 @@ pub fn process_initialize_extra_account_meta_list(
     let mint_info = next_account_info(account_info_iter)?;
     let authority_info = next_account_info(account_info_iter)?;
     let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
 
     // check that the one mint we want to target is trying to create extra account metas
     #[cfg(feature = "forbid-additional-mints")]
-    if *mint_info.key != crate::mint::id() {
+    if !crate::mint::check_id(mint_info.key) {
         return Err(ProgramError::InvalidArgument);
     }
 
-    // check that the mint authority is valid without fully deserializing
-    let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
-    let mint_authority = mint
-        .base
-        .mint_authority
-        .ok_or(TransferHookError::MintHasNoMintAuthority)?;
-
-    // Check signers
-    if !authority_info.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-    if *authority_info.key != mint_authority {
-        return Err(TransferHookError::IncorrectMintAuthority.into());
-    }
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
 
     // Check validation account
     let (expected_validation_address, bump_seed) =
@@ -127,12 +639,12 @@ pub fn process_initialize_extra_account_meta_list(
     let account_size = ExtraAccountMetaList::size_of(length)?;
     invoke_signed(
         &system_instruction::allocate(extra_account_metas_info.key, account_size as u64),
-        &[extra_account_metas_info.clone()],
+        std::slice::from_ref(extra_account_metas_info),
         &[&signer_seeds],
     )?;
     invoke_signed(
         &system_instruction::assign(extra_account_metas_info.key, program_id),
-        &[extra_account_metas_info.clone()],
+        std::slice::from_ref(extra_account_metas_info),
         &[&signer_seeds],
     )?;
 
@@ -156,22 +668,9 @@ pub fn process_update_extra_account_meta_list(
     let extra_account_metas_info = next_account_info(account_info_iter)?;
     let mint_info = next_account_info(account_info_iter)?;
     let authority_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
 
-    // check that the mint authority is valid without fully deserializing
-    let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
-    let mint_authority = mint
-        .base
-        .mint_authority
-        .ok_or(TransferHookError::MintHasNoMintAuthority)?;
-
-    // Check signers
-    if !authority_info.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-    if *authority_info.key != mint_authority {
-        return Err(TransferHookError::IncorrectMintAuthority.into());
-    }
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
 
     // Check validation account
     let expected_validation_address = get_extra_account_metas_address(mint_info.key, program_id);
@@ -205,26 +704,2617 @@ pub fn process_update_extra_account_meta_list(
     Ok(())
 }
 
-/// Processes an [Instruction](enum.Instruction.html).
-pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
-    let instruction = TransferHookInstruction::unpack(input)?;
+/// Processes a [CloseExtraAccountMetaList](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_close_extra_account_meta_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
 
-    match instruction {
-        TransferHookInstruction::Execute { amount } => {
-            msg!("Instruction: Execute");
-            process_execute(program_id, accounts, amount)
-        }
-        TransferHookInstruction::InitializeExtraAccountMetaList {
-            extra_account_metas,
-        } => {
-            msg!("Instruction: InitializeExtraAccountMetaList");
-            process_initialize_extra_account_meta_list(program_id, accounts, &extra_account_metas)
-        }
-        TransferHookInstruction::UpdateExtraAccountMetaList {
-            extra_account_metas,
-        } => {
-            msg!("Instruction: UpdateExtraAccountMetaList");
-            process_update_extra_account_meta_list(program_id, accounts, &extra_account_metas)
-        }
+    let extra_account_metas_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    // Check validation account
+    let expected_validation_address = get_extra_account_metas_address(mint_info.key, program_id);
+    if expected_validation_address != *extra_account_metas_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if program_id != extra_account_metas_info.owner {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    extra_account_metas_info.try_borrow_mut_data()?.fill(0);
+
+    close_program_account(extra_account_metas_info, destination_info)
+}
+
+/// Processes an [InitializeAllowlist](enum.PolicyInstruction.html) or
+/// [InitializeBlocklist](enum.PolicyInstruction.html) instruction.
+pub fn process_initialize_owner_list<T: OwnerList>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let list_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_list_address, bump_seed) = T::find_address(mint_info.key, program_id);
+    if expected_list_address != *list_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_policy_account(
+        program_id,
+        &T::new(authority),
+        list_info,
+        payer_info,
+        &[T::SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes an [AddToAllowlist](enum.PolicyInstruction.html) or
+/// [AddToBlocklist](enum.PolicyInstruction.html) instruction.
+pub fn process_add_owners<T: OwnerList>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    owners: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let list_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut list = unpack_policy_account::<T>(program_id, list_info)?;
+    check_policy_authority(list.authority(), authority_info)?;
+
+    list.owners_mut().extend(owners);
+
+    pack_policy_account(&list, list_info, Some(payer_info))
+}
+
+/// Processes a [RemoveFromAllowlist](enum.PolicyInstruction.html) or
+/// [RemoveFromBlocklist](enum.PolicyInstruction.html) instruction.
+pub fn process_remove_owners<T: OwnerList>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    owners: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let list_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut list = unpack_policy_account::<T>(program_id, list_info)?;
+    check_policy_authority(list.authority(), authority_info)?;
+
+    for owner in owners {
+        list.owners_mut().remove(owner);
+    }
+
+    pack_policy_account(&list, list_info, None)
+}
+
+/// Processes an [InitializeVelocityLimit](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_velocity_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeVelocityLimit,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let limit_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_limit_address, bump_seed) =
+        find_velocity_limit_address(mint_info.key, program_id);
+    if expected_limit_address != *limit_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if data.window_seconds <= 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let limit = VelocityLimit {
+        authority: data.authority,
+        window_seconds: data.window_seconds,
+        max_amount: data.max_amount,
+    };
+    create_policy_account(
+        program_id,
+        &limit,
+        limit_info,
+        payer_info,
+        &[VELOCITY_LIMIT_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetVelocityLimit](enum.PolicyInstruction.html) instruction.
+pub fn process_set_velocity_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetVelocityLimit,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let limit_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut limit = unpack_policy_account::<VelocityLimit>(program_id, limit_info)?;
+    check_policy_authority(&limit.authority, authority_info)?;
+    if data.window_seconds <= 0 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    limit.window_seconds = data.window_seconds;
+    limit.max_amount = data.max_amount;
+
+    pack_policy_account(&limit, limit_info, None)
+}
+
+/// Processes an [InitializeVelocityTracker](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_velocity_tracker(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let tracker_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let (expected_tracker_address, bump_seed) =
+        find_velocity_tracker_address(mint_info.key, owner_info.key, program_id);
+    if expected_tracker_address != *tracker_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_policy_account(
+        program_id,
+        &VelocityTracker::default(),
+        tracker_info,
+        payer_info,
+        &[
+            VELOCITY_TRACKER_SEED,
+            mint_info.key.as_ref(),
+            owner_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )
+}
+
+/// Processes an [InitializeAmountLimits](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_amount_limits(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeAmountLimits,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let limits_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_limits_address, bump_seed) =
+        find_amount_limits_address(mint_info.key, program_id);
+    if expected_limits_address != *limits_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if data.min_amount > data.max_amount {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let limits = AmountLimits {
+        authority: data.authority,
+        min_amount: data.min_amount,
+        max_amount: data.max_amount,
+    };
+    create_policy_account(
+        program_id,
+        &limits,
+        limits_info,
+        payer_info,
+        &[AMOUNT_LIMITS_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetAmountLimits](enum.PolicyInstruction.html) instruction.
+pub fn process_set_amount_limits(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetAmountLimits,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let limits_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut limits = unpack_policy_account::<AmountLimits>(program_id, limits_info)?;
+    check_policy_authority(&limits.authority, authority_info)?;
+    if data.min_amount > data.max_amount {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    limits.min_amount = data.min_amount;
+    limits.max_amount = data.max_amount;
+
+    pack_policy_account(&limits, limits_info, None)
+}
+
+/// Checks the daily windows of a trading schedule
+fn check_daily_windows(daily_windows: &[DailyWindow]) -> ProgramResult {
+    if daily_windows.iter().all(DailyWindow::is_valid) {
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidArgument)
+    }
+}
+
+/// Processes an [InitializeTradingSchedule](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_trading_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeTradingSchedule,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let schedule_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_schedule_address, bump_seed) =
+        find_trading_schedule_address(mint_info.key, program_id);
+    if expected_schedule_address != *schedule_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    check_daily_windows(&data.daily_windows)?;
+
+    let schedule = TradingSchedule {
+        authority: data.authority,
+        unlock_timestamp: data.unlock_timestamp,
+        daily_windows: data.daily_windows,
+        exempt_accounts: Default::default(),
+    };
+    create_policy_account(
+        program_id,
+        &schedule,
+        schedule_info,
+        payer_info,
+        &[TRADING_SCHEDULE_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetTradingSchedule](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_set_trading_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetTradingSchedule,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let schedule_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut schedule = unpack_policy_account::<TradingSchedule>(program_id, schedule_info)?;
+    check_policy_authority(&schedule.authority, authority_info)?;
+    check_daily_windows(&data.daily_windows)?;
+
+    schedule.unlock_timestamp = data.unlock_timestamp;
+    schedule.daily_windows = data.daily_windows;
+
+    pack_policy_account(&schedule, schedule_info, Some(payer_info))
+}
+
+/// Processes an [AddScheduleExemptions](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_add_schedule_exemptions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt_accounts: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let schedule_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut schedule = unpack_policy_account::<TradingSchedule>(program_id, schedule_info)?;
+    check_policy_authority(&schedule.authority, authority_info)?;
+
+    schedule.exempt_accounts.extend(exempt_accounts);
+
+    pack_policy_account(&schedule, schedule_info, Some(payer_info))
+}
+
+/// Processes a [RemoveScheduleExemptions](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_remove_schedule_exemptions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt_accounts: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let schedule_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut schedule = unpack_policy_account::<TradingSchedule>(program_id, schedule_info)?;
+    check_policy_authority(&schedule.authority, authority_info)?;
+
+    for account in exempt_accounts {
+        schedule.exempt_accounts.remove(account);
+    }
+
+    pack_policy_account(&schedule, schedule_info, None)
+}
+
+/// Processes an [InitializeVestingSchedule](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_vesting_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeVestingSchedule,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let vesting_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let beneficiary_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_vesting_address, bump_seed) =
+        find_vesting_schedule_address(mint_info.key, beneficiary_info.key, program_id);
+    if expected_vesting_address != *vesting_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let vesting = VestingSchedule {
+        authority: data.authority,
+        total_amount: data.total_amount,
+        start_timestamp: data.start_timestamp,
+        cliff_timestamp: data.cliff_timestamp,
+        end_timestamp: data.end_timestamp,
+    };
+    if !vesting.is_valid() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    create_policy_account(
+        program_id,
+        &vesting,
+        vesting_info,
+        payer_info,
+        &[
+            VESTING_SCHEDULE_SEED,
+            mint_info.key.as_ref(),
+            beneficiary_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )
+}
+
+/// Processes a [RevokeVestingSchedule](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_revoke_vesting_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let vesting_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+
+    let vesting = unpack_policy_account::<VestingSchedule>(program_id, vesting_info)?;
+    check_policy_authority(&vesting.authority, authority_info)?;
+
+    close_program_account(vesting_info, destination_info)
+}
+
+/// Processes an [InitializeTransferStats](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_transfer_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let stats_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_stats_address, bump_seed) =
+        find_transfer_stats_address(mint_info.key, program_id);
+    if expected_stats_address != *stats_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_policy_account(
+        program_id,
+        &TransferStats::default(),
+        stats_info,
+        payer_info,
+        &[TRANSFER_STATS_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes an [InitializeOwnerStats](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_owner_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let stats_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let (expected_stats_address, bump_seed) =
+        find_owner_stats_address(mint_info.key, owner_info.key, program_id);
+    if expected_stats_address != *stats_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_policy_account(
+        program_id,
+        &OwnerStats::default(),
+        stats_info,
+        payer_info,
+        &[
+            OWNER_STATS_SEED,
+            mint_info.key.as_ref(),
+            owner_info.key.as_ref(),
+            &[bump_seed],
+        ],
+    )
+}
+
+/// Processes an [InitializeHookConfig](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_hook_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    admin: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    check_hook_config_authority(mint_info, authority_info)?;
+
+    let (expected_config_address, bump_seed) = find_hook_config_address(mint_info.key, program_id);
+    if expected_config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = HookConfig {
+        admin,
+        pending_admin: Pubkey::default(),
+    };
+    create_policy_account(
+        program_id,
+        &config,
+        config_info,
+        payer_info,
+        &[HOOK_CONFIG_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [ProposeHookAdmin](enum.PolicyInstruction.html) instruction.
+pub fn process_propose_hook_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_admin: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let admin_info = next_account_info(account_info_iter)?;
+
+    let mut config = unpack_policy_account::<HookConfig>(program_id, config_info)?;
+    check_policy_authority(&config.admin, admin_info)?;
+
+    config.pending_admin = new_admin;
+
+    pack_policy_account(&config, config_info, None)
+}
+
+/// Processes an [AcceptHookAdmin](enum.PolicyInstruction.html) instruction.
+pub fn process_accept_hook_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let new_admin_info = next_account_info(account_info_iter)?;
+
+    let mut config = unpack_policy_account::<HookConfig>(program_id, config_info)?;
+    if config.pending_admin == Pubkey::default() {
+        return Err(HookPolicyError::IncorrectAuthority.into());
+    }
+    check_policy_authority(&config.pending_admin, new_admin_info)?;
+
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+
+    pack_policy_account(&config, config_info, None)
+}
+
+/// Processes an [InitializeRoyalty](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_royalty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeRoyalty,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let royalty_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_royalty_address, bump_seed) =
+        find_royalty_config_address(mint_info.key, program_id);
+    if expected_royalty_address != *royalty_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let royalty = RoyaltyConfig {
+        authority: data.authority,
+        creator_vault: data.creator_vault,
+        basis_points: data.basis_points,
+        min_lamports: data.min_lamports,
+    };
+    if !royalty.is_valid() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    create_policy_account(
+        program_id,
+        &royalty,
+        royalty_info,
+        payer_info,
+        &[ROYALTY_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetRoyalty](enum.PolicyInstruction.html) instruction.
+pub fn process_set_royalty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetRoyalty,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let royalty_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut royalty = unpack_policy_account::<RoyaltyConfig>(program_id, royalty_info)?;
+    check_policy_authority(&royalty.authority, authority_info)?;
+
+    royalty.creator_vault = data.creator_vault;
+    royalty.basis_points = data.basis_points;
+    royalty.min_lamports = data.min_lamports;
+    if !royalty.is_valid() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    pack_policy_account(&royalty, royalty_info, None)
+}
+
+/// Processes an [InitializeMemoConfig](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_memo_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeMemoConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_config_address, bump_seed) = find_memo_config_address(mint_info.key, program_id);
+    if expected_config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = MemoConfig {
+        authority: data.authority,
+        max_length: data.max_length,
+        pattern: data.pattern,
+    };
+    create_policy_account(
+        program_id,
+        &config,
+        config_info,
+        payer_info,
+        &[MEMO_CONFIG_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetMemoConfig](enum.PolicyInstruction.html) instruction.
+pub fn process_set_memo_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetMemoConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut config = unpack_policy_account::<MemoConfig>(program_id, config_info)?;
+    check_policy_authority(&config.authority, authority_info)?;
+
+    config.max_length = data.max_length;
+    config.pattern = data.pattern;
+
+    pack_policy_account(&config, config_info, Some(payer_info))
+}
+
+/// Processes an [InitializePause](enum.PolicyInstruction.html) instruction.
+pub fn process_initialize_pause(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    guardian: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let pause_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_pause_address, bump_seed) = find_pause_address(mint_info.key, program_id);
+    if expected_pause_address != *pause_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let pause = PauseState {
+        guardian,
+        ..PauseState::default()
+    };
+    create_policy_account(
+        program_id,
+        &pause,
+        pause_info,
+        payer_info,
+        &[PAUSE_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [Pause](enum.PolicyInstruction.html) or
+/// [Unpause](enum.PolicyInstruction.html) instruction.
+pub fn process_set_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let pause_info = next_account_info(account_info_iter)?;
+    let guardian_info = next_account_info(account_info_iter)?;
+
+    let mut pause = unpack_policy_account::<PauseState>(program_id, pause_info)?;
+    check_policy_authority(&pause.guardian, guardian_info)?;
+
+    pause.paused = paused;
+
+    pack_policy_account(&pause, pause_info, None)
+}
+
+/// Processes an [AddPauseExemptions](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_add_pause_exemptions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt_accounts: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let pause_info = next_account_info(account_info_iter)?;
+    let guardian_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut pause = unpack_policy_account::<PauseState>(program_id, pause_info)?;
+    check_policy_authority(&pause.guardian, guardian_info)?;
+
+    pause.exempt_accounts.extend(exempt_accounts);
+
+    pack_policy_account(&pause, pause_info, Some(payer_info))
+}
+
+/// Processes a [RemovePauseExemptions](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_remove_pause_exemptions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    exempt_accounts: &[Pubkey],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let pause_info = next_account_info(account_info_iter)?;
+    let guardian_info = next_account_info(account_info_iter)?;
+
+    let mut pause = unpack_policy_account::<PauseState>(program_id, pause_info)?;
+    check_policy_authority(&pause.guardian, guardian_info)?;
+
+    for account in exempt_accounts {
+        pause.exempt_accounts.remove(account);
+    }
+
+    pack_policy_account(&pause, pause_info, None)
+}
+
+/// Processes an [InitializeKycConfig](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_kyc_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeKycConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_config_address, bump_seed) = find_kyc_config_address(mint_info.key, program_id);
+    if expected_config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let config = KycConfig {
+        authority: data.authority,
+        attester: data.attester,
+        tier_limits: data.tier_limits,
+    };
+    create_policy_account(
+        program_id,
+        &config,
+        config_info,
+        payer_info,
+        &[KYC_CONFIG_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetKycConfig](enum.PolicyInstruction.html) instruction.
+pub fn process_set_kyc_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetKycConfig,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let config_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let mut config = unpack_policy_account::<KycConfig>(program_id, config_info)?;
+    check_policy_authority(&config.authority, authority_info)?;
+
+    config.attester = data.attester;
+    config.tier_limits = data.tier_limits;
+
+    pack_policy_account(&config, config_info, Some(payer_info))
+}
+
+/// Processes an [IssueAttestation](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_issue_attestation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: IssueAttestation,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attestation_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let config_info = next_account_info(account_info_iter)?;
+    let attester_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+
+    let (expected_config_address, _) = find_kyc_config_address(mint_info.key, program_id);
+    if expected_config_address != *config_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let config = unpack_policy_account::<KycConfig>(program_id, config_info)?;
+    check_policy_authority(&config.attester, attester_info)?;
+
+    let (expected_attestation_address, bump_seed) =
+        find_attestation_address(mint_info.key, owner_info.key, program_id);
+    if expected_attestation_address != *attestation_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let attestation = Attestation {
+        attester: config.attester,
+        tier: data.tier,
+        expiry_timestamp: data.expiry_timestamp,
+    };
+    if attestation_info.owner == program_id {
+        // Renewing the attestation, or reissuing one from a replaced attester
+        pack_policy_account(&attestation, attestation_info, None)
+    } else {
+        create_policy_account(
+            program_id,
+            &attestation,
+            attestation_info,
+            payer_info,
+            &[
+                ATTESTATION_SEED,
+                mint_info.key.as_ref(),
+                owner_info.key.as_ref(),
+                &[bump_seed],
+            ],
+        )
+    }
+}
+
+/// Processes a [RevokeAttestation](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_revoke_attestation(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let attestation_info = next_account_info(account_info_iter)?;
+    let attester_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+
+    let attestation = unpack_policy_account::<Attestation>(program_id, attestation_info)?;
+    check_policy_authority(&attestation.attester, attester_info)?;
+
+    close_program_account(attestation_info, destination_info)
+}
+
+/// Processes an [InitializeHolderCount](enum.PolicyInstruction.html)
+/// instruction.
+pub fn process_initialize_holder_count(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeHolderCount,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let count_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let _system_program_info = next_account_info(account_info_iter)?;
+    let hook_config_info = next_account_info(account_info_iter)?;
+
+    check_hook_authority(program_id, mint_info, authority_info, hook_config_info)?;
+
+    let (expected_count_address, bump_seed) = find_holder_count_address(mint_info.key, program_id);
+    if expected_count_address != *count_info.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let count = HolderCount {
+        authority: data.authority,
+        holder_count: data.holder_count,
+        max_holders: data.max_holders,
+    };
+    create_policy_account(
+        program_id,
+        &count,
+        count_info,
+        payer_info,
+        &[HOLDER_COUNT_SEED, mint_info.key.as_ref(), &[bump_seed]],
+    )
+}
+
+/// Processes a [SetHolderCount](enum.PolicyInstruction.html) instruction.
+pub fn process_set_holder_count(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: SetHolderCount,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let count_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+
+    let mut count = unpack_policy_account::<HolderCount>(program_id, count_info)?;
+    check_policy_authority(&count.authority, authority_info)?;
+
+    count.holder_count = data.holder_count;
+    count.max_holders = data.max_holders;
+
+    pack_policy_account(&count, count_info, None)
+}
+
+/// Processes an [Instruction](enum.Instruction.html).
+///
+/// Policy instructions are tried first, falling back to the transfer hook
+/// interface.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    if let Some(instruction) = PolicyInstruction::try_unpack(input)? {
+        return process_policy_instruction(program_id, accounts, instruction);
+    }
+
+    let instruction = TransferHookInstruction::unpack(input)?;
+
+    match instruction {
+        TransferHookInstruction::Execute { amount } => {
+            msg!("Instruction: Execute");
+            process_execute(program_id, accounts, amount)
+        }
+        TransferHookInstruction::InitializeExtraAccountMetaList {
+            extra_account_metas,
+        } => {
+            msg!("Instruction: InitializeExtraAccountMetaList");
+            process_initialize_extra_account_meta_list(program_id, accounts, &extra_account_metas)
+        }
+        TransferHookInstruction::UpdateExtraAccountMetaList {
+            extra_account_metas,
+        } => {
+            msg!("Instruction: UpdateExtraAccountMetaList");
+            process_update_extra_account_meta_list(program_id, accounts, &extra_account_metas)
+        }
+    }
+}
+
+fn process_policy_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: PolicyInstruction,
+) -> ProgramResult {
+    match instruction {
+        PolicyInstruction::CloseExtraAccountMetaList(_) => {
+            msg!("Instruction: CloseExtraAccountMetaList");
+            process_close_extra_account_meta_list(program_id, accounts)
+        }
+        PolicyInstruction::InitializeAllowlist(data) => {
+            msg!("Instruction: InitializeAllowlist");
+            process_initialize_owner_list::<Allowlist>(program_id, accounts, data.authority)
+        }
+        PolicyInstruction::AddToAllowlist(data) => {
+            msg!("Instruction: AddToAllowlist");
+            process_add_owners::<Allowlist>(program_id, accounts, data.owners)
+        }
+        PolicyInstruction::RemoveFromAllowlist(data) => {
+            msg!("Instruction: RemoveFromAllowlist");
+            process_remove_owners::<Allowlist>(program_id, accounts, &data.owners)
+        }
+        PolicyInstruction::InitializeBlocklist(data) => {
+            msg!("Instruction: InitializeBlocklist");
+            process_initialize_owner_list::<Blocklist>(program_id, accounts, data.authority)
+        }
+        PolicyInstruction::AddToBlocklist(data) => {
+            msg!("Instruction: AddToBlocklist");
+            process_add_owners::<Blocklist>(program_id, accounts, data.owners)
+        }
+        PolicyInstruction::RemoveFromBlocklist(data) => {
+            msg!("Instruction: RemoveFromBlocklist");
+            process_remove_owners::<Blocklist>(program_id, accounts, &data.owners)
+        }
+        PolicyInstruction::InitializeVelocityLimit(data) => {
+            msg!("Instruction: InitializeVelocityLimit");
+            process_initialize_velocity_limit(program_id, accounts, data)
+        }
+        PolicyInstruction::SetVelocityLimit(data) => {
+            msg!("Instruction: SetVelocityLimit");
+            process_set_velocity_limit(program_id, accounts, data)
+        }
+        PolicyInstruction::InitializeVelocityTracker(_) => {
+            msg!("Instruction: InitializeVelocityTracker");
+            process_initialize_velocity_tracker(program_id, accounts)
+        }
+        PolicyInstruction::InitializeAmountLimits(data) => {
+            msg!("Instruction: InitializeAmountLimits");
+            process_initialize_amount_limits(program_id, accounts, data)
+        }
+        PolicyInstruction::SetAmountLimits(data) => {
+            msg!("Instruction: SetAmountLimits");
+            process_set_amount_limits(program_id, accounts, data)
+        }
+        PolicyInstruction::InitializeTradingSchedule(data) => {
+            msg!("Instruction: InitializeTradingSchedule");
+            process_initialize_trading_schedule(program_id, accounts, data)
+        }
+        PolicyInstruction::SetTradingSchedule(data) => {
+            msg!("Instruction: SetTradingSchedule");
+            process_set_trading_schedule(program_id, accounts, data)
+        }
+        PolicyInstruction::AddScheduleExemptions(data) => {
+            msg!("Instruction: AddScheduleExemptions");
+            process_add_schedule_exemptions(program_id, accounts, data.accounts)
+        }
+        PolicyInstruction::RemoveScheduleExemptions(data) => {
+            msg!("Instruction: RemoveScheduleExemptions");
+            process_remove_schedule_exemptions(program_id, accounts, &data.accounts)
+        }
+        PolicyInstruction::InitializeVestingSchedule(data) => {
+            msg!("Instruction: InitializeVestingSchedule");
+            process_initialize_vesting_schedule(program_id, accounts, data)
+        }
+        PolicyInstruction::RevokeVestingSchedule(_) => {
+            msg!("Instruction: RevokeVestingSchedule");
+            process_revoke_vesting_schedule(program_id, accounts)
+        }
+        PolicyInstruction::InitializeTransferStats(_) => {
+            msg!("Instruction: InitializeTransferStats");
+            process_initialize_transfer_stats(program_id, accounts)
+        }
+        PolicyInstruction::InitializeOwnerStats(_) => {
+            msg!("Instruction: InitializeOwnerStats");
+            process_initialize_owner_stats(program_id, accounts)
+        }
+        PolicyInstruction::InitializeHookConfig(data) => {
+            msg!("Instruction: InitializeHookConfig");
+            process_initialize_hook_config(program_id, accounts, data.admin)
+        }
+        PolicyInstruction::ProposeHookAdmin(data) => {
+            msg!("Instruction: ProposeHookAdmin");
+            process_propose_hook_admin(program_id, accounts, data.new_admin)
+        }
+        PolicyInstruction::AcceptHookAdmin(_) => {
+            msg!("Instruction: AcceptHookAdmin");
+            process_accept_hook_admin(program_id, accounts)
+        }
+        PolicyInstruction::InitializeRoyalty(data) => {
+            msg!("Instruction: InitializeRoyalty");
+            process_initialize_royalty(program_id, accounts, data)
+        }
+        PolicyInstruction::SetRoyalty(data) => {
+            msg!("Instruction: SetRoyalty");
+            process_set_royalty(program_id, accounts, data)
+        }
+        PolicyInstruction::InitializeMemoConfig(data) => {
+            msg!("Instruction: InitializeMemoConfig");
+            process_initialize_memo_config(program_id, accounts, data)
+        }
+        PolicyInstruction::SetMemoConfig(data) => {
+            msg!("Instruction: SetMemoConfig");
+            process_set_memo_config(program_id, accounts, data)
+        }
+        PolicyInstruction::InitializePause(data) => {
+            msg!("Instruction: InitializePause");
+            process_initialize_pause(program_id, accounts, data.guardian)
+        }
+        PolicyInstruction::Pause(_) => {
+            msg!("Instruction: Pause");
+            process_set_paused(program_id, accounts, true)
+        }
+        PolicyInstruction::Unpause(_) => {
+            msg!("Instruction: Unpause");
+            process_set_paused(program_id, accounts, false)
+        }
+        PolicyInstruction::AddPauseExemptions(data) => {
+            msg!("Instruction: AddPauseExemptions");
+            process_add_pause_exemptions(program_id, accounts, data.accounts)
+        }
+        PolicyInstruction::RemovePauseExemptions(data) => {
+            msg!("Instruction: RemovePauseExemptions");
+            process_remove_pause_exemptions(program_id, accounts, &data.accounts)
+        }
+        PolicyInstruction::InitializeKycConfig(data) => {
+            msg!("Instruction: InitializeKycConfig");
+            process_initialize_kyc_config(program_id, accounts, data)
+        }
+        PolicyInstruction::SetKycConfig(data) => {
+            msg!("Instruction: SetKycConfig");
+            process_set_kyc_config(program_id, accounts, data)
+        }
+        PolicyInstruction::IssueAttestation(data) => {
+            msg!("Instruction: IssueAttestation");
+            process_issue_attestation(program_id, accounts, data)
+        }
+        PolicyInstruction::RevokeAttestation(_) => {
+            msg!("Instruction: RevokeAttestation");
+            process_revoke_attestation(program_id, accounts)
+        }
+        PolicyInstruction::InitializeHolderCount(data) => {
+            msg!("Instruction: InitializeHolderCount");
+            process_initialize_holder_count(program_id, accounts, data)
+        }
+        PolicyInstruction::SetHolderCount(data) => {
+            msg!("Instruction: SetHolderCount");
+            process_set_holder_count(program_id, accounts, data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instruction::AddToBlocklist,
+            state::{self, find_allowlist_address, find_blocklist_address, Policy, SortedPubkeys},
+        },
+        solana_program::{
+            entrypoint::MAX_PERMITTED_DATA_INCREASE,
+            instruction::AccountMeta,
+            program_option::COption,
+            program_pack::Pack,
+            system_program,
+            sysvar::instructions::{
+                construct_instructions_data, store_current_index, BorrowedAccountMeta,
+                BorrowedInstruction,
+            },
+        },
+        spl_discriminator::SplDiscriminate,
+        spl_tlv_account_resolution::error::AccountResolutionError,
+        spl_token_2022::extension::{
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        },
+    };
+
+    const KEY_OFFSET: usize = 8;
+    const DATA_OFFSET: usize = KEY_OFFSET + 32 + 8;
+
+    /// An account laid out in memory the way the runtime serializes it, with
+    /// the original data length right before the key and the current length
+    /// right before the data, followed by room to grow. `AccountInfo::realloc`
+    /// relies on both lengths.
+    struct TestAccount {
+        memory: Vec<u64>,
+        data_len: usize,
+        lamports: u64,
+        owner: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+    }
+
+    impl TestAccount {
+        fn new(key: &Pubkey, owner: &Pubkey, data: &[u8]) -> Self {
+            let len = DATA_OFFSET + data.len() + MAX_PERMITTED_DATA_INCREASE;
+            let mut memory = vec![0; len.div_ceil(8)];
+            let bytes = as_bytes_mut(&mut memory);
+            bytes[4..KEY_OFFSET].copy_from_slice(&(data.len() as u32).to_le_bytes());
+            bytes[KEY_OFFSET..DATA_OFFSET - 8].copy_from_slice(key.as_ref());
+            bytes[DATA_OFFSET - 8..DATA_OFFSET].copy_from_slice(&(data.len() as u64).to_le_bytes());
+            bytes[DATA_OFFSET..DATA_OFFSET + data.len()].copy_from_slice(data);
+
+            Self {
+                memory,
+                data_len: data.len(),
+                lamports: 1_000_000_000,
+                owner: *owner,
+                is_signer: false,
+                is_writable: false,
+            }
+        }
+
+        fn signer(mut self) -> Self {
+            self.is_signer = true;
+            self
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            let (head, tail) = as_bytes_mut(&mut self.memory).split_at_mut(DATA_OFFSET);
+            // SAFETY: `Pubkey` is a transparent wrapper around 32 bytes
+            let key = unsafe { &*(head[KEY_OFFSET..].as_ptr() as *const Pubkey) };
+
+            AccountInfo::new(
+                key,
+                self.is_signer,
+                self.is_writable,
+                &mut self.lamports,
+                &mut tail[..self.data_len],
+                &self.owner,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn as_bytes_mut(memory: &mut [u64]) -> &mut [u8] {
+        // SAFETY: any bit pattern is a valid `u8` and the length covers exactly
+        // the same memory
+        unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr().cast(), memory.len() * 8) }
+    }
+
+    fn mint_data(mint_authority: Option<Pubkey>) -> Vec<u8> {
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            mint_authority: mint_authority.into(),
+            is_initialized: true,
+            freeze_authority: COption::None,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    fn token_account_data(mint: &Pubkey, owner: &Pubkey, transferring: bool) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<Account>(&[
+            ExtensionType::TransferHookAccount,
+        ])
+        .unwrap();
+        let mut data = vec![0; len];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        state.base = Account {
+            mint: *mint,
+            owner: *owner,
+            amount: 100,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Account::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        state
+            .init_extension::<TransferHookAccount>(true)
+            .unwrap()
+            .transferring = transferring.into();
+        data
+    }
+
+    fn extra_account_metas_data(extra_account_metas: &[ExtraAccountMeta]) -> Vec<u8> {
+        let mut data = vec![0; ExtraAccountMetaList::size_of(extra_account_metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, extra_account_metas).unwrap();
+        data
+    }
+
+    fn extra_account_metas(keys: &[Pubkey]) -> Vec<ExtraAccountMeta> {
+        keys.iter()
+            .map(|key| AccountMeta::new_readonly(*key, false).into())
+            .collect()
+    }
+
+    /// Accounts of an `Execute` instruction for a transfer of `mint` resolving
+    /// one extra account
+    struct ExecuteAccounts {
+        source: TestAccount,
+        mint: TestAccount,
+        destination: TestAccount,
+        authority: TestAccount,
+        validation: TestAccount,
+        extras: Vec<TestAccount>,
+        mint_key: Pubkey,
+        source_owner: Pubkey,
+        destination_owner: Pubkey,
+    }
+
+    impl ExecuteAccounts {
+        fn new(program_id: &Pubkey, extra_key: &Pubkey) -> Self {
+            let mut accounts = Self::without_extras(program_id);
+            accounts.set_extras(
+                program_id,
+                &extra_account_metas(&[*extra_key]),
+                vec![TestAccount::new(extra_key, &system_program::id(), &[])],
+            );
+            accounts
+        }
+
+        /// Accounts resolving the accounts of `policy`, which are left
+        /// uninitialized
+        fn with_policy(program_id: &Pubkey, policy: Policy) -> Self {
+            let mut accounts = Self::without_extras(program_id);
+            let metas = state::extra_account_metas(policy);
+            let extras = policy_addresses(policy, &accounts, program_id)
+                .iter()
+                .zip(&metas)
+                .map(|(address, meta)| {
+                    let mut account = TestAccount::new(address, &system_program::id(), &[]);
+                    account.is_writable = meta.is_writable.into();
+                    account
+                })
+                .collect();
+            accounts.set_extras(program_id, &metas, extras);
+            accounts
+        }
+
+        fn without_extras(program_id: &Pubkey) -> Self {
+            let mint_key = Pubkey::new_unique();
+            let source_owner = Pubkey::new_unique();
+            let destination_owner = Pubkey::new_unique();
+
+            Self {
+                source: TestAccount::new(
+                    &Pubkey::new_unique(),
+                    &spl_token_2022::id(),
+                    &token_account_data(&mint_key, &source_owner, true),
+                ),
+                mint: TestAccount::new(&mint_key, &spl_token_2022::id(), &mint_data(None)),
+                destination: TestAccount::new(
+                    &Pubkey::new_unique(),
+                    &spl_token_2022::id(),
+                    &token_account_data(&mint_key, &destination_owner, true),
+                ),
+                authority: TestAccount::new(&Pubkey::new_unique(), &system_program::id(), &[]),
+                validation: TestAccount::new(
+                    &get_extra_account_metas_address(&mint_key, program_id),
+                    program_id,
+                    &extra_account_metas_data(&[]),
+                ),
+                extras: Vec::new(),
+                mint_key,
+                source_owner,
+                destination_owner,
+            }
+        }
+
+        fn set_extras(
+            &mut self,
+            program_id: &Pubkey,
+            extra_account_metas: &[ExtraAccountMeta],
+            extras: Vec<TestAccount>,
+        ) {
+            self.validation = TestAccount::new(
+                &get_extra_account_metas_address(&self.mint_key, program_id),
+                program_id,
+                &extra_account_metas_data(extra_account_metas),
+            );
+            self.extras = extras;
+        }
+
+        /// Initialize the policy account at `index` among the extras with
+        /// `data`
+        fn set_policy_data(&mut self, program_id: &Pubkey, index: usize, data: &[u8]) {
+            let extra = &mut self.extras[index];
+            let is_writable = extra.is_writable;
+            *extra = TestAccount::new(extra.info().key, program_id, data);
+            extra.is_writable = is_writable;
+        }
+
+        fn execute(&mut self, program_id: &Pubkey) -> ProgramResult {
+            let mut accounts = vec![
+                self.source.info(),
+                self.mint.info(),
+                self.destination.info(),
+                self.authority.info(),
+                self.validation.info(),
+            ];
+            accounts.extend(self.extras.iter_mut().map(TestAccount::info));
+
+            process_execute(program_id, &accounts, 10)
+        }
+    }
+
+    fn policy_addresses(
+        policy: Policy,
+        accounts: &ExecuteAccounts,
+        program_id: &Pubkey,
+    ) -> Vec<Pubkey> {
+        let mint = &accounts.mint_key;
+        match policy {
+            Policy::Allowlist => vec![find_allowlist_address(mint, program_id).0],
+            Policy::Blocklist => vec![find_blocklist_address(mint, program_id).0],
+            Policy::VelocityLimit => vec![
+                find_velocity_limit_address(mint, program_id).0,
+                find_velocity_tracker_address(mint, &accounts.source_owner, program_id).0,
+            ],
+            Policy::AmountLimits => vec![find_amount_limits_address(mint, program_id).0],
+            Policy::TradingSchedule => vec![find_trading_schedule_address(mint, program_id).0],
+            Policy::Vesting => {
+                vec![find_vesting_schedule_address(mint, &accounts.source_owner, program_id).0]
+            }
+            Policy::TransferStats => vec![find_transfer_stats_address(mint, program_id).0],
+            Policy::OwnerStats => vec![
+                find_owner_stats_address(mint, &accounts.source_owner, program_id).0,
+                find_owner_stats_address(mint, &accounts.destination_owner, program_id).0,
+            ],
+            Policy::Royalty => vec![
+                find_royalty_config_address(mint, program_id).0,
+                sysvar::instructions::id(),
+            ],
+            Policy::Memo => vec![
+                find_memo_config_address(mint, program_id).0,
+                sysvar::instructions::id(),
+            ],
+            Policy::Pause => vec![find_pause_address(mint, program_id).0],
+            Policy::Kyc => vec![
+                find_kyc_config_address(mint, program_id).0,
+                find_attestation_address(mint, &accounts.source_owner, program_id).0,
+                find_attestation_address(mint, &accounts.destination_owner, program_id).0,
+            ],
+            Policy::HolderCount => vec![find_holder_count_address(mint, program_id).0],
+        }
+    }
+
+    #[test]
+    fn execute_succeeds_while_transferring() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+
+        assert_eq!(accounts.execute(&program_id), Ok(()));
+    }
+
+    #[test]
+    fn execute_rejects_source_outside_of_transfer() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        accounts.source = TestAccount::new(
+            &Pubkey::new_unique(),
+            &spl_token_2022::id(),
+            &token_account_data(accounts.mint.info().key, &Pubkey::new_unique(), false),
+        );
+
+        assert_eq!(
+            accounts.execute(&program_id),
+            Err(TransferHookError::ProgramCalledOutsideOfTransfer.into())
+        );
+    }
+
+    #[test]
+    fn execute_rejects_destination_outside_of_transfer() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        accounts.destination = TestAccount::new(
+            &Pubkey::new_unique(),
+            &spl_token_2022::id(),
+            &token_account_data(accounts.mint.info().key, &Pubkey::new_unique(), false),
+        );
+
+        assert_eq!(
+            accounts.execute(&program_id),
+            Err(TransferHookError::ProgramCalledOutsideOfTransfer.into())
+        );
+    }
+
+    #[test]
+    fn execute_rejects_token_account_without_hook_extension() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint: *accounts.mint.info().key,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        accounts.source = TestAccount::new(&Pubkey::new_unique(), &spl_token_2022::id(), &data);
+
+        assert_eq!(
+            accounts.execute(&program_id),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn execute_rejects_wrong_validation_address() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        let data = accounts.validation.info().data.borrow().to_vec();
+        accounts.validation = TestAccount::new(&Pubkey::new_unique(), &program_id, &data);
+
+        assert_eq!(
+            accounts.execute(&program_id),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn execute_rejects_wrong_extra_account() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        accounts.extras = vec![TestAccount::new(
+            &Pubkey::new_unique(),
+            &system_program::id(),
+            &[],
+        )];
+
+        assert_eq!(
+            accounts.execute(&program_id),
+            Err(AccountResolutionError::IncorrectAccount.into())
+        );
+    }
+
+    #[test]
+    fn execute_rejects_missing_accounts() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = ExecuteAccounts::new(&program_id, &Pubkey::new_unique());
+        let infos = [
+            accounts.source.info(),
+            accounts.mint.info(),
+            accounts.destination.info(),
+            accounts.authority.info(),
+        ];
+
+        assert_eq!(
+            process_execute(&program_id, &infos, 10),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    /// The Instructions sysvar of a transaction made of `instructions`, in
+    /// the middle of processing the one at `current_index`
+    fn instructions_sysvar(instructions: &[Instruction], current_index: u16) -> TestAccount {
+        let borrowed = instructions
+            .iter()
+            .map(|instruction| BorrowedInstruction {
+                program_id: &instruction.program_id,
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &instruction.data,
+            })
+            .collect::<Vec<_>>();
+        let mut data = construct_instructions_data(&borrowed);
+        store_current_index(&mut data, current_index);
+
+        TestAccount::new(&sysvar::instructions::id(), &sysvar::id(), &data)
+    }
+
+    mod allowlist {
+        use super::*;
+
+        fn allowlist_data(owners: &[Pubkey]) -> Vec<u8> {
+            Allowlist {
+                authority: Pubkey::new_unique(),
+                owners: owners.iter().copied().collect(),
+            }
+            .pack()
+        }
+
+        #[test]
+        fn execute_allows_listed_destination_owner() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Allowlist);
+            let owners = [Pubkey::new_unique(), accounts.destination_owner];
+            accounts.set_policy_data(&program_id, 0, &allowlist_data(&owners));
+
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+        }
+
+        #[test]
+        fn execute_rejects_unlisted_destination_owner() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Allowlist);
+            let owners = [Pubkey::new_unique(), Pubkey::new_unique()];
+            accounts.set_policy_data(&program_id, 0, &allowlist_data(&owners));
+
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::DestinationNotAllowlisted.into())
+            );
+        }
+
+        #[test]
+        fn execute_rejects_uninitialized_allowlist() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Allowlist);
+
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(ProgramError::UninitializedAccount)
+            );
+        }
+    }
+
+    mod blocklist {
+        use super::*;
+
+        fn blocklist_data(owners: &[Pubkey]) -> Vec<u8> {
+            let mut blocklist = Blocklist::new(Pubkey::new_unique());
+            blocklist.owners.extend(owners.iter().copied());
+            blocklist.pack()
+        }
+
+        /// Accounts resolving a blocklist of two random owners and `listed`
+        fn blocklist_accounts(
+            program_id: &Pubkey,
+            listed: impl Fn(&ExecuteAccounts) -> Pubkey,
+        ) -> ExecuteAccounts {
+            let mut accounts = ExecuteAccounts::with_policy(program_id, Policy::Blocklist);
+            let owners = [
+                Pubkey::new_unique(),
+                listed(&accounts),
+                Pubkey::new_unique(),
+            ];
+            accounts.set_policy_data(program_id, 0, &blocklist_data(&owners));
+            accounts
+        }
+
+        #[test]
+        fn execute_allows_unlisted_owners() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = blocklist_accounts(&program_id, |_| Pubkey::new_unique());
+
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+        }
+
+        #[test]
+        fn execute_rejects_blocklisted_source_owner() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = blocklist_accounts(&program_id, |accounts| accounts.source_owner);
+
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::SourceBlocklisted.into())
+            );
+        }
+
+        #[test]
+        fn execute_rejects_blocklisted_destination_owner() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts =
+                blocklist_accounts(&program_id, |accounts| accounts.destination_owner);
+
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::DestinationBlocklisted.into())
+            );
+        }
+    }
+
+    mod velocity {
+        use super::*;
+
+        fn velocity_limit(window_seconds: i64, max_amount: u64) -> VelocityLimit {
+            VelocityLimit {
+                authority: Pubkey::new_unique(),
+                window_seconds,
+                max_amount,
+            }
+        }
+
+        #[test]
+        fn execute_caps_first_transfer_without_velocity_tracker() {
+            let program_id = Pubkey::new_unique();
+
+            // `ExecuteAccounts` transfers 10 and leaves the tracker uncreated
+            for (max_amount, expected) in [
+                (10, Ok(())),
+                (100, Ok(())),
+                (9, Err(HookPolicyError::VelocityLimitExceeded.into())),
+                (0, Err(HookPolicyError::VelocityLimitExceeded.into())),
+            ] {
+                let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::VelocityLimit);
+                accounts.set_policy_data(&program_id, 0, &velocity_limit(60, max_amount).pack());
+
+                assert_eq!(accounts.execute(&program_id), expected, "{max_amount}");
+            }
+        }
+
+        #[test]
+        fn execute_rejects_malformed_velocity_tracker() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::VelocityLimit);
+            accounts.set_policy_data(&program_id, 0, &velocity_limit(60, 100).pack());
+            accounts.set_policy_data(&program_id, 1, &[]);
+
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    mod amount_limits {
+        use super::*;
+
+        fn amount_limits_accounts(
+            program_id: &Pubkey,
+            min_amount: u64,
+            max_amount: u64,
+        ) -> ExecuteAccounts {
+            let mut accounts = ExecuteAccounts::with_policy(program_id, Policy::AmountLimits);
+            let limits = AmountLimits {
+                authority: Pubkey::new_unique(),
+                min_amount,
+                max_amount,
+            };
+            accounts.set_policy_data(program_id, 0, &limits.pack());
+            accounts
+        }
+
+        #[test]
+        fn execute_allows_amount_within_limits() {
+            let program_id = Pubkey::new_unique();
+
+            // `ExecuteAccounts` transfers 10
+            for (min_amount, max_amount) in [(10, 10), (0, 10), (10, u64::MAX)] {
+                let mut accounts = amount_limits_accounts(&program_id, min_amount, max_amount);
+                assert_eq!(accounts.execute(&program_id), Ok(()));
+            }
+        }
+
+        #[test]
+        fn execute_rejects_amount_outside_limits() {
+            let program_id = Pubkey::new_unique();
+
+            let mut accounts = amount_limits_accounts(&program_id, 11, 100);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::AmountBelowMinimum.into())
+            );
+
+            let mut accounts = amount_limits_accounts(&program_id, 1, 9);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::AmountAboveMaximum.into())
+            );
+        }
+    }
+
+    mod royalty {
+        use super::*;
+
+        /// A sale paying the source owner `price` and the creator vault
+        /// `royalty` lamports
+        #[derive(Clone, Copy)]
+        enum Step {
+            Pay { price: u64, royalty: u64 },
+            Transfer,
+        }
+
+        /// Accounts of the transfer at `current` among `steps`, for a mint owing
+        /// 5% of the sale price and at least 10 lamports
+        fn royalty_accounts(
+            program_id: &Pubkey,
+            steps: &[Step],
+            current: usize,
+        ) -> ExecuteAccounts {
+            let mut accounts = ExecuteAccounts::with_policy(program_id, Policy::Royalty);
+            let config = RoyaltyConfig {
+                authority: Pubkey::new_unique(),
+                creator_vault: Pubkey::new_unique(),
+                basis_points: 500,
+                min_lamports: 10,
+            };
+            accounts.set_policy_data(program_id, 0, &config.pack());
+
+            let buyer = Pubkey::new_unique();
+            let mut instructions = Vec::new();
+            let mut current_index = None;
+            for (index, step) in steps.iter().enumerate() {
+                match *step {
+                    Step::Pay { price, royalty } => instructions.extend([
+                        system_instruction::transfer(&buyer, &accounts.source_owner, price),
+                        system_instruction::transfer(&buyer, &config.creator_vault, royalty),
+                    ]),
+                    Step::Transfer => {
+                        if index == current {
+                            current_index = Some(instructions.len() as u16);
+                        }
+                        instructions.push(Instruction::new_with_bytes(
+                            spl_token_2022::id(),
+                            &[],
+                            vec![],
+                        ));
+                    }
+                }
+            }
+            accounts.extras[1] = instructions_sysvar(&instructions, current_index.unwrap());
+            accounts
+        }
+
+        #[test]
+        fn execute_requires_royalty_payment() {
+            let program_id = Pubkey::new_unique();
+            let pay = |price, royalty| Step::Pay { price, royalty };
+
+            let mut accounts = royalty_accounts(&program_id, &[pay(1_000, 50), Step::Transfer], 1);
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+
+            let mut accounts = royalty_accounts(&program_id, &[pay(1_000, 49), Step::Transfer], 1);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::RoyaltyNotPaid.into())
+            );
+
+            // Payments after the transfer are not counted
+            let mut accounts = royalty_accounts(&program_id, &[Step::Transfer, pay(0, 50)], 0);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::RoyaltyNotPaid.into())
+            );
+        }
+
+        #[test]
+        fn execute_counts_each_royalty_payment_once() {
+            let program_id = Pubkey::new_unique();
+            let pay = Step::Pay {
+                price: 1_000,
+                royalty: 50,
+            };
+
+            // A second transfer cannot reuse the payment of the first one
+            let steps = [pay, Step::Transfer, Step::Transfer];
+            let mut accounts = royalty_accounts(&program_id, &steps, 1);
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+            let mut accounts = royalty_accounts(&program_id, &steps, 2);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::RoyaltyNotPaid.into())
+            );
+
+            // Nor can it take the royalty of its own payment from the other
+            let steps = [
+                Step::Pay {
+                    price: 0,
+                    royalty: 100,
+                },
+                Step::Transfer,
+                Step::Pay {
+                    price: 2_000,
+                    royalty: 0,
+                },
+                Step::Transfer,
+            ];
+            let mut accounts = royalty_accounts(&program_id, &steps, 3);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::RoyaltyNotPaid.into())
+            );
+
+            let steps = [pay, Step::Transfer, pay, Step::Transfer];
+            for current in [1, 3] {
+                let mut accounts = royalty_accounts(&program_id, &steps, current);
+                assert_eq!(accounts.execute(&program_id), Ok(()), "{current}");
+            }
+        }
+    }
+
+    mod memo {
+        use super::*;
+
+        /// Accounts of a transfer that is the last of `instructions`, for a mint
+        /// requiring a memo of at most 8 bytes
+        fn memo_accounts(program_id: &Pubkey, instructions: &[Instruction]) -> ExecuteAccounts {
+            let mut accounts = ExecuteAccounts::with_policy(program_id, Policy::Memo);
+            let config = MemoConfig {
+                max_length: 8,
+                ..MemoConfig::default()
+            };
+            accounts.set_policy_data(program_id, 0, &config.pack());
+
+            let mut instructions = instructions.to_vec();
+            instructions.push(Instruction::new_with_bytes(
+                spl_token_2022::id(),
+                &[],
+                vec![],
+            ));
+            let current_index = instructions.len() as u16 - 1;
+            accounts.extras[1] = instructions_sysvar(&instructions, current_index);
+            accounts
+        }
+
+        #[test]
+        fn execute_requires_preceding_memo() {
+            let program_id = Pubkey::new_unique();
+            let memo = |text: &[u8]| spl_memo::build_memo(text, &[]);
+
+            let mut accounts = memo_accounts(&program_id, &[]);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::MemoRequired.into())
+            );
+
+            let mut accounts = memo_accounts(&program_id, &[memo(b"paid")]);
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+
+            // The memo closest to the transfer is the one checked
+            let mut accounts = memo_accounts(&program_id, &[memo(b"paid"), memo(b"too long!")]);
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::MemoTooLong.into())
+            );
+        }
+    }
+
+    mod pause {
+        use super::*;
+
+        #[test]
+        fn execute_rejects_paused_transfers_unless_exempt() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Pause);
+            let source = *accounts.source.info().key;
+            let mut pause = PauseState {
+                guardian: Pubkey::new_unique(),
+                paused: false,
+                exempt_accounts: SortedPubkeys::default(),
+            };
+            accounts.set_policy_data(&program_id, 0, &pause.pack());
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+
+            pause.paused = true;
+            accounts.set_policy_data(&program_id, 0, &pause.pack());
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::TransfersPaused.into())
+            );
+
+            pause.exempt_accounts.insert(source);
+            accounts.set_policy_data(&program_id, 0, &pause.pack());
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+        }
+    }
+
+    mod kyc {
+        use super::*;
+
+        #[test]
+        fn execute_requires_attestations_of_both_owners() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Kyc);
+            let config = KycConfig {
+                authority: Pubkey::new_unique(),
+                attester: Pubkey::new_unique(),
+                tier_limits: vec![],
+            };
+            accounts.set_policy_data(&program_id, 0, &config.pack());
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::SourceNotAttested.into())
+            );
+
+            let attestation = Attestation {
+                attester: config.attester,
+                tier: 0,
+                expiry_timestamp: i64::MAX,
+            };
+            accounts.set_policy_data(&program_id, 1, &attestation.pack());
+            assert_eq!(
+                accounts.execute(&program_id),
+                Err(HookPolicyError::DestinationNotAttested.into())
+            );
+        }
+    }
+
+    mod schedule {
+        use super::*;
+
+        #[test]
+        fn execute_skips_schedule_for_exempt_source() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::TradingSchedule);
+            let mut schedule = TradingSchedule {
+                unlock_timestamp: i64::MAX,
+                ..TradingSchedule::default()
+            };
+            schedule.exempt_accounts.insert(*accounts.source.info().key);
+            accounts.set_policy_data(&program_id, 0, &schedule.pack());
+
+            // Checking the schedule would need the clock, which is unavailable
+            // off-chain
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+        }
+    }
+
+    mod vesting {
+        use super::*;
+
+        #[test]
+        fn execute_allows_owner_without_vesting_schedule() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::Vesting);
+
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+        }
+    }
+
+    mod stats {
+        use super::*;
+
+        #[test]
+        fn execute_counts_transfer_for_owners_with_stats() {
+            let program_id = Pubkey::new_unique();
+            let mut accounts = ExecuteAccounts::with_policy(&program_id, Policy::OwnerStats);
+            // Only the source owner has stats
+            accounts.set_policy_data(&program_id, 0, &OwnerStats::default().pack());
+
+            assert_eq!(accounts.execute(&program_id), Ok(()));
+            let stats = OwnerStats::unpack(&accounts.extras[0].info().data.borrow()).unwrap();
+            assert_eq!(stats.sent_count, 1);
+            assert_eq!(stats.sent_amount, 10);
+            assert_eq!(stats.received_count, 0);
+        }
+    }
+
+    /// A mint extra account metas can be initialized for
+    fn served_mint() -> Pubkey {
+        #[cfg(feature = "forbid-additional-mints")]
+        return crate::mint::id();
+        #[cfg(not(feature = "forbid-additional-mints"))]
+        Pubkey::new_unique()
+    }
+
+    /// Accounts of an `InitializeExtraAccountMetaList` or
+    /// `UpdateExtraAccountMetaList` instruction
+    struct MetaListAccounts {
+        validation: TestAccount,
+        mint: TestAccount,
+        authority: TestAccount,
+        system_program: TestAccount,
+        hook_config: Option<TestAccount>,
+    }
+
+    impl MetaListAccounts {
+        /// Accounts with a signing mint authority, a hook config that was
+        /// never created and a validation account holding `validation_data`,
+        /// owned by `validation_owner`
+        fn new(program_id: &Pubkey, validation_owner: &Pubkey, validation_data: &[u8]) -> Self {
+            let mint_key = served_mint();
+            let authority_key = Pubkey::new_unique();
+            let (hook_config_key, _) = find_hook_config_address(&mint_key, program_id);
+
+            Self {
+                validation: TestAccount::new(
+                    &get_extra_account_metas_address(&mint_key, program_id),
+                    validation_owner,
+                    validation_data,
+                ),
+                mint: TestAccount::new(
+                    &mint_key,
+                    &spl_token_2022::id(),
+                    &mint_data(Some(authority_key)),
+                ),
+                authority: TestAccount::new(&authority_key, &system_program::id(), &[]).signer(),
+                system_program: TestAccount::new(&system_program::id(), &Pubkey::default(), &[]),
+                hook_config: Some(TestAccount::new(
+                    &hook_config_key,
+                    &system_program::id(),
+                    &[],
+                )),
+            }
+        }
+
+        /// Accounts for initializing a list of `length` entries, with the
+        /// validation account already allocated since the system program is
+        /// not available to allocate it
+        fn for_initialize(program_id: &Pubkey, length: usize) -> Self {
+            let size = ExtraAccountMetaList::size_of(length).unwrap();
+            Self::new(program_id, &system_program::id(), &vec![0; size])
+        }
+
+        /// Accounts for updating an initialized list of `keys`
+        fn for_update(program_id: &Pubkey, keys: &[Pubkey]) -> Self {
+            let data = extra_account_metas_data(&extra_account_metas(keys));
+            Self::new(program_id, program_id, &data)
+        }
+
+        fn mint_key(&mut self) -> Pubkey {
+            *self.mint.info().key
+        }
+
+        fn set_mint_authority(&mut self, mint_authority: Option<Pubkey>) {
+            self.mint = TestAccount::new(
+                &self.mint_key(),
+                &spl_token_2022::id(),
+                &mint_data(mint_authority),
+            );
+        }
+
+        /// Create the hook config, naming `admin`
+        fn set_hook_admin(&mut self, program_id: &Pubkey, admin: Pubkey) {
+            let (address, _) = find_hook_config_address(&self.mint_key(), program_id);
+            let config = HookConfig {
+                admin,
+                pending_admin: Pubkey::default(),
+            };
+            self.hook_config = Some(TestAccount::new(&address, program_id, &config.pack()));
+        }
+
+        fn initialize(&mut self, program_id: &Pubkey, keys: &[Pubkey]) -> ProgramResult {
+            let mut accounts = vec![
+                self.validation.info(),
+                self.mint.info(),
+                self.authority.info(),
+                self.system_program.info(),
+            ];
+            accounts.extend(self.hook_config.as_mut().map(TestAccount::info));
+
+            process_initialize_extra_account_meta_list(
+                program_id,
+                &accounts,
+                &extra_account_metas(keys),
+            )
+        }
+
+        fn close(&mut self, program_id: &Pubkey, destination: &mut TestAccount) -> ProgramResult {
+            let mut accounts = vec![
+                self.validation.info(),
+                self.mint.info(),
+                self.authority.info(),
+                destination.info(),
+            ];
+            accounts.extend(self.hook_config.as_mut().map(TestAccount::info));
+
+            process_close_extra_account_meta_list(program_id, &accounts)?;
+
+            assert_eq!(accounts[0].lamports(), 0);
+            assert_eq!(accounts[0].data_len(), 0);
+
+            Ok(())
+        }
+
+        fn update(&mut self, program_id: &Pubkey, keys: &[Pubkey]) -> ProgramResult {
+            let mut accounts = vec![
+                self.validation.info(),
+                self.mint.info(),
+                self.authority.info(),
+            ];
+            accounts.extend(self.hook_config.as_mut().map(TestAccount::info));
+
+            process_update_extra_account_meta_list(
+                program_id,
+                &accounts,
+                &extra_account_metas(keys),
+            )?;
+
+            // Check the list through the updated `AccountInfo`, whose data
+            // length reflects any reallocation
+            let data = accounts[0].data.borrow();
+            assert_eq!(
+                data.len(),
+                ExtraAccountMetaList::size_of(keys.len()).unwrap()
+            );
+            assert_stored_keys(&data, keys);
+
+            Ok(())
+        }
+    }
+
+    fn assert_stored_keys(data: &[u8], keys: &[Pubkey]) {
+        let state = spl_type_length_value::state::TlvStateBorrowed::unpack(data).unwrap();
+        let list =
+            ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state).unwrap();
+        assert_eq!(list.data(), extra_account_metas(keys).as_slice());
+    }
+
+    #[test]
+    fn initialize_writes_extra_account_metas() {
+        let program_id = Pubkey::new_unique();
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, keys.len());
+
+        assert_eq!(accounts.initialize(&program_id, &keys), Ok(()));
+        assert_stored_keys(&accounts.validation.info().data.borrow(), &keys);
+    }
+
+    #[test]
+    fn initialize_rejects_mint_without_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        accounts.set_mint_authority(None);
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Err(TransferHookError::MintHasNoMintAuthority.into())
+        );
+    }
+
+    #[test]
+    fn initialize_rejects_unsigned_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        accounts.authority.is_signer = false;
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn initialize_rejects_wrong_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        accounts.set_mint_authority(Some(Pubkey::new_unique()));
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Err(TransferHookError::IncorrectMintAuthority.into())
+        );
+    }
+
+    #[test]
+    fn initialize_rejects_wrong_validation_address() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        accounts.validation = TestAccount::new(
+            &Pubkey::new_unique(),
+            &system_program::id(),
+            &vec![0; ExtraAccountMetaList::size_of(1).unwrap()],
+        );
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[cfg(feature = "forbid-additional-mints")]
+    #[test]
+    fn initialize_rejects_additional_mint() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        let authority = *accounts.authority.info().key;
+        let mint_key = Pubkey::new_unique();
+        accounts.mint = TestAccount::new(
+            &mint_key,
+            &spl_token_2022::id(),
+            &mint_data(Some(authority)),
+        );
+        accounts.validation = TestAccount::new(
+            &get_extra_account_metas_address(&mint_key, &program_id),
+            &system_program::id(),
+            &vec![0; ExtraAccountMetaList::size_of(1).unwrap()],
+        );
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[cfg(feature = "forbid-additional-mints")]
+    #[test]
+    fn mint_defaults_to_placeholder() {
+        if option_env!("TRANSFER_HOOK_MINT").is_none() {
+            assert_eq!(crate::mint::id().to_string(), crate::mint::DEFAULT);
+        }
+    }
+
+    #[test]
+    fn update_grows_list() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let keys = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+
+        assert_eq!(accounts.update(&program_id, &keys), Ok(()));
+    }
+
+    #[test]
+    fn update_shrinks_list() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(
+            &program_id,
+            &[Pubkey::new_unique(), Pubkey::new_unique()],
+        );
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn update_replaces_list_of_same_length() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn update_rejects_mint_without_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.set_mint_authority(None);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(TransferHookError::MintHasNoMintAuthority.into())
+        );
+    }
+
+    #[test]
+    fn update_rejects_unsigned_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.authority.is_signer = false;
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+    }
+
+    #[test]
+    fn update_rejects_wrong_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.set_mint_authority(Some(Pubkey::new_unique()));
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(TransferHookError::IncorrectMintAuthority.into())
+        );
+    }
+
+    #[test]
+    fn update_rejects_wrong_validation_address() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let data = accounts.validation.info().data.borrow().to_vec();
+        accounts.validation = TestAccount::new(&Pubkey::new_unique(), &program_id, &data);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn update_rejects_list_owned_by_another_program() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn update_rejects_list_too_small_to_be_initialized() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::new(&program_id, &program_id, &[]);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn hook_admin_manages_list_without_mint_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let admin = *accounts.authority.info().key;
+        accounts.set_mint_authority(None);
+        accounts.set_hook_admin(&program_id, admin);
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Ok(())
+        );
+
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        let admin = *accounts.authority.info().key;
+        accounts.set_mint_authority(None);
+        accounts.set_hook_admin(&program_id, admin);
+
+        assert_eq!(
+            accounts.initialize(&program_id, &[Pubkey::new_unique()]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn update_with_hook_config_rejects_mint_authority() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.set_hook_admin(&program_id, Pubkey::new_unique());
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(HookPolicyError::IncorrectAuthority.into())
+        );
+    }
+
+    #[test]
+    fn update_requires_hook_config() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.hook_config = None;
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+    }
+
+    #[test]
+    fn update_rejects_wrong_hook_config() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let admin = *accounts.authority.info().key;
+        let config = HookConfig {
+            admin,
+            pending_admin: Pubkey::default(),
+        };
+        accounts.hook_config = Some(TestAccount::new(
+            &Pubkey::new_unique(),
+            &program_id,
+            &config.pack(),
+        ));
+
+        assert_eq!(
+            accounts.update(&program_id, &[Pubkey::new_unique()]),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn hook_admin_is_handed_over_in_two_steps() {
+        let program_id = Pubkey::new_unique();
+        let admin_key = Pubkey::new_unique();
+        let new_admin_key = Pubkey::new_unique();
+        let config = HookConfig {
+            admin: admin_key,
+            pending_admin: Pubkey::default(),
+        };
+        let mut config = TestAccount::new(&Pubkey::new_unique(), &program_id, &config.pack());
+        config.is_writable = true;
+        let mut admin = TestAccount::new(&admin_key, &system_program::id(), &[]).signer();
+        let mut new_admin = TestAccount::new(&new_admin_key, &system_program::id(), &[]).signer();
+
+        // Nothing to accept before a proposal
+        assert_eq!(
+            process_accept_hook_admin(&program_id, &[config.info(), new_admin.info()]),
+            Err(HookPolicyError::IncorrectAuthority.into())
+        );
+        assert_eq!(
+            process_propose_hook_admin(
+                &program_id,
+                &[config.info(), new_admin.info()],
+                new_admin_key
+            ),
+            Err(HookPolicyError::IncorrectAuthority.into())
+        );
+        assert_eq!(
+            process_propose_hook_admin(&program_id, &[config.info(), admin.info()], new_admin_key),
+            Ok(())
+        );
+        assert_eq!(
+            process_accept_hook_admin(&program_id, &[config.info(), admin.info()]),
+            Err(HookPolicyError::IncorrectAuthority.into())
+        );
+        assert_eq!(
+            process_accept_hook_admin(&program_id, &[config.info(), new_admin.info()]),
+            Ok(())
+        );
+
+        assert_eq!(
+            HookConfig::unpack(&config.info().data.borrow()).unwrap(),
+            HookConfig {
+                admin: new_admin_key,
+                pending_admin: Pubkey::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn close_returns_rent_to_destination() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let mut destination = TestAccount::new(&Pubkey::new_unique(), &system_program::id(), &[]);
+        let rent = accounts.validation.lamports + destination.lamports;
+
+        assert_eq!(accounts.close(&program_id, &mut destination), Ok(()));
+        assert_eq!(destination.lamports, rent);
+    }
+
+    #[test]
+    fn close_is_authorized_like_update() {
+        let program_id = Pubkey::new_unique();
+        let mut destination = TestAccount::new(&Pubkey::new_unique(), &system_program::id(), &[]);
+
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        accounts.set_mint_authority(Some(Pubkey::new_unique()));
+        assert_eq!(
+            accounts.close(&program_id, &mut destination),
+            Err(TransferHookError::IncorrectMintAuthority.into())
+        );
+
+        let mut accounts = MetaListAccounts::for_update(&program_id, &[Pubkey::new_unique()]);
+        let admin = *accounts.authority.info().key;
+        accounts.set_mint_authority(None);
+        accounts.set_hook_admin(&program_id, admin);
+        assert_eq!(accounts.close(&program_id, &mut destination), Ok(()));
+    }
+
+    #[test]
+    fn close_rejects_list_owned_by_another_program() {
+        let program_id = Pubkey::new_unique();
+        let mut accounts = MetaListAccounts::for_initialize(&program_id, 1);
+        let mut destination = TestAccount::new(&Pubkey::new_unique(), &system_program::id(), &[]);
+
+        assert_eq!(
+            accounts.close(&program_id, &mut destination),
+            Err(ProgramError::UninitializedAccount)
+        );
+    }
+
+    #[test]
+    fn process_rejects_malformed_policy_instruction() {
+        let mut input = AddToBlocklist::SPL_DISCRIMINATOR_SLICE.to_vec();
+        // A list of one owner, cut short
+        input.extend([1, 0, 0, 0, 7]);
+
+        assert!(matches!(
+            process(&Pubkey::new_unique(), &[], &input),
+            Err(ProgramError::BorshIoError(_))
+        ));
+    }
+
+    #[test]
+    fn process_rejects_unknown_instruction() {
+        assert_eq!(
+            process(&Pubkey::new_unique(), &[], &[0xff; 8]),
+            Err(ProgramError::InvalidInstructionData)
+        );
     }
 }
@@ -0,0 +1,1427 @@
+//! Policy accounts owned by the program
+//!
+//! Every policy account is a PDA of the mint it applies to and starts with
+//! the 8-byte discriminator of its type, followed by its borsh-serialized
+//! fields. A policy is enforced on every transfer of a mint whose extra
+//! account metas include its accounts, see [`extra_account_metas`].
+
+use {
+    crate::error::HookPolicyError,
+    borsh::{BorshDeserialize, BorshSerialize},
+    solana_program::{program_error::ProgramError, pubkey::Pubkey, sysvar},
+    spl_discriminator::{ArrayDiscriminator, SplDiscriminate},
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed},
+};
+
+/// Serialization shared by the policy accounts
+pub trait PolicyAccount: SplDiscriminate + BorshSerialize + BorshDeserialize {
+    /// Deserialize the account from `data`, checking its discriminator
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        let fields = data
+            .strip_prefix(Self::SPL_DISCRIMINATOR_SLICE)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Self::try_from_slice(fields).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Serialize the account with its discriminator
+    fn pack(&self) -> Vec<u8> {
+        let mut data = Self::SPL_DISCRIMINATOR_SLICE.to_vec();
+        // Writing to a `Vec` cannot fail
+        self.serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Serialize the account into `data`, which must be exactly as long as
+    /// the packed account
+    fn pack_into(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        let packed = self.pack();
+        if packed.len() != data.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        data.copy_from_slice(&packed);
+        Ok(())
+    }
+
+    /// Size of the packed account
+    fn packed_len(&self) -> usize {
+        ArrayDiscriminator::LENGTH + borsh::object_length(self).unwrap()
+    }
+}
+
+/// Pubkeys kept sorted so lookups are a binary search.
+///
+/// Serialized like a `Vec<Pubkey>`, so the keys can be searched in place with
+/// [`SortedPubkeys::contains_packed`] without deserializing a whole list.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct SortedPubkeys(Vec<Pubkey>);
+
+impl SortedPubkeys {
+    /// The keys in ascending order
+    pub fn keys(&self) -> &[Pubkey] {
+        &self.0
+    }
+
+    /// Whether `key` is in the list
+    pub fn contains(&self, key: &Pubkey) -> bool {
+        self.0.binary_search(key).is_ok()
+    }
+
+    /// Add `key`, returning `false` if it was already present
+    pub fn insert(&mut self, key: Pubkey) -> bool {
+        match self.0.binary_search(&key) {
+            Ok(_) => false,
+            Err(index) => {
+                self.0.insert(index, key);
+                true
+            }
+        }
+    }
+
+    /// Add every key of `keys`, sorting once rather than inserting them one
+    /// by one so bulk loads stay cheap
+    pub fn extend(&mut self, keys: impl IntoIterator<Item = Pubkey>) {
+        self.0.extend(keys);
+        self.0.sort_unstable();
+        self.0.dedup();
+    }
+
+    /// Remove `key`, returning `false` if it was not present
+    pub fn remove(&mut self, key: &Pubkey) -> bool {
+        match self.0.binary_search(key) {
+            Ok(index) => {
+                self.0.remove(index);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Whether `key` is in the serialized list at the start of `data`
+    pub fn contains_packed(data: &[u8], key: &Pubkey) -> Result<bool, ProgramError> {
+        let (len, keys) = data
+            .split_first_chunk::<4>()
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let keys = keys
+            .get(..u32::from_le_bytes(*len) as usize * 32)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let (mut low, mut high) = (0, keys.len() / 32);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match keys[middle * 32..(middle + 1) * 32].cmp(key.as_ref()) {
+                std::cmp::Ordering::Equal => return Ok(true),
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl FromIterator<Pubkey> for SortedPubkeys {
+    fn from_iter<T: IntoIterator<Item = Pubkey>>(iter: T) -> Self {
+        let mut keys = iter.into_iter().collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+        Self(keys)
+    }
+}
+
+/// Policy accounts made of an authority followed by a list of owners
+pub trait OwnerList: PolicyAccount {
+    /// Seed of the account address, followed by the mint
+    const SEED: &'static [u8];
+
+    /// An empty list managed by `authority`
+    fn new(authority: Pubkey) -> Self;
+
+    /// Authority allowed to add and remove owners
+    fn authority(&self) -> &Pubkey;
+
+    /// The listed owners
+    fn owners(&self) -> &SortedPubkeys;
+
+    /// The listed owners, for changing them
+    fn owners_mut(&mut self) -> &mut SortedPubkeys;
+
+    /// Whether `owner` is on the list stored in `data`, without deserializing
+    /// it
+    fn contains_packed(data: &[u8], owner: &Pubkey) -> Result<bool, ProgramError> {
+        if !data.starts_with(Self::SPL_DISCRIMINATOR_SLICE) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // The owners follow the discriminator and the authority
+        let owners = data
+            .get(ArrayDiscriminator::LENGTH + 32..)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        SortedPubkeys::contains_packed(owners, owner)
+    }
+
+    /// Address and bump seed of the list of `mint`
+    fn find_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Self::SEED, mint.as_ref()], program_id)
+    }
+}
+
+/// Seed of the allowlist address, followed by the mint
+pub const ALLOWLIST_SEED: &[u8] = b"allowlist";
+
+/// Owners allowed to receive tokens of a mint
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:allowlist")]
+pub struct Allowlist {
+    /// Authority allowed to add and remove owners
+    pub authority: Pubkey,
+    /// Owners allowed to receive tokens
+    pub owners: SortedPubkeys,
+}
+
+impl PolicyAccount for Allowlist {}
+
+impl OwnerList for Allowlist {
+    const SEED: &'static [u8] = ALLOWLIST_SEED;
+
+    fn new(authority: Pubkey) -> Self {
+        Self {
+            authority,
+            owners: SortedPubkeys::default(),
+        }
+    }
+
+    fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+
+    fn owners(&self) -> &SortedPubkeys {
+        &self.owners
+    }
+
+    fn owners_mut(&mut self) -> &mut SortedPubkeys {
+        &mut self.owners
+    }
+}
+
+/// Address and bump seed of the allowlist of `mint`
+pub fn find_allowlist_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Allowlist::find_address(mint, program_id)
+}
+
+/// Seed of the blocklist address, followed by the mint
+pub const BLOCKLIST_SEED: &[u8] = b"blocklist";
+
+/// Owners barred from sending or receiving tokens of a mint
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:blocklist")]
+pub struct Blocklist {
+    /// Authority allowed to add and remove owners
+    pub authority: Pubkey,
+    /// Owners barred from transfers
+    pub owners: SortedPubkeys,
+}
+
+impl PolicyAccount for Blocklist {}
+
+impl OwnerList for Blocklist {
+    const SEED: &'static [u8] = BLOCKLIST_SEED;
+
+    fn new(authority: Pubkey) -> Self {
+        Self {
+            authority,
+            owners: SortedPubkeys::default(),
+        }
+    }
+
+    fn authority(&self) -> &Pubkey {
+        &self.authority
+    }
+
+    fn owners(&self) -> &SortedPubkeys {
+        &self.owners
+    }
+
+    fn owners_mut(&mut self) -> &mut SortedPubkeys {
+        &mut self.owners
+    }
+}
+
+/// Address and bump seed of the blocklist of `mint`
+pub fn find_blocklist_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Blocklist::find_address(mint, program_id)
+}
+
+/// Seed of the velocity limit address, followed by the mint
+pub const VELOCITY_LIMIT_SEED: &[u8] = b"velocity_limit";
+
+/// Cap on the amount each owner may send within any window-long span
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:velocity_limit")]
+pub struct VelocityLimit {
+    /// Authority allowed to change the limit
+    pub authority: Pubkey,
+    /// Length of a window in seconds
+    pub window_seconds: i64,
+    /// Most an owner may send within a window, in base units
+    pub max_amount: u64,
+}
+
+impl PolicyAccount for VelocityLimit {}
+
+/// Address and bump seed of the velocity limit of `mint`
+pub fn find_velocity_limit_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VELOCITY_LIMIT_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the velocity tracker address, followed by the mint and the owner
+pub const VELOCITY_TRACKER_SEED: &[u8] = b"velocity";
+
+/// Number of buckets a [`VelocityTracker`] splits its span into
+pub const VELOCITY_BUCKETS: usize = 8;
+
+/// Amounts an owner sent in the rolling window of a [`VelocityLimit`],
+/// grouped into [`VELOCITY_BUCKETS`] consecutive buckets
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:velocity_tracker")]
+pub struct VelocityTracker {
+    /// Unix timestamp the newest bucket opened at
+    pub bucket_start: i64,
+    /// Amount sent in each bucket, oldest first
+    pub buckets: [u64; VELOCITY_BUCKETS],
+}
+
+impl PolicyAccount for VelocityTracker {}
+
+impl VelocityTracker {
+    /// Length in seconds of each bucket under `limit`, chosen so that all
+    /// but the newest bucket together cover at least a window
+    pub fn bucket_seconds(limit: &VelocityLimit) -> i64 {
+        let older_buckets = VELOCITY_BUCKETS as i64 - 1;
+        (limit.window_seconds.saturating_add(older_buckets - 1) / older_buckets).max(1)
+    }
+
+    /// Amount counted against the cap, sent since the oldest bucket opened
+    pub fn transferred(&self) -> u64 {
+        self.buckets
+            .iter()
+            .fold(0, |total, amount| total.saturating_add(*amount))
+    }
+
+    /// Record a transfer of `amount` at `now`, failing if it would take the
+    /// owner over `limit`.
+    ///
+    /// The buckets before the newest one cover at least a window, so an owner
+    /// never sends more than the cap within any window-long span. In exchange
+    /// an amount may keep counting for up to two buckets past its window.
+    pub fn record(
+        &mut self,
+        limit: &VelocityLimit,
+        now: i64,
+        amount: u64,
+    ) -> Result<(), HookPolicyError> {
+        let bucket_seconds = Self::bucket_seconds(limit);
+        let elapsed = now.saturating_sub(self.bucket_start) / bucket_seconds;
+        if elapsed >= VELOCITY_BUCKETS as i64 {
+            self.buckets = Default::default();
+            self.bucket_start = now;
+        } else if elapsed > 0 {
+            let elapsed = elapsed as usize;
+            self.buckets.rotate_left(elapsed);
+            self.buckets[VELOCITY_BUCKETS - elapsed..].fill(0);
+            self.bucket_start += elapsed as i64 * bucket_seconds;
+        }
+
+        self.transferred()
+            .checked_add(amount)
+            .filter(|transferred| *transferred <= limit.max_amount)
+            .ok_or(HookPolicyError::VelocityLimitExceeded)?;
+        self.buckets[VELOCITY_BUCKETS - 1] += amount;
+
+        Ok(())
+    }
+}
+
+/// Address and bump seed of the velocity tracker of `owner` for `mint`
+pub fn find_velocity_tracker_address(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VELOCITY_TRACKER_SEED, mint.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed of the amount limits address, followed by the mint
+pub const AMOUNT_LIMITS_SEED: &[u8] = b"amount_limits";
+
+/// Bounds on the amount of a single transfer
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:amount_limits")]
+pub struct AmountLimits {
+    /// Authority allowed to change the limits
+    pub authority: Pubkey,
+    /// Smallest amount a transfer may move, in base units
+    pub min_amount: u64,
+    /// Largest amount a transfer may move, in base units
+    pub max_amount: u64,
+}
+
+impl PolicyAccount for AmountLimits {}
+
+impl AmountLimits {
+    /// Check that a transfer of `amount` is within the limits
+    pub fn check(&self, amount: u64) -> Result<(), HookPolicyError> {
+        if amount < self.min_amount {
+            Err(HookPolicyError::AmountBelowMinimum)
+        } else if amount > self.max_amount {
+            Err(HookPolicyError::AmountAboveMaximum)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Address and bump seed of the amount limits of `mint`
+pub fn find_amount_limits_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AMOUNT_LIMITS_SEED, mint.as_ref()], program_id)
+}
+
+/// Seconds in a day, the period of a [`DailyWindow`]
+pub const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// A span of every day in seconds since midnight UTC, wrapping past midnight
+/// if it ends before it starts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct DailyWindow {
+    /// First second of the window
+    pub start: u32,
+    /// Second after the last one of the window
+    pub end: u32,
+}
+
+impl DailyWindow {
+    /// Whether both ends fall within a day and the window is not empty
+    pub fn is_valid(&self) -> bool {
+        self.start < SECONDS_PER_DAY && self.end < SECONDS_PER_DAY && self.start != self.end
+    }
+
+    /// Whether the window includes `second_of_day`
+    pub fn contains(&self, second_of_day: u32) -> bool {
+        if self.start < self.end {
+            (self.start..self.end).contains(&second_of_day)
+        } else {
+            second_of_day >= self.start || second_of_day < self.end
+        }
+    }
+}
+
+/// Seed of the trading schedule address, followed by the mint
+pub const TRADING_SCHEDULE_SEED: &[u8] = b"trading_schedule";
+
+/// When tokens of a mint may move
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:trading_schedule")]
+pub struct TradingSchedule {
+    /// Authority allowed to change the schedule and its exemptions
+    pub authority: Pubkey,
+    /// Unix timestamp before which transfers are locked
+    pub unlock_timestamp: i64,
+    /// Spans of each day transfers are allowed in, or every day if empty
+    pub daily_windows: Vec<DailyWindow>,
+    /// Source token accounts the schedule does not apply to
+    pub exempt_accounts: SortedPubkeys,
+}
+
+impl PolicyAccount for TradingSchedule {}
+
+impl TradingSchedule {
+    /// Check that the schedule allows transfers at `now`
+    pub fn check(&self, now: i64) -> Result<(), HookPolicyError> {
+        if now < self.unlock_timestamp {
+            return Err(HookPolicyError::TransfersLocked);
+        }
+
+        let second_of_day = now.rem_euclid(SECONDS_PER_DAY.into()) as u32;
+        if !self.daily_windows.is_empty()
+            && !self
+                .daily_windows
+                .iter()
+                .any(|window| window.contains(second_of_day))
+        {
+            return Err(HookPolicyError::OutsideTradingWindow);
+        }
+
+        Ok(())
+    }
+}
+
+/// Address and bump seed of the trading schedule of `mint`
+pub fn find_trading_schedule_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TRADING_SCHEDULE_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the vesting schedule address, followed by the mint and the
+/// beneficiary owner
+pub const VESTING_SCHEDULE_SEED: &[u8] = b"vesting";
+
+/// Tokens of a mint granted to an owner that unlock over time.
+///
+/// Nothing unlocks before the cliff. From then on the grant unlocks linearly
+/// from the start to the end, so the cliff releases everything accrued since
+/// the start at once.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:vesting_schedule")]
+pub struct VestingSchedule {
+    /// Authority allowed to revoke the schedule
+    pub authority: Pubkey,
+    /// Amount granted, in base units
+    pub total_amount: u64,
+    /// Unix timestamp the grant starts accruing at
+    pub start_timestamp: i64,
+    /// Unix timestamp before which nothing unlocks
+    pub cliff_timestamp: i64,
+    /// Unix timestamp the whole grant is unlocked at
+    pub end_timestamp: i64,
+}
+
+impl PolicyAccount for VestingSchedule {}
+
+impl VestingSchedule {
+    /// Whether the cliff falls between the start and the end
+    pub fn is_valid(&self) -> bool {
+        self.start_timestamp <= self.cliff_timestamp && self.cliff_timestamp <= self.end_timestamp
+    }
+
+    /// Amount of the grant still locked at `now`
+    pub fn locked_amount(&self, now: i64) -> u64 {
+        if now < self.cliff_timestamp {
+            return self.total_amount;
+        }
+        if now >= self.end_timestamp {
+            return 0;
+        }
+
+        let elapsed = now.saturating_sub(self.start_timestamp) as u128;
+        let duration = self.end_timestamp.saturating_sub(self.start_timestamp) as u128;
+        let unlocked = u128::from(self.total_amount) * elapsed / duration;
+        self.total_amount - unlocked as u64
+    }
+
+    /// Check that `balance` left in an account of the beneficiary after a
+    /// transfer at `now` covers the amount still locked
+    pub fn check(&self, now: i64, balance: u64) -> Result<(), HookPolicyError> {
+        if balance < self.locked_amount(now) {
+            Err(HookPolicyError::VestingLocked)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Address and bump seed of the vesting schedule of `beneficiary` for `mint`
+pub fn find_vesting_schedule_address(
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VESTING_SCHEDULE_SEED, mint.as_ref(), beneficiary.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed of the transfer stats address, followed by the mint
+pub const TRANSFER_STATS_SEED: &[u8] = b"transfer_stats";
+
+/// Running totals of the transfers of a mint
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:transfer_stats")]
+pub struct TransferStats {
+    /// Number of transfers
+    pub transfer_count: u64,
+    /// Amount transferred, in base units
+    pub volume: u128,
+    /// Slot of the latest transfer
+    pub last_transfer_slot: u64,
+}
+
+impl PolicyAccount for TransferStats {}
+
+impl TransferStats {
+    /// Count a transfer of `amount` in `slot`
+    pub fn record(&mut self, slot: u64, amount: u64) {
+        self.transfer_count = self.transfer_count.saturating_add(1);
+        self.volume = self.volume.saturating_add(amount.into());
+        self.last_transfer_slot = slot;
+    }
+}
+
+/// Address and bump seed of the transfer stats of `mint`
+pub fn find_transfer_stats_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TRANSFER_STATS_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the owner stats address, followed by the mint and the owner
+pub const OWNER_STATS_SEED: &[u8] = b"owner_stats";
+
+/// Running totals of the transfers of a mint sent and received by an owner
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:owner_stats")]
+pub struct OwnerStats {
+    /// Number of transfers sent
+    pub sent_count: u64,
+    /// Amount sent, in base units
+    pub sent_amount: u128,
+    /// Number of transfers received
+    pub received_count: u64,
+    /// Amount received, in base units
+    pub received_amount: u128,
+}
+
+impl PolicyAccount for OwnerStats {}
+
+impl OwnerStats {
+    /// Count a transfer of `amount` sent by the owner
+    pub fn record_sent(&mut self, amount: u64) {
+        self.sent_count = self.sent_count.saturating_add(1);
+        self.sent_amount = self.sent_amount.saturating_add(amount.into());
+    }
+
+    /// Count a transfer of `amount` received by the owner
+    pub fn record_received(&mut self, amount: u64) {
+        self.received_count = self.received_count.saturating_add(1);
+        self.received_amount = self.received_amount.saturating_add(amount.into());
+    }
+}
+
+/// Address and bump seed of the stats of `owner` for `mint`
+pub fn find_owner_stats_address(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OWNER_STATS_SEED, mint.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed of the hook config address, followed by the mint
+pub const HOOK_CONFIG_SEED: &[u8] = b"hook_config";
+
+/// Who manages the extra account metas and creates the policies of a mint in
+/// place of its mint authority.
+///
+/// Unlike the mint authority, the admin outlives a fixed supply, and is handed
+/// over in two steps so it cannot be lost to a mistyped key.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:hook_config")]
+pub struct HookConfig {
+    /// Authority allowed to manage the extra account metas and create
+    /// policies
+    pub admin: Pubkey,
+    /// Admin proposed by the current one, or the default pubkey if none, so
+    /// the account keeps its size
+    pub pending_admin: Pubkey,
+}
+
+impl PolicyAccount for HookConfig {}
+
+/// Address and bump seed of the hook config of `mint`
+pub fn find_hook_config_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HOOK_CONFIG_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the royalty config address, followed by the mint
+pub const ROYALTY_SEED: &[u8] = b"royalty";
+
+/// Basis points in a whole
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Royalty every transaction transferring a mint must pay to its creator.
+///
+/// The hook cannot move lamports itself, so it checks that the system
+/// transfers right before the transfer instruction pay the creator vault the
+/// royalty. The sale price is taken as the lamports those transfers send to
+/// the source owner, which makes it self-reported: a sale settled any other
+/// way shows no price, so only `min_lamports` is enforced for certain.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:royalty_config")]
+pub struct RoyaltyConfig {
+    /// Authority allowed to change the royalty
+    pub authority: Pubkey,
+    /// Account the royalty is paid to
+    pub creator_vault: Pubkey,
+    /// Share of the sale price owed to the creator
+    pub basis_points: u16,
+    /// Lamports owed on every transfer, including those without a sale
+    pub min_lamports: u64,
+}
+
+impl PolicyAccount for RoyaltyConfig {}
+
+impl RoyaltyConfig {
+    /// Whether the basis points are at most a whole
+    pub fn is_valid(&self) -> bool {
+        self.basis_points <= MAX_BASIS_POINTS
+    }
+
+    /// Lamports owed on a sale at `price`, rounded up so splitting a sale
+    /// cannot avoid the royalty
+    pub fn owed(&self, price: u64) -> u64 {
+        let share = (u128::from(price) * u128::from(self.basis_points))
+            .div_ceil(u128::from(MAX_BASIS_POINTS));
+        // The share is at most `price` for valid basis points, so it fits
+        u64::try_from(share)
+            .unwrap_or(u64::MAX)
+            .max(self.min_lamports)
+    }
+
+    /// Check that `paid` lamports cover the royalty on a sale at `price`
+    pub fn check(&self, price: u64, paid: u64) -> Result<(), HookPolicyError> {
+        if paid >= self.owed(price) {
+            Ok(())
+        } else {
+            Err(HookPolicyError::RoyaltyNotPaid)
+        }
+    }
+}
+
+/// Address and bump seed of the royalty config of `mint`
+pub fn find_royalty_config_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ROYALTY_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the memo config address, followed by the mint
+pub const MEMO_CONFIG_SEED: &[u8] = b"memo_config";
+
+/// Memo every transfer of a mint must follow, like the `MemoTransfer`
+/// extension but for all token accounts of the mint
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:memo_config")]
+pub struct MemoConfig {
+    /// Authority allowed to change the config
+    pub authority: Pubkey,
+    /// Most bytes the memo may have, or 0 for no limit
+    pub max_length: u16,
+    /// Pattern the memo must match, or empty to accept any memo. `*` matches
+    /// any run of characters and `?` any single one.
+    pub pattern: String,
+}
+
+impl PolicyAccount for MemoConfig {}
+
+impl MemoConfig {
+    /// Check that `memo` is within the length limit and matches the pattern
+    pub fn check(&self, memo: &[u8]) -> Result<(), HookPolicyError> {
+        if self.max_length != 0 && memo.len() > usize::from(self.max_length) {
+            return Err(HookPolicyError::MemoTooLong);
+        }
+        if self.pattern.is_empty() {
+            return Ok(());
+        }
+
+        // The memo program only accepts UTF-8
+        let memo = std::str::from_utf8(memo).map_err(|_| HookPolicyError::MemoPatternMismatch)?;
+        if matches_pattern(&self.pattern, memo) {
+            Ok(())
+        } else {
+            Err(HookPolicyError::MemoPatternMismatch)
+        }
+    }
+}
+
+/// Whether `text` matches `pattern`, where `*` matches any run of characters
+/// and `?` any single one
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and of the text it was matched against, to
+    // backtrack to when the rest fails
+    let mut star = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Address and bump seed of the memo config of `mint`
+pub fn find_memo_config_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MEMO_CONFIG_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the pause state address, followed by the mint
+pub const PAUSE_SEED: &[u8] = b"pause";
+
+/// Emergency switch stopping every transfer of a mint at once, without
+/// freezing its token accounts one by one
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:pause_state")]
+pub struct PauseState {
+    /// Key allowed to pause and unpause transfers and change the exemptions
+    pub guardian: Pubkey,
+    /// Whether transfers are paused
+    pub paused: bool,
+    /// Source token accounts that may still send while paused, such as a
+    /// treasury
+    pub exempt_accounts: SortedPubkeys,
+}
+
+impl PolicyAccount for PauseState {}
+
+impl PauseState {
+    /// Check that `source` may send tokens
+    pub fn check(&self, source: &Pubkey) -> Result<(), HookPolicyError> {
+        if self.paused && !self.exempt_accounts.contains(source) {
+            Err(HookPolicyError::TransfersPaused)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Address and bump seed of the pause state of `mint`
+pub fn find_pause_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAUSE_SEED, mint.as_ref()], program_id)
+}
+
+/// Seed of the KYC config address, followed by the mint
+pub const KYC_CONFIG_SEED: &[u8] = b"kyc_config";
+
+/// Seed of an attestation address, followed by the mint and the owner
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+
+/// Who may attest to the owners of a mint and how much each tier may move
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:kyc_config")]
+pub struct KycConfig {
+    /// Authority allowed to change the config
+    pub authority: Pubkey,
+    /// Key issuing and revoking attestations. Attestations issued by any
+    /// other key are not accepted.
+    pub attester: Pubkey,
+    /// Largest amount a transfer may move when its owners have at least the
+    /// tier at each index, in base units. Tiers past the end have the limit
+    /// of the last one, and every amount is allowed if empty.
+    pub tier_limits: Vec<u64>,
+}
+
+impl PolicyAccount for KycConfig {}
+
+impl KycConfig {
+    /// Largest amount an owner of `tier` may send or receive, if limited
+    pub fn tier_limit(&self, tier: u8) -> Option<u64> {
+        self.tier_limits
+            .get(usize::from(tier))
+            .or(self.tier_limits.last())
+            .copied()
+    }
+
+    /// Check that the source and destination owners hold attestations valid
+    /// at `now` whose tiers allow a transfer of `amount`
+    pub fn check(
+        &self,
+        now: i64,
+        amount: u64,
+        source: &Attestation,
+        destination: &Attestation,
+    ) -> Result<(), HookPolicyError> {
+        if source.attester != self.attester {
+            return Err(HookPolicyError::SourceNotAttested);
+        }
+        if destination.attester != self.attester {
+            return Err(HookPolicyError::DestinationNotAttested);
+        }
+        if source.is_expired(now) || destination.is_expired(now) {
+            return Err(HookPolicyError::AttestationExpired);
+        }
+
+        let limit = [source.tier, destination.tier]
+            .into_iter()
+            .filter_map(|tier| self.tier_limit(tier))
+            .min();
+        match limit {
+            Some(limit) if amount > limit => Err(HookPolicyError::AmountAboveTierLimit),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An attester's word that an owner passed KYC, up to a tier
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:attestation")]
+pub struct Attestation {
+    /// Key that issued the attestation
+    pub attester: Pubkey,
+    /// Tier the owner was verified at, indexing the tier limits of the mint
+    pub tier: u8,
+    /// Unix timestamp the attestation expires at
+    pub expiry_timestamp: i64,
+}
+
+impl PolicyAccount for Attestation {}
+
+impl Attestation {
+    /// Whether the attestation has expired at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expiry_timestamp
+    }
+}
+
+/// Address and bump seed of the KYC config of `mint`
+pub fn find_kyc_config_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[KYC_CONFIG_SEED, mint.as_ref()], program_id)
+}
+
+/// Address and bump seed of the attestation of `owner` for `mint`
+pub fn find_attestation_address(
+    mint: &Pubkey,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ATTESTATION_SEED, mint.as_ref(), owner.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed of the holder count address, followed by the mint
+pub const HOLDER_COUNT_SEED: &[u8] = b"holder_count";
+
+/// Number of token accounts of a mint holding a balance, with a cap on it.
+///
+/// The hook only sees transfers, so minting to an empty account or burning
+/// an account's whole balance leaves the count off until the authority
+/// corrects it.
+#[derive(Clone, Debug, Default, PartialEq, BorshSerialize, BorshDeserialize, SplDiscriminate)]
+#[discriminator_hash_input("transfer_hooks_program:holder_count")]
+pub struct HolderCount {
+    /// Authority allowed to change the cap and correct the count
+    pub authority: Pubkey,
+    /// Token accounts with a non-zero balance
+    pub holder_count: u64,
+    /// Most token accounts that may hold a balance, or 0 for no cap
+    pub max_holders: u64,
+}
+
+impl PolicyAccount for HolderCount {}
+
+impl HolderCount {
+    /// Count a transfer of `amount`, `fee` of which was withheld, that left
+    /// the source and destination token accounts with `source_balance` and
+    /// `destination_balance`, rejecting it if it adds a holder past the cap.
+    /// A transfer to the same account must not be counted.
+    pub fn record(
+        &mut self,
+        amount: u64,
+        fee: u64,
+        source_balance: u64,
+        destination_balance: u64,
+    ) -> Result<(), HookPolicyError> {
+        if amount == 0 {
+            return Ok(());
+        }
+        // The destination was empty if what it received is all it holds now
+        let received = amount.saturating_sub(fee);
+        let gained = received > 0 && destination_balance == received;
+        let lost = source_balance == 0;
+
+        match (gained, lost) {
+            (true, false) => {
+                if self.max_holders != 0 && self.holder_count >= self.max_holders {
+                    return Err(HookPolicyError::HolderLimitExceeded);
+                }
+                self.holder_count = self.holder_count.saturating_add(1);
+            }
+            (false, true) => self.holder_count = self.holder_count.saturating_sub(1),
+            // Moving a whole balance to an empty account swaps one holder
+            // for another
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Address and bump seed of the holder count of `mint`
+pub fn find_holder_count_address(mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HOLDER_COUNT_SEED, mint.as_ref()], program_id)
+}
+
+/// Policy accounts that can be added to the extra account metas of a mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Only owners on the allowlist may receive tokens
+    Allowlist,
+    /// Owners on the blocklist may neither send nor receive tokens
+    Blocklist,
+    /// Owners may send at most a capped amount per window
+    VelocityLimit,
+    /// Every transfer must move an amount within bounds
+    AmountLimits,
+    /// Transfers are locked until a timestamp and limited to daily windows
+    TradingSchedule,
+    /// Owners with a vesting schedule must keep its locked amount
+    Vesting,
+    /// Every transfer is counted in the stats of the mint
+    TransferStats,
+    /// Transfers are counted in the stats of the sending and receiving
+    /// owners that have them
+    OwnerStats,
+    /// Every transaction transferring tokens pays a royalty to the creator
+    Royalty,
+    /// Every transfer follows a memo, which may be limited in length and
+    /// have to match a pattern
+    Memo,
+    /// A guardian may stop transfers from all but exempt accounts
+    Pause,
+    /// The sending and receiving owners must hold unexpired attestations,
+    /// whose tiers limit the amount
+    Kyc,
+    /// Token accounts holding a balance are counted and may not exceed a cap
+    HolderCount,
+}
+
+/// The extra account metas resolving the accounts of `policy` for the mint
+/// being transferred
+pub fn extra_account_metas(policy: Policy) -> Vec<ExtraAccountMeta> {
+    // Accounts 0, 1 and 2 of an `Execute` instruction are the source token
+    // account, the mint and the destination token account
+    let mint = Seed::AccountKey { index: 1 };
+    let source_owner = Seed::AccountData {
+        account_index: 0,
+        data_index: 32,
+        length: 32,
+    };
+    let destination_owner = Seed::AccountData {
+        account_index: 2,
+        data_index: 32,
+        length: 32,
+    };
+    let literal = |bytes: &[u8]| Seed::Literal {
+        bytes: bytes.to_vec(),
+    };
+    let instructions = ExtraAccountMeta::new_with_pubkey(&sysvar::instructions::id(), false, false)
+        .expect("a pubkey fits in an extra account meta");
+    let meta = |seeds: &[Seed], is_writable| {
+        ExtraAccountMeta::new_with_seeds(seeds, false, is_writable)
+            .expect("seeds fit in an extra account meta")
+    };
+
+    match policy {
+        Policy::Allowlist => vec![meta(&[literal(ALLOWLIST_SEED), mint], false)],
+        Policy::Blocklist => vec![meta(&[literal(BLOCKLIST_SEED), mint], false)],
+        Policy::VelocityLimit => vec![
+            meta(&[literal(VELOCITY_LIMIT_SEED), mint.clone()], false),
+            meta(&[literal(VELOCITY_TRACKER_SEED), mint, source_owner], true),
+        ],
+        Policy::AmountLimits => vec![meta(&[literal(AMOUNT_LIMITS_SEED), mint], false)],
+        Policy::TradingSchedule => vec![meta(&[literal(TRADING_SCHEDULE_SEED), mint], false)],
+        Policy::Vesting => vec![meta(
+            &[literal(VESTING_SCHEDULE_SEED), mint, source_owner],
+            false,
+        )],
+        Policy::TransferStats => vec![meta(&[literal(TRANSFER_STATS_SEED), mint], true)],
+        Policy::OwnerStats => vec![
+            meta(
+                &[literal(OWNER_STATS_SEED), mint.clone(), source_owner],
+                true,
+            ),
+            meta(&[literal(OWNER_STATS_SEED), mint, destination_owner], true),
+        ],
+        Policy::Royalty => vec![meta(&[literal(ROYALTY_SEED), mint], false), instructions],
+        Policy::Memo => vec![
+            meta(&[literal(MEMO_CONFIG_SEED), mint], false),
+            instructions,
+        ],
+        Policy::Pause => vec![meta(&[literal(PAUSE_SEED), mint], false)],
+        Policy::Kyc => vec![
+            meta(&[literal(KYC_CONFIG_SEED), mint.clone()], false),
+            meta(
+                &[literal(ATTESTATION_SEED), mint.clone(), source_owner],
+                false,
+            ),
+            meta(&[literal(ATTESTATION_SEED), mint, destination_owner], false),
+        ],
+        Policy::HolderCount => vec![meta(&[literal(HOLDER_COUNT_SEED), mint], true)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_program::program_error::ProgramError};
+
+    fn allowlist_data(owners: &[Pubkey]) -> Vec<u8> {
+        Allowlist {
+            authority: Pubkey::new_unique(),
+            owners: owners.iter().copied().collect(),
+        }
+        .pack()
+    }
+
+    #[test]
+    fn allowlist_is_searched_in_place() {
+        let owners = (0..5).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let data = allowlist_data(&owners);
+
+        for owner in &owners {
+            assert_eq!(Allowlist::contains_packed(&data, owner), Ok(true));
+        }
+        assert_eq!(
+            Allowlist::contains_packed(&data, &Pubkey::new_unique()),
+            Ok(false)
+        );
+        assert_eq!(
+            Allowlist::contains_packed(&data[..data.len() - 1], &owners[0]),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn bulk_loaded_owners_stay_sorted() {
+        let mut owners = (0..4)
+            .map(|_| Pubkey::new_unique())
+            .collect::<SortedPubkeys>();
+        let loaded = (0..4).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        owners.extend(loaded.iter().rev().chain(owners.clone().keys()).copied());
+
+        assert_eq!(owners.keys().len(), 8);
+        assert!(owners.keys().windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(loaded.iter().all(|owner| owners.contains(owner)));
+    }
+
+    fn velocity_limit(window_seconds: i64, max_amount: u64) -> VelocityLimit {
+        VelocityLimit {
+            authority: Pubkey::new_unique(),
+            window_seconds,
+            max_amount,
+        }
+    }
+
+    #[test]
+    fn velocity_tracker_caps_amount_per_rolling_window() {
+        let limit = velocity_limit(70, 100);
+        let mut tracker = VelocityTracker::default();
+        assert_eq!(VelocityTracker::bucket_seconds(&limit), 10);
+
+        assert_eq!(tracker.record(&limit, 1_000, 60), Ok(()));
+        assert_eq!(tracker.record(&limit, 1_065, 40), Ok(()));
+        assert_eq!(
+            tracker.record(&limit, 1_069, 1),
+            Err(HookPolicyError::VelocityLimitExceeded)
+        );
+        assert_eq!(tracker.transferred(), 100);
+
+        // A fixed window opened at 1_000 would have closed by now, but the
+        // 40 sent at 1_065 is still within the last 70 seconds
+        assert_eq!(
+            tracker.record(&limit, 1_080, 61),
+            Err(HookPolicyError::VelocityLimitExceeded)
+        );
+        assert_eq!(tracker.record(&limit, 1_080, 60), Ok(()));
+        assert_eq!(tracker.bucket_start, 1_080);
+        assert_eq!(tracker.transferred(), 100);
+
+        // Amounts drop out with their whole bucket, once the bucket has
+        // fallen a window behind
+        assert_eq!(
+            tracker.record(&limit, 1_135, 1),
+            Err(HookPolicyError::VelocityLimitExceeded)
+        );
+        assert_eq!(tracker.record(&limit, 1_140, 1), Ok(()));
+        assert_eq!(tracker.transferred(), 61);
+        assert_eq!(tracker.record(&limit, 1_160, 40), Ok(()));
+        assert_eq!(tracker.transferred(), 41);
+        assert_eq!(tracker.record(&limit, 1_240, 100), Ok(()));
+        assert_eq!(tracker.transferred(), 100);
+        assert_eq!(
+            tracker.record(&limit, 1_241, u64::MAX),
+            Err(HookPolicyError::VelocityLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn royalty_is_owed_on_the_sale_price_rounded_up() {
+        let royalty = RoyaltyConfig {
+            basis_points: 250,
+            min_lamports: 10,
+            ..RoyaltyConfig::default()
+        };
+
+        assert_eq!(royalty.owed(0), 10);
+        assert_eq!(royalty.owed(1_000), 25);
+        assert_eq!(royalty.owed(1_001), 26);
+        assert_eq!(royalty.owed(u64::MAX), u64::MAX / 40 + 1);
+        assert_eq!(royalty.check(1_000, 25), Ok(()));
+        assert_eq!(
+            royalty.check(1_000, 24),
+            Err(HookPolicyError::RoyaltyNotPaid)
+        );
+
+        let royalty = RoyaltyConfig {
+            basis_points: MAX_BASIS_POINTS + 1,
+            ..royalty
+        };
+        assert!(!royalty.is_valid());
+    }
+
+    #[test]
+    fn memo_must_fit_length_and_pattern() {
+        let config = MemoConfig {
+            max_length: 12,
+            pattern: "INV-????-*".to_string(),
+            ..MemoConfig::default()
+        };
+
+        assert_eq!(config.check(b"INV-2024-"), Ok(()));
+        assert_eq!(config.check(b"INV-2024-001"), Ok(()));
+        assert_eq!(
+            config.check(b"INV-2024-0001"),
+            Err(HookPolicyError::MemoTooLong)
+        );
+        for memo in [&b"INV-24-001"[..], b"inv-2024-001", b"INV-2024", b""] {
+            assert_eq!(
+                config.check(memo),
+                Err(HookPolicyError::MemoPatternMismatch),
+                "{memo:?}"
+            );
+        }
+
+        let config = MemoConfig {
+            max_length: 0,
+            pattern: "*order*".to_string(),
+            ..config
+        };
+        assert_eq!(config.check(b"order"), Ok(()));
+        assert_eq!(config.check(b"see order 42 for details"), Ok(()));
+        assert_eq!(
+            config.check(&[0xff]),
+            Err(HookPolicyError::MemoPatternMismatch)
+        );
+
+        assert_eq!(MemoConfig::default().check(&[0; 1_000]), Ok(()));
+    }
+
+    const HOUR: u32 = 60 * 60;
+
+    #[test]
+    fn kyc_tiers_limit_amount_until_expiry() {
+        let attester = Pubkey::new_unique();
+        let config = KycConfig {
+            authority: Pubkey::new_unique(),
+            attester,
+            tier_limits: vec![100, 1_000],
+        };
+        let attestation = |tier, expiry_timestamp| Attestation {
+            attester,
+            tier,
+            expiry_timestamp,
+        };
+        let (basic, verified, institutional) =
+            (attestation(0, 50), attestation(1, 50), attestation(2, 60));
+
+        assert_eq!(config.tier_limit(0), Some(100));
+        // Tiers past the end keep the last limit
+        assert_eq!(config.tier_limit(7), Some(1_000));
+        assert_eq!(config.check(0, 1_000, &verified, &institutional), Ok(()));
+        // The lower tier of the two owners applies
+        assert_eq!(
+            config.check(0, 101, &basic, &institutional),
+            Err(HookPolicyError::AmountAboveTierLimit)
+        );
+        assert_eq!(
+            config.check(50, 1, &verified, &institutional),
+            Err(HookPolicyError::AttestationExpired)
+        );
+
+        let foreign = Attestation {
+            attester: Pubkey::new_unique(),
+            ..basic.clone()
+        };
+        assert_eq!(
+            config.check(0, 1, &foreign, &basic),
+            Err(HookPolicyError::SourceNotAttested)
+        );
+        assert_eq!(
+            config.check(0, 1, &basic, &foreign),
+            Err(HookPolicyError::DestinationNotAttested)
+        );
+
+        let unlimited = KycConfig {
+            tier_limits: vec![],
+            ..config
+        };
+        assert_eq!(unlimited.check(0, u64::MAX, &basic, &basic), Ok(()));
+    }
+
+    #[test]
+    fn holder_count_follows_balances_to_and_from_zero() {
+        let mut count = HolderCount {
+            authority: Pubkey::new_unique(),
+            holder_count: 1,
+            max_holders: 2,
+        };
+
+        // 10 to an empty account, leaving 90 in the source
+        assert_eq!(count.record(10, 0, 90, 10), Ok(()));
+        assert_eq!(count.holder_count, 2);
+        assert_eq!(
+            count.record(10, 0, 80, 10),
+            Err(HookPolicyError::HolderLimitExceeded)
+        );
+        // A whole balance to an empty account keeps the count
+        assert_eq!(count.record(80, 0, 0, 80), Ok(()));
+        assert_eq!(count.holder_count, 2);
+        // A whole balance to a holder frees a place
+        assert_eq!(count.record(10, 0, 0, 90), Ok(()));
+        assert_eq!(count.holder_count, 1);
+        assert_eq!(count.record(0, 0, 0, 0), Ok(()));
+        assert_eq!(count.holder_count, 1);
+
+        // With a fee of 1 withheld, 10 leaves a holder of 1 with 10 and an
+        // empty account with 9
+        assert_eq!(count.record(10, 1, 50, 10), Ok(()));
+        assert_eq!(count.holder_count, 1);
+        assert_eq!(count.record(10, 1, 40, 9), Ok(()));
+        assert_eq!(count.holder_count, 2);
+        // Nothing arrives when the fee takes the whole amount
+        assert_eq!(count.record(5, 5, 35, 0), Ok(()));
+        assert_eq!(count.holder_count, 2);
+
+        count.max_holders = 0;
+        count.holder_count = u64::MAX - 1;
+        assert_eq!(count.record(1, 0, 1, 1), Ok(()));
+        assert_eq!(count.holder_count, u64::MAX);
+    }
+
+    #[test]
+    fn trading_schedule_locks_until_unlock_and_outside_windows() {
+        let day = i64::from(SECONDS_PER_DAY);
+        let mut schedule = TradingSchedule {
+            unlock_timestamp: 10 * day,
+            ..TradingSchedule::default()
+        };
+
+        assert_eq!(
+            schedule.check(10 * day - 1),
+            Err(HookPolicyError::TransfersLocked)
+        );
+        assert_eq!(schedule.check(10 * day), Ok(()));
+
+        // 09:00-17:00 and 22:00-02:00, wrapping past midnight
+        schedule.daily_windows = vec![
+            DailyWindow {
+                start: 9 * HOUR,
+                end: 17 * HOUR,
+            },
+            DailyWindow {
+                start: 22 * HOUR,
+                end: 2 * HOUR,
+            },
+        ];
+        let at = |hour: u32| 11 * day + i64::from(hour * HOUR);
+        for hour in [0, 1, 9, 16, 22, 23] {
+            assert_eq!(schedule.check(at(hour)), Ok(()), "{hour}:00");
+        }
+        for hour in [2, 8, 17, 21] {
+            assert_eq!(
+                schedule.check(at(hour)),
+                Err(HookPolicyError::OutsideTradingWindow),
+                "{hour}:00"
+            );
+        }
+        assert_eq!(
+            schedule.check(9 * day + i64::from(10 * HOUR)),
+            Err(HookPolicyError::TransfersLocked)
+        );
+    }
+
+    #[test]
+    fn vesting_unlocks_linearly_after_cliff() {
+        let vesting = VestingSchedule {
+            authority: Pubkey::new_unique(),
+            total_amount: 1_000,
+            start_timestamp: 1_000,
+            cliff_timestamp: 1_250,
+            end_timestamp: 2_000,
+        };
+
+        assert_eq!(vesting.locked_amount(0), 1_000);
+        assert_eq!(vesting.locked_amount(1_249), 1_000);
+        // The cliff releases everything accrued since the start
+        assert_eq!(vesting.locked_amount(1_250), 750);
+        assert_eq!(vesting.locked_amount(1_999), 1);
+        assert_eq!(vesting.locked_amount(2_000), 0);
+        assert_eq!(vesting.locked_amount(i64::MAX), 0);
+
+        assert_eq!(vesting.check(1_500, 500), Ok(()));
+        assert_eq!(
+            vesting.check(1_500, 499),
+            Err(HookPolicyError::VestingLocked)
+        );
+    }
+
+    #[test]
+    fn vesting_schedule_must_have_cliff_within_grant() {
+        let vesting = VestingSchedule {
+            total_amount: 100,
+            start_timestamp: 10,
+            cliff_timestamp: 10,
+            end_timestamp: 10,
+            ..VestingSchedule::default()
+        };
+        assert!(vesting.is_valid());
+        // Everything unlocks at once when the grant has no duration
+        assert_eq!(vesting.locked_amount(9), 100);
+        assert_eq!(vesting.locked_amount(10), 0);
+
+        for (start, cliff, end) in [(10, 9, 20), (10, 21, 20), (20, 20, 10)] {
+            let vesting = VestingSchedule {
+                start_timestamp: start,
+                cliff_timestamp: cliff,
+                end_timestamp: end,
+                ..VestingSchedule::default()
+            };
+            assert!(!vesting.is_valid(), "{start} {cliff} {end}");
+        }
+    }
+
+    #[test]
+    fn transfer_stats_count_transfers() {
+        let mut stats = TransferStats::default();
+        stats.record(5, 10);
+        stats.record(7, u64::MAX);
+
+        assert_eq!(
+            stats,
+            TransferStats {
+                transfer_count: 2,
+                volume: u128::from(u64::MAX) + 10,
+                last_transfer_slot: 7,
+            }
+        );
+    }
+}
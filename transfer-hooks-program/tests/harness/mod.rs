@@ -0,0 +1,285 @@
+//! In-process bank shared by the integration tests
+//!
+//! Token-2022, the associated token account program and the memo program
+//! are the builds bundled with `solana-program-test`. The transfer hook
+//! program runs natively under `cargo test` and from its shared object under
+//! `cargo test-sbf`.
+
+#![allow(dead_code)]
+
+use {
+    solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext},
+    solana_sdk::system_instruction,
+    solana_sdk::{
+        clock::Clock,
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+    spl_tlv_account_resolution::{
+        account::ExtraAccountMeta,
+        state::{AccountDataResult, ExtraAccountMetaList},
+    },
+    spl_token_2022::{
+        extension::StateWithExtensionsOwned, instruction::mint_to,
+        offchain::create_transfer_checked_instruction_with_extra_metas, state::Account,
+    },
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address, instruction::initialize_extra_account_meta_list,
+    },
+    token_extensions_common::{
+        account::{associated_token_address, create_associated_token_account},
+        mint::MintBuilder,
+    },
+    transfer_hooks_program::instruction::with_hook_config,
+};
+
+/// Start a bank with the transfer hook program loaded
+pub async fn start() -> ProgramTestContext {
+    let program_test = ProgramTest::new(
+        "transfer_hooks_program",
+        transfer_hooks_program::id(),
+        processor!(transfer_hooks_program::processor::process),
+    );
+
+    program_test.start_with_context().await
+}
+
+/// Sign `instructions` with the payer and `signers` and process them
+pub async fn process(
+    context: &ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let transaction = transaction(context, instructions, signers).await;
+
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Simulate `instructions` as [`process`] would send them, returning the
+/// logs or the error
+pub async fn simulate(
+    context: &ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<Vec<String>, TransactionError> {
+    let transaction = transaction(context, instructions, signers).await;
+    let simulation = context
+        .banks_client
+        .simulate_transaction(transaction)
+        .await
+        .unwrap();
+
+    simulation.result.unwrap()?;
+    Ok(simulation.simulation_details.unwrap().logs)
+}
+
+async fn transaction(
+    context: &ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Transaction {
+    // Blockhashes only change as slots pass, so a test sending the same
+    // instructions twice has to vary them to avoid a duplicate signature
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    )
+}
+
+/// The custom error code a failed transaction returned
+pub fn custom_error(error: BanksClientError) -> u32 {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        error => panic!("expected a custom program error, found {error:?}"),
+    }
+}
+
+/// Create the mint described by `builder`, paid for by the payer
+pub async fn create_mint(context: &ProgramTestContext, builder: &MintBuilder) -> Keypair {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let lamports = rent.minimum_balance(builder.mint_len().unwrap());
+    let instructions = builder
+        .instructions(&context.payer.pubkey(), &mint.pubkey(), lamports)
+        .unwrap();
+
+    process(context, &instructions, &[&mint]).await.unwrap();
+
+    mint
+}
+
+/// Create a mint invoking the hook program, with the payer as mint authority
+/// and `extra_account_metas` initialized
+pub async fn create_hooked_mint(
+    context: &ProgramTestContext,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> Pubkey {
+    let builder = MintBuilder::new(context.payer.pubkey(), 0);
+    create_hooked_mint_with(context, builder, extra_account_metas).await
+}
+
+/// Like [`create_hooked_mint`], with the other extensions of `builder`
+pub async fn create_hooked_mint_with(
+    context: &ProgramTestContext,
+    builder: MintBuilder,
+    extra_account_metas: &[ExtraAccountMeta],
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let program_id = transfer_hooks_program::id();
+    let builder = builder.transfer_hook(program_id);
+    let mint = create_mint(context, &builder).await.pubkey();
+
+    let address = get_extra_account_metas_address(&mint, &program_id);
+    let lamports = context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(ExtraAccountMetaList::size_of(extra_account_metas.len()).unwrap());
+    let instructions = [
+        system_instruction::transfer(&payer, &address, lamports),
+        with_hook_config(
+            initialize_extra_account_meta_list(
+                &program_id,
+                &address,
+                &mint,
+                &payer,
+                extra_account_metas,
+            ),
+            &program_id,
+            &mint,
+        ),
+    ];
+    process(context, &instructions, &[]).await.unwrap();
+
+    mint
+}
+
+/// A checked transfer of a hooked mint created by [`create_hooked_mint`],
+/// with the accounts the hook needs resolved
+pub async fn hooked_transfer(
+    context: &ProgramTestContext,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    create_transfer_checked_instruction_with_extra_metas(
+        &spl_token_2022::id(),
+        source,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        0,
+        |address| account_data(context, address),
+    )
+    .await
+    .unwrap()
+}
+
+/// Process a [`hooked_transfer`] signed by `owner`, returning the custom
+/// error code of a failure
+pub async fn transfer(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    source: &Pubkey,
+    owner: &Keypair,
+    destination: &Pubkey,
+    amount: u64,
+) -> Result<(), u32> {
+    transfer_after(context, vec![], mint, source, owner, destination, amount).await
+}
+
+/// [`transfer`] preceded by `instructions` in the same transaction
+pub async fn transfer_after(
+    context: &ProgramTestContext,
+    mut instructions: Vec<Instruction>,
+    mint: &Pubkey,
+    source: &Pubkey,
+    owner: &Keypair,
+    destination: &Pubkey,
+    amount: u64,
+) -> Result<(), u32> {
+    instructions
+        .push(hooked_transfer(context, source, mint, destination, &owner.pubkey(), amount).await);
+
+    process(context, &instructions, &[owner])
+        .await
+        .map_err(custom_error)
+}
+
+/// Move the bank's clock to `timestamp`
+pub async fn set_clock(context: &ProgramTestContext, timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = timestamp;
+    context.set_sysvar(&clock);
+}
+
+/// Create the associated token account of `owner`, paid for by the payer
+pub async fn create_token_account(
+    context: &ProgramTestContext,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Pubkey {
+    let instruction = create_associated_token_account(&context.payer.pubkey(), owner, mint);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    associated_token_address(owner, mint)
+}
+
+/// Mint `amount` to `account` with the payer as mint authority
+pub async fn mint_tokens(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let instruction = mint_to(
+        &spl_token_2022::id(),
+        mint,
+        account,
+        &context.payer.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+
+    process(context, &[instruction], &[]).await
+}
+
+/// Fetch and unpack a token account
+pub async fn get_token_account(
+    context: &ProgramTestContext,
+    address: &Pubkey,
+) -> StateWithExtensionsOwned<Account> {
+    let account = context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .expect("token account exists");
+
+    StateWithExtensionsOwned::<Account>::unpack(account.data).unwrap()
+}
+
+/// Data of the account at `address`, for resolving transfer hook accounts
+pub async fn account_data(context: &ProgramTestContext, address: Pubkey) -> AccountDataResult {
+    Ok(context
+        .banks_client
+        .get_account(address)
+        .await?
+        .map(|account| account.data))
+}
@@ -0,0 +1,177 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signature::Keypair, signer::Signer,
+        transaction::TransactionError,
+    },
+    spl_token_2022::instruction::{set_authority, AuthorityType},
+    spl_transfer_hook_interface::{
+        error::TransferHookError, get_extra_account_metas_address,
+        instruction::update_extra_account_meta_list,
+    },
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            accept_hook_admin, close_extra_account_meta_list, initialize_amount_limits,
+            initialize_hook_config, propose_hook_admin, with_hook_config,
+        },
+        state::{extra_account_metas, Policy},
+    },
+};
+
+#[tokio::test]
+async fn hook_admin_manages_metas_of_fixed_supply_mint() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let program_id = hook_program_id();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+    let address = get_extra_account_metas_address(&mint, &program_id);
+
+    let instruction = initialize_hook_config(&program_id, &mint, &payer, &payer, &payer);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    // The admin role only moves once the proposed admin accepts it
+    let admin = Keypair::new();
+    let instruction = propose_hook_admin(&program_id, &mint, &payer, &admin.pubkey());
+    process(&context, &[instruction], &[]).await.unwrap();
+    let instruction = accept_hook_admin(&program_id, &mint, &admin.pubkey());
+    process(&context, &[instruction], &[&admin]).await.unwrap();
+
+    let instruction = set_authority(
+        &spl_token_2022::id(),
+        &mint,
+        None,
+        AuthorityType::MintTokens,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let update = |authority: &Pubkey| {
+        let instruction =
+            update_extra_account_meta_list(&program_id, &address, &mint, authority, &[]);
+        with_hook_config(instruction, &program_id, &mint)
+    };
+    let error = process(&context, &[update(&payer)], &[]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+
+    process(&context, &[update(&admin.pubkey())], &[&admin])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mint_authority_cannot_bypass_hook_admin() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let program_id = hook_program_id();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+    let address = get_extra_account_metas_address(&mint, &program_id);
+
+    let admin = Keypair::new();
+    let instruction = initialize_hook_config(&program_id, &mint, &payer, &payer, &admin.pubkey());
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    // Leaving out the hook config does not fall back to the mint authority
+    let update = |authority: &Pubkey| {
+        update_extra_account_meta_list(&program_id, &address, &mint, authority, &[])
+    };
+    let error = process(&context, &[update(&payer)], &[]).await.unwrap_err();
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+    let instruction = with_hook_config(update(&payer), &program_id, &mint);
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+    let instruction = close_extra_account_meta_list(&program_id, &mint, &payer, &payer);
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+
+    let instruction = with_hook_config(update(&admin.pubkey()), &program_id, &mint);
+    process(&context, &[instruction], &[&admin]).await.unwrap();
+}
+
+#[tokio::test]
+async fn hook_admin_creates_policies() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let program_id = hook_program_id();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+
+    let admin = Keypair::new();
+    let instruction = initialize_hook_config(&program_id, &mint, &payer, &payer, &admin.pubkey());
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let initialize = |authority: &Pubkey| {
+        initialize_amount_limits(&program_id, &mint, authority, &payer, authority, 1, 100)
+    };
+    let error = process(&context, &[initialize(&payer)], &[])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+
+    // Leaving out the hook config does not fall back to the mint authority
+    let mut instruction = initialize(&payer);
+    instruction.accounts.pop();
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+
+    process(&context, &[initialize(&admin.pubkey())], &[&admin])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn transfer_hook_authority_creates_hook_config_without_mint_authority() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let program_id = hook_program_id();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+
+    // The payer stays the authority of the transfer hook
+    let instruction = set_authority(
+        &spl_token_2022::id(),
+        &mint,
+        None,
+        AuthorityType::MintTokens,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let other = Keypair::new();
+    let instruction = initialize_hook_config(&program_id, &mint, &other.pubkey(), &payer, &payer);
+    let error = process(&context, &[instruction], &[&other])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        TransferHookError::IncorrectMintAuthority as u32
+    );
+
+    let instruction = initialize_hook_config(&program_id, &mint, &payer, &payer, &payer);
+    process(&context, &[instruction], &[]).await.unwrap();
+    let instruction = initialize_amount_limits(&program_id, &mint, &payer, &payer, &payer, 1, 100);
+    process(&context, &[instruction], &[]).await.unwrap();
+}
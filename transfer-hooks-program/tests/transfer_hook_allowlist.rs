@@ -0,0 +1,110 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{add_to_allowlist, initialize_allowlist, remove_from_allowlist},
+        state::{extra_account_metas, find_allowlist_address, Allowlist, Policy, PolicyAccount},
+    },
+};
+
+/// A hooked mint enforcing an allowlist managed by the returned authority,
+/// with the payer holding 100 tokens
+async fn setup(context: &ProgramTestContext) -> (Pubkey, Pubkey, Keypair) {
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(context, &extra_account_metas(Policy::Allowlist)).await;
+    let authority = Keypair::new();
+    let instruction = initialize_allowlist(
+        &hook_program_id(),
+        &mint,
+        &payer,
+        &payer,
+        &authority.pubkey(),
+    );
+    process(context, &[instruction], &[]).await.unwrap();
+
+    let source = create_token_account(context, &payer, &mint).await;
+    mint_tokens(context, &mint, &source, 100).await.unwrap();
+
+    (mint, source, authority)
+}
+
+#[tokio::test]
+async fn transfer_requires_listed_destination_owner() {
+    let context = start().await;
+    let (mint, source, authority) = setup(&context).await;
+    let owner = Pubkey::new_unique();
+    let destination = create_token_account(&context, &owner, &mint).await;
+
+    assert_eq!(
+        transfer(&context, &mint, &source, &context.payer, &destination, 10).await,
+        Err(HookPolicyError::DestinationNotAllowlisted as u32)
+    );
+
+    let instruction = add_to_allowlist(
+        &hook_program_id(),
+        &mint,
+        &authority.pubkey(),
+        &context.payer.pubkey(),
+        vec![Pubkey::new_unique(), owner],
+    );
+    process(&context, &[instruction], &[&authority])
+        .await
+        .unwrap();
+    transfer(&context, &mint, &source, &context.payer, &destination, 11)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        11
+    );
+
+    let instruction =
+        remove_from_allowlist(&hook_program_id(), &mint, &authority.pubkey(), vec![owner]);
+    process(&context, &[instruction], &[&authority])
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &source, &context.payer, &destination, 12).await,
+        Err(HookPolicyError::DestinationNotAllowlisted as u32)
+    );
+}
+
+#[tokio::test]
+async fn allowlist_changes_require_authority() {
+    let context = start().await;
+    let (mint, _, _) = setup(&context).await;
+    let impostor = Keypair::new();
+
+    let instruction = add_to_allowlist(
+        &hook_program_id(),
+        &mint,
+        &impostor.pubkey(),
+        &context.payer.pubkey(),
+        vec![Pubkey::new_unique()],
+    );
+    let error = process(&context, &[instruction], &[&impostor])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+
+    let (address, _) = find_allowlist_address(&mint, &hook_program_id());
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(Allowlist::unpack(&account.data)
+        .unwrap()
+        .owners
+        .keys()
+        .is_empty());
+}
@@ -0,0 +1,74 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, signer::Signer,
+        transaction::TransactionError,
+    },
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{initialize_amount_limits, set_amount_limits},
+        state::{extra_account_metas, Policy},
+    },
+};
+
+#[tokio::test]
+async fn transfer_amount_must_be_within_limits() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+    let instruction =
+        initialize_amount_limits(&hook_program_id(), &mint, &payer, &payer, &payer, 10, 50);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let source = create_token_account(&context, &payer, &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 1000).await.unwrap();
+    let send = |amount| {
+        transfer(
+            &context,
+            &mint,
+            &source,
+            &context.payer,
+            &destination,
+            amount,
+        )
+    };
+
+    assert_eq!(
+        send(9).await,
+        Err(HookPolicyError::AmountBelowMinimum as u32)
+    );
+    assert_eq!(
+        send(51).await,
+        Err(HookPolicyError::AmountAboveMaximum as u32)
+    );
+    send(10).await.unwrap();
+    send(50).await.unwrap();
+
+    let instruction = set_amount_limits(&hook_program_id(), &mint, &payer, 1, 100);
+    process(&context, &[instruction], &[]).await.unwrap();
+    send(100).await.unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        160
+    );
+}
+
+#[tokio::test]
+async fn amount_limits_must_be_ordered() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::AmountLimits)).await;
+
+    let instruction =
+        initialize_amount_limits(&hook_program_id(), &mint, &payer, &payer, &payer, 51, 50);
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,98 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{add_to_blocklist, initialize_blocklist, remove_from_blocklist},
+        state::{extra_account_metas, find_blocklist_address, Blocklist, Policy, PolicyAccount},
+    },
+};
+
+/// A hooked mint enforcing a blocklist managed by the payer, with the
+/// returned owner holding 100 tokens in the returned account
+async fn setup(context: &ProgramTestContext) -> (Pubkey, Keypair, Pubkey) {
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(context, &extra_account_metas(Policy::Blocklist)).await;
+    let instruction = initialize_blocklist(&hook_program_id(), &mint, &payer, &payer, &payer);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    let owner = Keypair::new();
+    let source = create_token_account(context, &owner.pubkey(), &mint).await;
+    mint_tokens(context, &mint, &source, 100).await.unwrap();
+
+    (mint, owner, source)
+}
+
+async fn block(context: &ProgramTestContext, mint: &Pubkey, owners: Vec<Pubkey>) {
+    let payer = context.payer.pubkey();
+    let instruction = add_to_blocklist(&hook_program_id(), mint, &payer, &payer, owners);
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+async fn unblock(context: &ProgramTestContext, mint: &Pubkey, owners: Vec<Pubkey>) {
+    let payer = context.payer.pubkey();
+    let instruction = remove_from_blocklist(&hook_program_id(), mint, &payer, owners);
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn transfer_rejects_blocklisted_owners() {
+    let context = start().await;
+    let (mint, owner, source) = setup(&context).await;
+    let destination_owner = Pubkey::new_unique();
+    let destination = create_token_account(&context, &destination_owner, &mint).await;
+
+    transfer(&context, &mint, &source, &owner, &destination, 10)
+        .await
+        .unwrap();
+
+    block(&context, &mint, vec![owner.pubkey()]).await;
+    assert_eq!(
+        transfer(&context, &mint, &source, &owner, &destination, 11).await,
+        Err(HookPolicyError::SourceBlocklisted as u32)
+    );
+
+    unblock(&context, &mint, vec![owner.pubkey()]).await;
+    block(&context, &mint, vec![destination_owner]).await;
+    assert_eq!(
+        transfer(&context, &mint, &source, &owner, &destination, 12).await,
+        Err(HookPolicyError::DestinationBlocklisted as u32)
+    );
+
+    unblock(&context, &mint, vec![destination_owner]).await;
+    transfer(&context, &mint, &source, &owner, &destination, 13)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        23
+    );
+}
+
+#[tokio::test]
+async fn blocklist_is_bulk_loaded_in_batches() {
+    let context = start().await;
+    let (mint, _, _) = setup(&context).await;
+
+    let owners = (0..75).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    for batch in owners.chunks(25) {
+        block(&context, &mint, batch.to_vec()).await;
+    }
+
+    let (address, _) = find_blocklist_address(&mint, &hook_program_id());
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let blocklist = Blocklist::unpack(&account.data).unwrap();
+    assert_eq!(blocklist.owners.keys().len(), owners.len());
+    assert!(owners.iter().all(|owner| blocklist.owners.contains(owner)));
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert!(rent.is_exempt(account.lamports, account.data.len()));
+}
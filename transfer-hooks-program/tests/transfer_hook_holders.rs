@@ -0,0 +1,152 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    token_extensions_common::mint::MintBuilder,
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            initialize_holder_count, set_holder_count, InitializeHolderCount, SetHolderCount,
+        },
+        state::{
+            extra_account_metas, find_holder_count_address, HolderCount, Policy, PolicyAccount,
+        },
+    },
+};
+
+async fn get_holder_count(context: &ProgramTestContext, mint: &Pubkey) -> u64 {
+    let (address, _) = find_holder_count_address(mint, &hook_program_id());
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    HolderCount::unpack(&account.data).unwrap().holder_count
+}
+
+#[tokio::test]
+async fn transfers_to_new_holders_stop_at_the_cap() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::HolderCount)).await;
+
+    // The treasury already holds the supply
+    let treasury_owner = Keypair::new();
+    let treasury = create_token_account(&context, &treasury_owner.pubkey(), &mint).await;
+    mint_tokens(&context, &mint, &treasury, 100).await.unwrap();
+    let data = InitializeHolderCount {
+        authority: payer,
+        holder_count: 1,
+        max_holders: 2,
+    };
+    let instruction = initialize_holder_count(&hook_program_id(), &mint, &payer, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let investor_owner = Keypair::new();
+    let investor = create_token_account(&context, &investor_owner.pubkey(), &mint).await;
+    let latecomer = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    transfer(&context, &mint, &treasury, &treasury_owner, &investor, 10)
+        .await
+        .unwrap();
+    assert_eq!(get_holder_count(&context, &mint).await, 2);
+    assert_eq!(
+        transfer(&context, &mint, &treasury, &treasury_owner, &latecomer, 1).await,
+        Err(HookPolicyError::HolderLimitExceeded as u32)
+    );
+
+    // Selling out to the latecomer hands over the investor's place
+    transfer(&context, &mint, &investor, &investor_owner, &latecomer, 10)
+        .await
+        .unwrap();
+    assert_eq!(get_holder_count(&context, &mint).await, 2);
+    transfer(&context, &mint, &treasury, &treasury_owner, &latecomer, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &treasury, &treasury_owner, &investor, 3).await,
+        Err(HookPolicyError::HolderLimitExceeded as u32)
+    );
+
+    let data = SetHolderCount {
+        holder_count: 2,
+        max_holders: 3,
+    };
+    let instruction = set_holder_count(&hook_program_id(), &mint, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+    transfer(&context, &mint, &treasury, &treasury_owner, &investor, 4)
+        .await
+        .unwrap();
+    assert_eq!(get_holder_count(&context, &mint).await, 3);
+}
+
+#[tokio::test]
+async fn transfer_fees_are_left_out_of_the_destination_balance() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    // A 10% fee, rounded up
+    let builder = MintBuilder::new(payer, 0).transfer_fee(1_000, u64::MAX);
+    let mint =
+        create_hooked_mint_with(&context, builder, &extra_account_metas(Policy::HolderCount)).await;
+
+    let treasury_owner = Keypair::new();
+    let treasury = create_token_account(&context, &treasury_owner.pubkey(), &mint).await;
+    mint_tokens(&context, &mint, &treasury, 100).await.unwrap();
+    let data = InitializeHolderCount {
+        authority: payer,
+        holder_count: 1,
+        max_holders: 3,
+    };
+    let instruction = initialize_holder_count(&hook_program_id(), &mint, &payer, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let investor_owner = Keypair::new();
+    let investor = create_token_account(&context, &investor_owner.pubkey(), &mint).await;
+    let buyer = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    transfer(&context, &mint, &treasury, &treasury_owner, &investor, 10)
+        .await
+        .unwrap();
+    transfer(&context, &mint, &investor, &investor_owner, &buyer, 8)
+        .await
+        .unwrap();
+    assert_eq!(get_holder_count(&context, &mint).await, 3);
+
+    // The investor is left with 1 and receives 9, so holding the amount sent
+    // does not make it a new holder
+    transfer(&context, &mint, &treasury, &treasury_owner, &investor, 10)
+        .await
+        .unwrap();
+    assert_eq!(get_token_account(&context, &investor).await.base.amount, 10);
+    assert_eq!(get_holder_count(&context, &mint).await, 3);
+}
+
+#[tokio::test]
+async fn only_authority_can_change_the_cap() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::HolderCount)).await;
+    let data = InitializeHolderCount {
+        authority: payer,
+        holder_count: 0,
+        max_holders: 1,
+    };
+    let instruction = initialize_holder_count(&hook_program_id(), &mint, &payer, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let impostor = Keypair::new();
+    let data = SetHolderCount {
+        holder_count: 0,
+        max_holders: 0,
+    };
+    let instruction = set_holder_count(&hook_program_id(), &mint, &impostor.pubkey(), data);
+    let error = process(&context, &[instruction], &[&impostor])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+}
@@ -0,0 +1,168 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            initialize_kyc_config, issue_attestation, revoke_attestation, InitializeKycConfig,
+            IssueAttestation,
+        },
+        state::{extra_account_metas, find_attestation_address, Policy},
+    },
+};
+
+/// Create a hooked mint whose attester lets tier 0 move up to 100 and tier 1
+/// up to 1000
+async fn create_kyc_mint(context: &ProgramTestContext, attester: &Pubkey) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(context, &extra_account_metas(Policy::Kyc)).await;
+    let data = InitializeKycConfig {
+        authority: payer,
+        attester: *attester,
+        tier_limits: vec![100, 1000],
+    };
+    let instruction = initialize_kyc_config(&hook_program_id(), &mint, &payer, &payer, data);
+    process(context, &[instruction], &[]).await.unwrap();
+    mint
+}
+
+/// Attest to each of `owners` at `tier` until `expiry_timestamp`
+async fn attest(
+    context: &ProgramTestContext,
+    mint: &Pubkey,
+    attester: &Keypair,
+    owners: &[Pubkey],
+    tier: u8,
+    expiry_timestamp: i64,
+) -> Result<(), u32> {
+    let payer = context.payer.pubkey();
+    let instructions = owners
+        .iter()
+        .map(|owner| {
+            let data = IssueAttestation {
+                tier,
+                expiry_timestamp,
+            };
+            issue_attestation(
+                &hook_program_id(),
+                mint,
+                &attester.pubkey(),
+                owner,
+                &payer,
+                data,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    process(context, &instructions, &[attester])
+        .await
+        .map_err(custom_error)
+}
+
+#[tokio::test]
+async fn transfers_require_unexpired_attestations_within_tier() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let attester = Keypair::new();
+    let mint = create_kyc_mint(&context, &attester.pubkey()).await;
+
+    let sender = Keypair::new();
+    let receiver = Pubkey::new_unique();
+    let source = create_token_account(&context, &sender.pubkey(), &mint).await;
+    let destination = create_token_account(&context, &receiver, &mint).await;
+    mint_tokens(&context, &mint, &source, 1000).await.unwrap();
+    let send = |amount| transfer(&context, &mint, &source, &sender, &destination, amount);
+
+    set_clock(&context, 1000).await;
+    assert_eq!(
+        send(1).await,
+        Err(HookPolicyError::SourceNotAttested as u32)
+    );
+    attest(&context, &mint, &attester, &[sender.pubkey()], 1, 2000)
+        .await
+        .unwrap();
+    assert_eq!(
+        send(2).await,
+        Err(HookPolicyError::DestinationNotAttested as u32)
+    );
+
+    // The receiver's lower tier caps the amount
+    attest(&context, &mint, &attester, &[receiver], 0, 2000)
+        .await
+        .unwrap();
+    assert_eq!(
+        send(101).await,
+        Err(HookPolicyError::AmountAboveTierLimit as u32)
+    );
+    send(100).await.unwrap();
+
+    set_clock(&context, 2000).await;
+    assert_eq!(
+        send(3).await,
+        Err(HookPolicyError::AttestationExpired as u32)
+    );
+    attest(
+        &context,
+        &mint,
+        &attester,
+        &[sender.pubkey(), receiver],
+        1,
+        3000,
+    )
+    .await
+    .unwrap();
+    send(101).await.unwrap();
+
+    let instruction = revoke_attestation(
+        &hook_program_id(),
+        &mint,
+        &receiver,
+        &attester.pubkey(),
+        &payer,
+    );
+    process(&context, &[instruction], &[&attester])
+        .await
+        .unwrap();
+    let (address, _) = find_attestation_address(&mint, &receiver, &hook_program_id());
+    assert!(context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        send(4).await,
+        Err(HookPolicyError::DestinationNotAttested as u32)
+    );
+
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        201
+    );
+}
+
+#[tokio::test]
+async fn only_attester_can_issue() {
+    let context = start().await;
+    let attester = Keypair::new();
+    let mint = create_kyc_mint(&context, &attester.pubkey()).await;
+
+    // Not even the mint authority
+    let impostor = Keypair::new();
+    assert_eq!(
+        attest(
+            &context,
+            &mint,
+            &impostor,
+            &[Pubkey::new_unique()],
+            1,
+            i64::MAX
+        )
+        .await,
+        Err(HookPolicyError::IncorrectAuthority as u32)
+    );
+}
@@ -0,0 +1,75 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            initialize_memo_config, set_memo_config, InitializeMemoConfig, SetMemoConfig,
+        },
+        state::{extra_account_metas, Policy},
+    },
+};
+
+#[tokio::test]
+async fn transfer_requires_matching_memo() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::Memo)).await;
+    let data = InitializeMemoConfig {
+        authority: payer,
+        max_length: 16,
+        pattern: "INV-*".to_string(),
+    };
+    let instruction = initialize_memo_config(&hook_program_id(), &mint, &payer, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let source = create_token_account(&context, &payer, &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 100).await.unwrap();
+    let payer = context.payer.pubkey();
+    // Transfer `amount`, after a memo instruction if `memo` is given
+    let send = |amount, memo: Option<&str>| {
+        let memo =
+            Vec::from_iter(memo.map(|memo| spl_memo::build_memo(memo.as_bytes(), &[&payer])));
+        transfer_after(
+            &context,
+            memo,
+            &mint,
+            &source,
+            &context.payer,
+            &destination,
+            amount,
+        )
+    };
+
+    assert_eq!(
+        send(1, None).await,
+        Err(HookPolicyError::MemoRequired as u32)
+    );
+    assert_eq!(
+        send(2, Some("order 42")).await,
+        Err(HookPolicyError::MemoPatternMismatch as u32)
+    );
+    assert_eq!(
+        send(3, Some("INV-0000000000042")).await,
+        Err(HookPolicyError::MemoTooLong as u32)
+    );
+    send(4, Some("INV-42")).await.unwrap();
+
+    // A longer pattern grows the config
+    let data = SetMemoConfig {
+        max_length: 0,
+        pattern: "order ?? for customer *".to_string(),
+    };
+    let instruction = set_memo_config(&hook_program_id(), &mint, &payer, &payer, data);
+    process(&context, &[instruction], &[]).await.unwrap();
+    send(5, Some("order 42 for customer 7")).await.unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        9
+    );
+}
@@ -0,0 +1,106 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            add_pause_exemptions, initialize_pause, pause, remove_pause_exemptions, unpause,
+        },
+        state::{extra_account_metas, Policy},
+    },
+};
+
+#[tokio::test]
+async fn guardian_pauses_all_but_exempt_accounts() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::Pause)).await;
+    let guardian = Keypair::new();
+    let instruction = initialize_pause(
+        &hook_program_id(),
+        &mint,
+        &payer,
+        &payer,
+        &guardian.pubkey(),
+    );
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let treasury_owner = Keypair::new();
+    let treasury = create_token_account(&context, &treasury_owner.pubkey(), &mint).await;
+    let holder_owner = Keypair::new();
+    let holder = create_token_account(&context, &holder_owner.pubkey(), &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &treasury, 100).await.unwrap();
+    mint_tokens(&context, &mint, &holder, 100).await.unwrap();
+    transfer(&context, &mint, &holder, &holder_owner, &destination, 1)
+        .await
+        .unwrap();
+
+    let instructions = [
+        pause(&hook_program_id(), &mint, &guardian.pubkey()),
+        add_pause_exemptions(
+            &hook_program_id(),
+            &mint,
+            &guardian.pubkey(),
+            &payer,
+            vec![treasury],
+        ),
+    ];
+    process(&context, &instructions, &[&guardian])
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &holder, &holder_owner, &destination, 2).await,
+        Err(HookPolicyError::TransfersPaused as u32)
+    );
+    transfer(&context, &mint, &treasury, &treasury_owner, &destination, 3)
+        .await
+        .unwrap();
+
+    let instruction = remove_pause_exemptions(
+        &hook_program_id(),
+        &mint,
+        &guardian.pubkey(),
+        vec![treasury],
+    );
+    process(&context, &[instruction], &[&guardian])
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &treasury, &treasury_owner, &destination, 4).await,
+        Err(HookPolicyError::TransfersPaused as u32)
+    );
+
+    let instruction = unpause(&hook_program_id(), &mint, &guardian.pubkey());
+    process(&context, &[instruction], &[&guardian])
+        .await
+        .unwrap();
+    transfer(&context, &mint, &holder, &holder_owner, &destination, 5)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        9
+    );
+}
+
+#[tokio::test]
+async fn only_guardian_can_pause() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::Pause)).await;
+    let guardian = Pubkey::new_unique();
+    let instruction = initialize_pause(&hook_program_id(), &mint, &payer, &payer, &guardian);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    // Not even the mint authority
+    let instruction = pause(&hook_program_id(), &mint, &payer);
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+}
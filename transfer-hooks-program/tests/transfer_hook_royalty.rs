@@ -0,0 +1,200 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        pubkey::Pubkey,
+        signature::Keypair,
+        signer::Signer,
+        system_instruction,
+        transaction::TransactionError,
+    },
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{initialize_royalty, set_royalty, InitializeRoyalty, SetRoyalty},
+        state::{extra_account_metas, Policy},
+    },
+};
+
+const MIN_LAMPORTS: u64 = LAMPORTS_PER_SOL / 100;
+
+/// A hooked mint owing 5% of the sale price and at least `MIN_LAMPORTS` to
+/// the creator vault, with the seller holding 100 tokens in the source
+struct Market {
+    mint: Pubkey,
+    creator_vault: Pubkey,
+    seller: Keypair,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+impl Market {
+    async fn new(context: &ProgramTestContext) -> Self {
+        let payer = context.payer.pubkey();
+        let mint = create_hooked_mint(context, &extra_account_metas(Policy::Royalty)).await;
+        let creator_vault = Pubkey::new_unique();
+        let data = InitializeRoyalty {
+            authority: payer,
+            creator_vault,
+            basis_points: 500,
+            min_lamports: MIN_LAMPORTS,
+        };
+        let instruction = initialize_royalty(&hook_program_id(), &mint, &payer, &payer, data);
+        process(context, &[instruction], &[]).await.unwrap();
+
+        let seller = Keypair::new();
+        let source = create_token_account(context, &seller.pubkey(), &mint).await;
+        let destination = create_token_account(context, &payer, &mint).await;
+        mint_tokens(context, &mint, &source, 100).await.unwrap();
+
+        Self {
+            mint,
+            creator_vault,
+            seller,
+            source,
+            destination,
+        }
+    }
+
+    /// Instructions transferring `amount` from the seller, with the payer
+    /// paying them `price` and the creator vault `royalty` lamports first
+    async fn sale(
+        &self,
+        context: &ProgramTestContext,
+        amount: u64,
+        price: u64,
+        royalty: u64,
+    ) -> Vec<Instruction> {
+        let payer = context.payer.pubkey();
+        let seller = self.seller.pubkey();
+        let mut instructions = [(seller, price), (self.creator_vault, royalty)]
+            .into_iter()
+            .filter(|(_, lamports)| *lamports > 0)
+            .map(|(to, lamports)| system_instruction::transfer(&payer, &to, lamports))
+            .collect::<Vec<_>>();
+        let transfer = hooked_transfer(
+            context,
+            &self.source,
+            &self.mint,
+            &self.destination,
+            &seller,
+            amount,
+        )
+        .await;
+        instructions.push(transfer);
+        instructions
+    }
+
+    /// Process the [`Market::sale`] of `amount` in its own transaction
+    async fn sell(
+        &self,
+        context: &ProgramTestContext,
+        amount: u64,
+        price: u64,
+        royalty: u64,
+    ) -> Result<(), u32> {
+        let instructions = self.sale(context, amount, price, royalty).await;
+
+        process(context, &instructions, &[&self.seller])
+            .await
+            .map_err(custom_error)
+    }
+}
+
+#[tokio::test]
+async fn transfer_requires_royalty_payment() {
+    let context = start().await;
+    let market = Market::new(&context).await;
+    let price = LAMPORTS_PER_SOL;
+
+    assert_eq!(
+        market.sell(&context, 1, 0, 0).await,
+        Err(HookPolicyError::RoyaltyNotPaid as u32)
+    );
+    market.sell(&context, 2, 0, MIN_LAMPORTS).await.unwrap();
+
+    assert_eq!(
+        market.sell(&context, 3, price, price / 20 - 1).await,
+        Err(HookPolicyError::RoyaltyNotPaid as u32)
+    );
+    market.sell(&context, 4, price, price / 20).await.unwrap();
+
+    let vault = context
+        .banks_client
+        .get_account(market.creator_vault)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(vault.lamports, MIN_LAMPORTS + price / 20);
+    assert_eq!(
+        get_token_account(&context, &market.destination)
+            .await
+            .base
+            .amount,
+        6
+    );
+}
+
+#[tokio::test]
+async fn each_transfer_requires_its_own_royalty_payment() {
+    let context = start().await;
+    let market = Market::new(&context).await;
+    let price = LAMPORTS_PER_SOL;
+
+    // The second transfer has no payment right before it
+    let mut instructions = market.sale(&context, 1, price, price / 20).await;
+    instructions.extend(market.sale(&context, 2, 0, 0).await);
+    let error = process(&context, &instructions, &[&market.seller])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.unwrap(),
+        TransactionError::InstructionError(
+            3,
+            InstructionError::Custom(HookPolicyError::RoyaltyNotPaid as u32)
+        )
+    );
+
+    let mut instructions = market.sale(&context, 1, price, price / 20).await;
+    instructions.extend(market.sale(&context, 2, 0, MIN_LAMPORTS).await);
+    process(&context, &instructions, &[&market.seller])
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &market.destination)
+            .await
+            .base
+            .amount,
+        3
+    );
+}
+
+#[tokio::test]
+async fn royalty_changes_require_authority() {
+    let context = start().await;
+    let market = Market::new(&context).await;
+    let impostor = Keypair::new();
+    let data = || SetRoyalty {
+        creator_vault: market.creator_vault,
+        basis_points: 0,
+        min_lamports: 0,
+    };
+
+    let instruction = set_royalty(&hook_program_id(), &market.mint, &impostor.pubkey(), data());
+    let error = process(&context, &[instruction], &[&impostor])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        custom_error(error),
+        HookPolicyError::IncorrectAuthority as u32
+    );
+
+    let payer = context.payer.pubkey();
+    let instruction = set_royalty(&hook_program_id(), &market.mint, &payer, data());
+    process(&context, &[instruction], &[]).await.unwrap();
+    market.sell(&context, 1, 0, 0).await.unwrap();
+}
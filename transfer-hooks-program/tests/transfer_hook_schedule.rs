@@ -0,0 +1,84 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            add_schedule_exemptions, initialize_trading_schedule, remove_schedule_exemptions,
+            set_trading_schedule,
+        },
+        state::{extra_account_metas, DailyWindow, Policy, SECONDS_PER_DAY},
+    },
+};
+
+const HOUR: u32 = 60 * 60;
+
+#[tokio::test]
+async fn transfers_follow_the_schedule_unless_exempt() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let day = i64::from(SECONDS_PER_DAY);
+    let unlock = 100 * day;
+
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::TradingSchedule)).await;
+    let window = DailyWindow {
+        start: 9 * HOUR,
+        end: 17 * HOUR,
+    };
+    let instruction = initialize_trading_schedule(
+        &hook_program_id(),
+        &mint,
+        &payer,
+        &payer,
+        &payer,
+        unlock,
+        vec![window],
+    );
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let source = create_token_account(&context, &payer, &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 1000).await.unwrap();
+    let send = |amount| {
+        transfer(
+            &context,
+            &mint,
+            &source,
+            &context.payer,
+            &destination,
+            amount,
+        )
+    };
+
+    set_clock(&context, unlock - 1).await;
+    assert_eq!(send(1).await, Err(HookPolicyError::TransfersLocked as u32));
+
+    // The mint authority's account may move tokens during the lock
+    let instruction =
+        add_schedule_exemptions(&hook_program_id(), &mint, &payer, &payer, vec![source]);
+    process(&context, &[instruction], &[]).await.unwrap();
+    send(2).await.unwrap();
+    let instruction = remove_schedule_exemptions(&hook_program_id(), &mint, &payer, vec![source]);
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    set_clock(&context, unlock + i64::from(8 * HOUR)).await;
+    assert_eq!(
+        send(3).await,
+        Err(HookPolicyError::OutsideTradingWindow as u32)
+    );
+    set_clock(&context, unlock + i64::from(9 * HOUR)).await;
+    send(4).await.unwrap();
+
+    let instruction = set_trading_schedule(&hook_program_id(), &mint, &payer, &payer, 0, vec![]);
+    process(&context, &[instruction], &[]).await.unwrap();
+    set_clock(&context, unlock + i64::from(20 * HOUR)).await;
+    send(5).await.unwrap();
+
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        11
+    );
+}
@@ -0,0 +1,76 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{clock::Clock, pubkey::Pubkey, signer::Signer},
+    transfer_hooks_program::{
+        id as hook_program_id,
+        instruction::{initialize_owner_stats, initialize_transfer_stats},
+        state::{
+            extra_account_metas, find_owner_stats_address, find_transfer_stats_address, OwnerStats,
+            Policy, PolicyAccount, TransferStats,
+        },
+    },
+};
+
+async fn get_stats<T: PolicyAccount>(context: &ProgramTestContext, address: Pubkey) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    T::unpack(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn transfers_are_counted_for_mint_and_tracked_owners() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mut metas = extra_account_metas(Policy::TransferStats);
+    metas.extend(extra_account_metas(Policy::OwnerStats));
+    let mint = create_hooked_mint(&context, &metas).await;
+
+    let receiver = Pubkey::new_unique();
+    let instructions = [
+        initialize_transfer_stats(&hook_program_id(), &mint, &payer, &payer),
+        initialize_owner_stats(&hook_program_id(), &mint, &payer, &payer),
+        initialize_owner_stats(&hook_program_id(), &mint, &receiver, &payer),
+    ];
+    process(&context, &instructions, &[]).await.unwrap();
+
+    let source = create_token_account(&context, &payer, &mint).await;
+    let tracked = create_token_account(&context, &receiver, &mint).await;
+    let untracked = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 100).await.unwrap();
+    for (destination, amount) in [(tracked, 10), (untracked, 20)] {
+        let instruction =
+            hooked_transfer(&context, &source, &mint, &destination, &payer, amount).await;
+        process(&context, &[instruction], &[]).await.unwrap();
+    }
+
+    let (address, _) = find_transfer_stats_address(&mint, &hook_program_id());
+    let slot = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .slot;
+    assert_eq!(
+        get_stats::<TransferStats>(&context, address).await,
+        TransferStats {
+            transfer_count: 2,
+            volume: 30,
+            last_transfer_slot: slot,
+        }
+    );
+
+    let (address, _) = find_owner_stats_address(&mint, &payer, &hook_program_id());
+    let sender = get_stats::<OwnerStats>(&context, address).await;
+    assert_eq!((sender.sent_count, sender.sent_amount), (2, 30));
+    assert_eq!(sender.received_count, 0);
+    let (address, _) = find_owner_stats_address(&mint, &receiver, &hook_program_id());
+    let receiver = get_stats::<OwnerStats>(&context, address).await;
+    assert_eq!((receiver.received_count, receiver.received_amount), (1, 10));
+}
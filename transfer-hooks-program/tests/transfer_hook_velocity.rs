@@ -0,0 +1,102 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{clock::Clock, pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{initialize_velocity_limit, initialize_velocity_tracker, set_velocity_limit},
+        state::{extra_account_metas, Policy},
+    },
+};
+
+/// A hooked mint capping transfers at 100 per minute, managed by the payer,
+/// with the returned owner holding 1000 tokens in the returned account
+async fn setup(context: &ProgramTestContext) -> (Pubkey, Keypair, Pubkey) {
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(context, &extra_account_metas(Policy::VelocityLimit)).await;
+    let instruction =
+        initialize_velocity_limit(&hook_program_id(), &mint, &payer, &payer, &payer, 60, 100);
+    process(context, &[instruction], &[]).await.unwrap();
+
+    let owner = Keypair::new();
+    let source = create_token_account(context, &owner.pubkey(), &mint).await;
+    mint_tokens(context, &mint, &source, 1000).await.unwrap();
+
+    (mint, owner, source)
+}
+
+async fn track(context: &ProgramTestContext, mint: &Pubkey, owner: &Pubkey) {
+    let payer = context.payer.pubkey();
+    let instruction = initialize_velocity_tracker(&hook_program_id(), mint, owner, &payer);
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+async fn advance_clock(context: &ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn transfers_are_capped_per_window() {
+    let context = start().await;
+    let (mint, owner, source) = setup(&context).await;
+    track(&context, &mint, &owner.pubkey()).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+
+    transfer(&context, &mint, &source, &owner, &destination, 60)
+        .await
+        .unwrap();
+    transfer(&context, &mint, &source, &owner, &destination, 40)
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &source, &owner, &destination, 1).await,
+        Err(HookPolicyError::VelocityLimitExceeded as u32)
+    );
+
+    // Amounts keep counting until their bucket falls a window behind
+    advance_clock(&context, 75).await;
+    transfer(&context, &mint, &source, &owner, &destination, 100)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        200
+    );
+
+    let payer = context.payer.pubkey();
+    let instruction = set_velocity_limit(&hook_program_id(), &mint, &payer, 60, 150);
+    process(&context, &[instruction], &[]).await.unwrap();
+    transfer(&context, &mint, &source, &owner, &destination, 50)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn transfers_without_tracker_are_capped_but_not_counted() {
+    let context = start().await;
+    let (mint, owner, source) = setup(&context).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+
+    assert_eq!(
+        transfer(&context, &mint, &source, &owner, &destination, 101).await,
+        Err(HookPolicyError::VelocityLimitExceeded as u32)
+    );
+    transfer(&context, &mint, &source, &owner, &destination, 100)
+        .await
+        .unwrap();
+
+    // Counting starts once the tracker exists
+    track(&context, &mint, &owner.pubkey()).await;
+    transfer(&context, &mint, &source, &owner, &destination, 99)
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &source, &owner, &destination, 2).await,
+        Err(HookPolicyError::VelocityLimitExceeded as u32)
+    );
+}
@@ -0,0 +1,93 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        id as hook_program_id,
+        instruction::{
+            initialize_vesting_schedule, revoke_vesting_schedule, InitializeVestingSchedule,
+        },
+        state::{extra_account_metas, find_vesting_schedule_address, Policy},
+    },
+};
+
+#[tokio::test]
+async fn vesting_locks_grant_until_unlocked_or_revoked() {
+    let context = start().await;
+    let payer = context.payer.pubkey();
+    let mint = create_hooked_mint(&context, &extra_account_metas(Policy::Vesting)).await;
+
+    // 1000 granted over 1000 seconds with a cliff half way through
+    let beneficiary = Keypair::new();
+    let data = InitializeVestingSchedule {
+        authority: payer,
+        total_amount: 1000,
+        start_timestamp: 1000,
+        cliff_timestamp: 1500,
+        end_timestamp: 2000,
+    };
+    let instruction = initialize_vesting_schedule(
+        &hook_program_id(),
+        &mint,
+        &payer,
+        &payer,
+        &beneficiary.pubkey(),
+        data,
+    );
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    let source = create_token_account(&context, &beneficiary.pubkey(), &mint).await;
+    let destination = create_token_account(&context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(&context, &mint, &source, 1000).await.unwrap();
+
+    set_clock(&context, 1499).await;
+    assert_eq!(
+        transfer(&context, &mint, &source, &beneficiary, &destination, 1).await,
+        Err(HookPolicyError::VestingLocked as u32)
+    );
+
+    // Owners without a schedule are not affected
+    let payer_source = create_token_account(&context, &payer, &mint).await;
+    mint_tokens(&context, &mint, &payer_source, 10)
+        .await
+        .unwrap();
+    let instruction =
+        hooked_transfer(&context, &payer_source, &mint, &destination, &payer, 10).await;
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    set_clock(&context, 1500).await;
+    transfer(&context, &mint, &source, &beneficiary, &destination, 500)
+        .await
+        .unwrap();
+    assert_eq!(
+        transfer(&context, &mint, &source, &beneficiary, &destination, 2).await,
+        Err(HookPolicyError::VestingLocked as u32)
+    );
+
+    let instruction = revoke_vesting_schedule(
+        &hook_program_id(),
+        &mint,
+        &beneficiary.pubkey(),
+        &payer,
+        &payer,
+    );
+    process(&context, &[instruction], &[]).await.unwrap();
+    let (address, _) =
+        find_vesting_schedule_address(&mint, &beneficiary.pubkey(), &hook_program_id());
+    assert!(context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+
+    transfer(&context, &mint, &source, &beneficiary, &destination, 3)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        513
+    );
+}
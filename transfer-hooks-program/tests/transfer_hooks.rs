@@ -0,0 +1,125 @@
+mod harness;
+
+use {
+    harness::*,
+    solana_program_test::ProgramTestContext,
+    solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer},
+    spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList},
+    spl_transfer_hook_interface::{
+        error::TransferHookError,
+        get_extra_account_metas_address,
+        instruction::{execute, ExecuteInstruction},
+    },
+    transfer_hooks_program::{id as hook_program_id, instruction::close_extra_account_meta_list},
+};
+
+/// A mint invoking the hook program, with its extra account metas
+/// initialized and the payer holding 100 tokens
+async fn setup(context: &ProgramTestContext) -> (Pubkey, Pubkey, Pubkey) {
+    let extra_account_metas: [ExtraAccountMeta; 1] =
+        [AccountMeta::new_readonly(hook_program_id(), false).into()];
+    let mint = create_hooked_mint(context, &extra_account_metas).await;
+
+    let source = create_token_account(context, &context.payer.pubkey(), &mint).await;
+    let destination = create_token_account(context, &Pubkey::new_unique(), &mint).await;
+    mint_tokens(context, &mint, &source, 100).await.unwrap();
+
+    (mint, source, destination)
+}
+
+#[tokio::test]
+async fn transfer_executes_hook() {
+    let context = start().await;
+    let (mint, source, destination) = setup(&context).await;
+
+    let transfer = hooked_transfer(
+        &context,
+        &source,
+        &mint,
+        &destination,
+        &context.payer.pubkey(),
+        31,
+    )
+    .await;
+    let logs = simulate(&context, std::slice::from_ref(&transfer), &[])
+        .await
+        .unwrap();
+    // Logs written by a natively run program do not reach the log collector,
+    // so check that Token-2022 invoked the hook rather than for its message
+    let invoked = format!("Program {} invoke [2]", hook_program_id());
+    let succeeded = format!("Program {} success", hook_program_id());
+    assert!(logs.contains(&invoked), "{logs:#?}");
+    assert!(logs.contains(&succeeded), "{logs:#?}");
+
+    process(&context, &[transfer], &[]).await.unwrap();
+    assert_eq!(
+        get_token_account(&context, &destination).await.base.amount,
+        31
+    );
+}
+
+#[tokio::test]
+async fn execute_outside_of_transfer_is_rejected() {
+    let context = start().await;
+    let (mint, source, destination) = setup(&context).await;
+    let payer = context.payer.pubkey();
+
+    let address = get_extra_account_metas_address(&mint, &hook_program_id());
+    let mut instruction = execute(
+        &hook_program_id(),
+        &source,
+        &mint,
+        &destination,
+        &payer,
+        &address,
+        31,
+    );
+    let validation_data = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    ExtraAccountMetaList::add_to_instruction::<ExecuteInstruction, _, _>(
+        &mut instruction,
+        |address| account_data(&context, address),
+        &validation_data,
+    )
+    .await
+    .unwrap();
+    let error = process(&context, &[instruction], &[]).await.unwrap_err();
+
+    assert_eq!(
+        custom_error(error),
+        TransferHookError::ProgramCalledOutsideOfTransfer as u32
+    );
+}
+
+#[tokio::test]
+async fn closing_extra_account_metas_returns_rent() {
+    let context = start().await;
+    let (mint, _, _) = setup(&context).await;
+    let address = get_extra_account_metas_address(&mint, &hook_program_id());
+    let rent = context.banks_client.get_balance(address).await.unwrap();
+    let destination = Pubkey::new_unique();
+
+    let instruction = close_extra_account_meta_list(
+        &hook_program_id(),
+        &mint,
+        &context.payer.pubkey(),
+        &destination,
+    );
+    process(&context, &[instruction], &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        rent
+    );
+}
@@ -0,0 +1,100 @@
+//! `amount-limits` subcommands
+
+use {
+    crate::{
+        cli::AmountLimitsCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::{
+        instruction::{initialize_amount_limits, set_amount_limits},
+        state::{find_amount_limits_address, AmountLimits, Policy},
+    },
+};
+
+/// Run an `amount-limits` subcommand
+pub fn run(config: &Config, command: AmountLimitsCommand) -> Result<()> {
+    match command {
+        AmountLimitsCommand::Init {
+            mint,
+            min_amount,
+            max_amount,
+            authority,
+        } => init(config, &mint, min_amount, max_amount, authority),
+        AmountLimitsCommand::Set {
+            mint,
+            min_amount,
+            max_amount,
+        } => set(config, &mint, min_amount, max_amount),
+        AmountLimitsCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(
+    config: &Config,
+    mint: &Pubkey,
+    min_amount: u64,
+    max_amount: u64,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_amount_limits(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &authority.unwrap_or(payer.pubkey()),
+        min_amount,
+        max_amount,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::AmountLimits],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Amount limits: {}",
+        find_amount_limits_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(config: &Config, mint: &Pubkey, min_amount: u64, max_amount: u64) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = set_amount_limits(
+        &transfer_hooks_program::id(),
+        mint,
+        &payer.pubkey(),
+        min_amount,
+        max_amount,
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_amount_limits_address(mint, &transfer_hooks_program::id());
+    let limits = get_policy_account::<AmountLimits>(&client, &address)?;
+
+    println!("Amount limits: {address}");
+    println!("Authority: {}", limits.authority);
+    println!("Min amount: {}", limits.min_amount);
+    println!("Max amount: {}", limits.max_amount);
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! `blocklist` subcommands
+
+use {
+    crate::{
+        cli::BlocklistCommand,
+        policy::{enable_instructions, get_policy_account, read_owners, OWNERS_PER_INSTRUCTION},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    std::path::Path,
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::{
+        instruction::{add_to_blocklist, initialize_blocklist, remove_from_blocklist},
+        state::{find_blocklist_address, Blocklist, Policy},
+    },
+};
+
+/// Run a `blocklist` subcommand
+pub fn run(config: &Config, command: BlocklistCommand) -> Result<()> {
+    match command {
+        BlocklistCommand::Init { mint, authority } => init(config, &mint, authority),
+        BlocklistCommand::Sync { mint, csv, dry_run } => sync(config, &mint, &csv, dry_run),
+        BlocklistCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, authority: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_blocklist(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &authority.unwrap_or(payer.pubkey()),
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Blocklist],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("Blocklist: {}", find_blocklist_address(mint, &program_id).0);
+
+    Ok(())
+}
+
+fn sync(config: &Config, mint: &Pubkey, csv: &Path, dry_run: bool) -> Result<()> {
+    let client = config.client();
+    let program_id = transfer_hooks_program::id();
+    let (address, _) = find_blocklist_address(mint, &program_id);
+
+    let wanted = read_owners(csv)?;
+    let blocklist = get_policy_account::<Blocklist>(&client, &address)?;
+    let added = wanted
+        .keys()
+        .iter()
+        .filter(|owner| !blocklist.owners.contains(owner))
+        .copied()
+        .collect::<Vec<_>>();
+    let removed = blocklist
+        .owners
+        .keys()
+        .iter()
+        .filter(|owner| !wanted.contains(owner))
+        .copied()
+        .collect::<Vec<_>>();
+
+    for owner in &added {
+        println!("+ {owner}");
+    }
+    for owner in &removed {
+        println!("- {owner}");
+    }
+    println!("{} to add, {} to remove", added.len(), removed.len());
+    if dry_run {
+        return Ok(());
+    }
+
+    let payer = config.keypair()?;
+    // Block before unblocking, so a sync that fails partway never leaves an
+    // owner that should be blocked free to transfer
+    for owners in added.chunks(OWNERS_PER_INSTRUCTION) {
+        let instruction = add_to_blocklist(
+            &program_id,
+            mint,
+            &payer.pubkey(),
+            &payer.pubkey(),
+            owners.to_vec(),
+        );
+        let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+        println!("Signature: {signature}");
+    }
+    for owners in removed.chunks(OWNERS_PER_INSTRUCTION) {
+        let instruction =
+            remove_from_blocklist(&program_id, mint, &payer.pubkey(), owners.to_vec());
+        let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+        println!("Signature: {signature}");
+    }
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_blocklist_address(mint, &transfer_hooks_program::id());
+    let blocklist = get_policy_account::<Blocklist>(&client, &address)?;
+
+    println!("Blocklist: {address}");
+    println!("Authority: {}", blocklist.authority);
+    println!("Owners: {}", blocklist.owners.keys().len());
+    for owner in blocklist.owners.keys() {
+        println!("  {owner}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,623 @@
+//! Command line arguments
+
+use {
+    clap::{Parser, Subcommand},
+    solana_sdk::pubkey::Pubkey,
+    std::path::PathBuf,
+    token_extensions_common::config::ConfigArgs,
+    transfer_hooks_program::state::DailyWindow,
+};
+
+/// Transfer a hooked mint, or manage the policies its hook enforces
+#[derive(Debug, Parser)]
+#[command(name = "transfer-hooks", version)]
+pub struct Cli {
+    /// The keypair pays for every transaction and acts as every authority
+    #[command(flatten)]
+    pub config: ConfigArgs,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Admin managing the extra account metas of a mint instead of its mint
+    /// authority
+    HookAdmin {
+        #[command(subcommand)]
+        command: HookAdminCommand,
+    },
+    /// Close the extra account metas of a mint whose transfer hook moved to
+    /// another program, reclaiming their rent
+    Close {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Account receiving the rent [default: the keypair]
+        #[arg(long)]
+        destination: Option<Pubkey>,
+    },
+    /// Owners barred from sending or receiving a mint
+    Blocklist {
+        #[command(subcommand)]
+        command: BlocklistCommand,
+    },
+    /// Cap on the amount each owner may send per window
+    Velocity {
+        #[command(subcommand)]
+        command: VelocityCommand,
+    },
+    /// Bounds on the amount of a single transfer
+    AmountLimits {
+        #[command(subcommand)]
+        command: AmountLimitsCommand,
+    },
+    /// Unlock time and daily windows outside of which transfers are rejected
+    Schedule {
+        #[command(subcommand)]
+        command: ScheduleCommand,
+    },
+    /// Grants that unlock over time and may not be transferred before
+    Vesting {
+        #[command(subcommand)]
+        command: VestingCommand,
+    },
+    /// Transfer counts and volumes kept by the hook
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommand,
+    },
+    /// Royalty paid to the creator on every transfer
+    Royalty {
+        #[command(subcommand)]
+        command: RoyaltyCommand,
+    },
+    /// Memo required before every transfer
+    Memo {
+        #[command(subcommand)]
+        command: MemoCommand,
+    },
+    /// Emergency pause of every transfer of a mint, flipped by its guardian
+    Guardian {
+        #[command(subcommand)]
+        command: GuardianCommand,
+    },
+    /// Attestations owners need to send or receive a mint, and the amounts
+    /// their tiers allow
+    Kyc {
+        #[command(subcommand)]
+        command: KycCommand,
+    },
+    /// Number of token accounts holding a mint, and a cap on it
+    Holders {
+        #[command(subcommand)]
+        command: HoldersCommand,
+    },
+    /// Transfer from the keypair's associated token account, resolving the
+    /// accounts the hook needs and paying any royalty the mint requires, and
+    /// print why the hook rejected it if it did
+    Transfer {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner of the receiving associated token account
+        #[arg(long)]
+        to: Pubkey,
+        /// Amount in base units
+        #[arg(long)]
+        amount: u64,
+        /// Memo to add before the transfer
+        #[arg(long)]
+        memo: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HookAdminCommand {
+    /// Create the hook config of a mint, signed by the mint authority or the
+    /// transfer hook authority. A mint with neither can never get an admin
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Admin allowed to manage the extra account metas [default: the
+        /// keypair]
+        #[arg(long)]
+        admin: Option<Pubkey>,
+    },
+    /// Propose a new admin, who takes over once they accept
+    Propose {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        new_admin: Pubkey,
+    },
+    /// Accept being the admin, signed by the proposed admin
+    Accept {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Print the admin and any pending admin of a mint
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BlocklistCommand {
+    /// Create the blocklist of a mint and add it to the mint's extra account
+    /// metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Authority allowed to change the list [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Add and remove owners so the blocklist matches a CSV file
+    Sync {
+        #[arg(long)]
+        mint: Pubkey,
+        /// File with one owner per line in its first column. Blank lines,
+        /// lines starting with `#` and a header row are skipped.
+        #[arg(long)]
+        csv: PathBuf,
+        /// Print the changes without sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print the blocklist
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VelocityCommand {
+    /// Create the velocity limit of a mint and add it to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Length of a window in seconds
+        #[arg(long)]
+        window_seconds: i64,
+        /// Most an owner may send within a window, in base units
+        #[arg(long)]
+        max_amount: u64,
+        /// Authority allowed to change the limit [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the velocity limit of a mint
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Length of a window in seconds
+        #[arg(long)]
+        window_seconds: i64,
+        /// Most an owner may send within a window, in base units
+        #[arg(long)]
+        max_amount: u64,
+    },
+    /// Create the tracker an owner needs before sending a mint with a
+    /// velocity limit
+    Track {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner to track [default: the keypair]
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Print the velocity limit of a mint and what an owner sent under it
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner whose window to print [default: the keypair]
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AmountLimitsCommand {
+    /// Create the amount limits of a mint and add them to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Smallest amount a transfer may move, in base units
+        #[arg(long)]
+        min_amount: u64,
+        /// Largest amount a transfer may move, in base units
+        #[arg(long)]
+        max_amount: u64,
+        /// Authority allowed to change the limits [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the amount limits of a mint
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Smallest amount a transfer may move, in base units
+        #[arg(long)]
+        min_amount: u64,
+        /// Largest amount a transfer may move, in base units
+        #[arg(long)]
+        max_amount: u64,
+    },
+    /// Print the amount limits of a mint
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ScheduleCommand {
+    /// Create the trading schedule of a mint and add it to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Unix timestamp before which transfers are rejected
+        #[arg(long, default_value_t = 0)]
+        unlock_timestamp: i64,
+        /// Daily window transfers are allowed in, as HH:MM-HH:MM in UTC
+        /// [default: the whole day]
+        #[arg(long = "window", value_parser = parse_daily_window)]
+        daily_windows: Vec<DailyWindow>,
+        /// Source token account the schedule does not apply to, such as the
+        /// mint authority's associated token account
+        #[arg(long = "exempt")]
+        exempt_accounts: Vec<Pubkey>,
+        /// Authority allowed to change the schedule [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the unlock time and daily windows of a mint
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Unix timestamp before which transfers are rejected
+        #[arg(long, default_value_t = 0)]
+        unlock_timestamp: i64,
+        /// Daily window transfers are allowed in, as HH:MM-HH:MM in UTC
+        /// [default: the whole day]
+        #[arg(long = "window", value_parser = parse_daily_window)]
+        daily_windows: Vec<DailyWindow>,
+    },
+    /// Exempt source token accounts from the schedule
+    Exempt {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(required = true)]
+        accounts: Vec<Pubkey>,
+    },
+    /// Subject source token accounts to the schedule again
+    Unexempt {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(required = true)]
+        accounts: Vec<Pubkey>,
+    },
+    /// Print the trading schedule of a mint
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VestingCommand {
+    /// Create the vesting schedule of a beneficiary and add vesting to the
+    /// mint's extra account metas
+    Create {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner whose token accounts must keep the locked amount
+        #[arg(long)]
+        beneficiary: Pubkey,
+        /// Amount granted, in base units
+        #[arg(long)]
+        total_amount: u64,
+        /// Unix timestamp the grant starts accruing at
+        #[arg(long)]
+        start_timestamp: i64,
+        /// Unix timestamp before which nothing unlocks [default: the start]
+        #[arg(long)]
+        cliff_timestamp: Option<i64>,
+        /// Unix timestamp the whole grant is unlocked at
+        #[arg(long)]
+        end_timestamp: i64,
+        /// Authority allowed to revoke the schedule [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Delete the vesting schedule of a beneficiary, unlocking the rest of
+    /// the grant and returning the rent to the keypair
+    Revoke {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        beneficiary: Pubkey,
+    },
+    /// Print the vesting schedule of a beneficiary and how much it locks now
+    Status {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Beneficiary whose schedule to print [default: the keypair]
+        #[arg(long)]
+        beneficiary: Option<Pubkey>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StatsCommand {
+    /// Create the transfer stats of a mint and add them to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Also count transfers in the stats of owners that have them
+        #[arg(long)]
+        per_owner: bool,
+    },
+    /// Create the stats of an owner, counted if the mint keeps per-owner
+    /// stats
+    Track {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Owner to count [default: the keypair]
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Print the transfer stats of a mint and optionally of an owner
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RoyaltyCommand {
+    /// Create the royalty config of a mint and add it to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Account the royalty is paid to
+        #[arg(long)]
+        creator_vault: Pubkey,
+        /// Share of the sale price owed to the creator
+        #[arg(long, value_parser = clap::value_parser!(u16).range(..=10_000))]
+        basis_points: u16,
+        /// Lamports owed on every transfer, including those without a sale
+        #[arg(long, default_value_t = 0)]
+        min_lamports: u64,
+        /// Authority allowed to change the royalty [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the royalty of a mint
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Account the royalty is paid to
+        #[arg(long)]
+        creator_vault: Pubkey,
+        /// Share of the sale price owed to the creator
+        #[arg(long, value_parser = clap::value_parser!(u16).range(..=10_000))]
+        basis_points: u16,
+        /// Lamports owed on every transfer, including those without a sale
+        #[arg(long, default_value_t = 0)]
+        min_lamports: u64,
+    },
+    /// Print the royalty config of a mint
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MemoCommand {
+    /// Create the memo config of a mint and add it to the mint's extra
+    /// account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Most bytes the memo may have, or 0 for no limit
+        #[arg(long, default_value_t = 0)]
+        max_length: u16,
+        /// Pattern the memo must match, where `*` matches any run of
+        /// characters and `?` any single one [default: any memo]
+        #[arg(long, default_value = "")]
+        pattern: String,
+        /// Authority allowed to change the config [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the memo config of a mint
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Most bytes the memo may have, or 0 for no limit
+        #[arg(long, default_value_t = 0)]
+        max_length: u16,
+        /// Pattern the memo must match, where `*` matches any run of
+        /// characters and `?` any single one [default: any memo]
+        #[arg(long, default_value = "")]
+        pattern: String,
+    },
+    /// Print the memo config of a mint
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GuardianCommand {
+    /// Create the unpaused pause state of a mint and add it to the mint's
+    /// extra account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Key allowed to pause and unpause transfers [default: the keypair]
+        #[arg(long)]
+        guardian: Option<Pubkey>,
+    },
+    /// Reject every transfer of a mint but those from exempt accounts,
+    /// signed by the guardian
+    Pause {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Allow transfers of a mint again, signed by the guardian
+    Unpause {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+    /// Let source token accounts, such as a treasury, send while paused
+    Exempt {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(required = true)]
+        accounts: Vec<Pubkey>,
+    },
+    /// Stop source token accounts from sending while paused
+    Unexempt {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(required = true)]
+        accounts: Vec<Pubkey>,
+    },
+    /// Print whether transfers of a mint are paused
+    Status {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KycCommand {
+    /// Create the KYC config of a mint and add it and the owners'
+    /// attestations to the mint's extra account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Key issuing attestations [default: the keypair]
+        #[arg(long)]
+        attester: Option<Pubkey>,
+        /// Largest amount a transfer may move for each tier, starting at tier
+        /// 0, in base units. Higher tiers keep the last limit. [default: no
+        /// limit]
+        #[arg(long = "tier-limit")]
+        tier_limits: Vec<u64>,
+        /// Authority allowed to change the config [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the attester and tier limits of a mint. Attestations issued by
+    /// a replaced attester are no longer accepted.
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Key issuing attestations [default: the current attester]
+        #[arg(long)]
+        attester: Option<Pubkey>,
+        /// Largest amount a transfer may move for each tier, starting at tier
+        /// 0, in base units [default: no limit]
+        #[arg(long = "tier-limit")]
+        tier_limits: Vec<u64>,
+    },
+    /// Attest to owners in batches, or renew their attestations, signed by
+    /// the attester
+    Issue {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Tier the owners were verified at
+        #[arg(long)]
+        tier: u8,
+        /// Unix timestamp the attestations expire at
+        #[arg(long)]
+        expiry_timestamp: i64,
+        /// File with one owner per line in its first column. Blank lines,
+        /// lines starting with `#` and a header row are skipped.
+        #[arg(long, required_unless_present = "owners")]
+        csv: Option<PathBuf>,
+        owners: Vec<Pubkey>,
+    },
+    /// Revoke the attestation of an owner, signed by the attester, who gets
+    /// the rent back
+    Revoke {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        owner: Pubkey,
+    },
+    /// Print the KYC config of a mint and the attestation of an owner
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HoldersCommand {
+    /// Count the token accounts holding a mint, create its holder count and
+    /// add it to the mint's extra account metas
+    Init {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Most token accounts that may hold a balance, or 0 for no cap
+        #[arg(long, default_value_t = 0)]
+        max_holders: u64,
+        /// Authority allowed to change the cap [default: the keypair]
+        #[arg(long)]
+        authority: Option<Pubkey>,
+    },
+    /// Change the cap of a mint, recounting its holders to correct for mints
+    /// and burns the hook does not see
+    Set {
+        #[arg(long)]
+        mint: Pubkey,
+        /// Most token accounts that may hold a balance, or 0 for no cap
+        #[arg(long, default_value_t = 0)]
+        max_holders: u64,
+    },
+    /// Print the holder count of a mint next to the token accounts holding a
+    /// balance
+    Show {
+        #[arg(long)]
+        mint: Pubkey,
+    },
+}
+
+fn parse_daily_window(value: &str) -> Result<DailyWindow, String> {
+    fn parse_time(time: &str) -> Option<u32> {
+        let (hours, minutes) = time.split_once(':')?;
+        let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+        (hours < 24 && minutes < 60).then_some(hours * 3600 + minutes * 60)
+    }
+
+    let window = value
+        .split_once('-')
+        .and_then(|(start, end)| {
+            Some(DailyWindow {
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+            })
+        })
+        .ok_or_else(|| format!("expected HH:MM-HH:MM, found `{value}`"))?;
+    if !window.is_valid() {
+        return Err(format!("`{value}` is empty"));
+    }
+
+    Ok(window)
+}
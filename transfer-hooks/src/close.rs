@@ -0,0 +1,26 @@
+//! `close` subcommand
+
+use {
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::instruction::close_extra_account_meta_list,
+};
+
+/// Close the extra account metas of `mint`, signed by the keypair as the hook
+/// admin or, if the mint has no hook config, as the mint authority
+pub fn run(config: &Config, mint: &Pubkey, destination: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let instruction = close_extra_account_meta_list(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &destination.unwrap_or(payer.pubkey()),
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! Walkthrough run when no subcommand is given
+
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer, system_instruction};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_token_2022::{
+    instruction::mint_to, offchain::create_transfer_checked_instruction_with_extra_metas,
+};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address,
+    instruction::{execute, initialize_extra_account_meta_list, ExecuteInstruction},
+};
+use token_extensions_common::{
+    account::{associated_token_address, create_associated_token_account},
+    mint::MintBuilder,
+    Config, Error, Result,
+};
+use transfer_hooks_program::instruction::with_hook_config;
+
+/// Create a hooked mint, initialize its extra account metas and transfer
+/// between two accounts
+pub async fn run(config: &Config) -> Result<()> {
+    let client = config.client();
+
+    let transfer_hook_program_id = transfer_hooks_program::id();
+    let mint_authority = config.keypair()?;
+    let mint_account = config.signer_or_new("mint")?;
+    let destination = config.signer_or_new("destination")?;
+
+    let decimals = 0u8;
+
+    println!("TRANSFER HOOK PROGRAM: {}", &transfer_hook_program_id);
+    println!("MINT AUTHORITY: {}", mint_authority.pubkey());
+    println!("MINT ACCOUNT: {}", mint_account.pubkey());
+    println!("Destination Keypair: {}", &destination.pubkey());
+    println!("MINT Decimals : {}", decimals);
+
+    let extra_account_metas_address =
+        get_extra_account_metas_address(&mint_account.pubkey(), &transfer_hook_program_id);
+    println!(
+        "Extra Account Metas Address: {}",
+        extra_account_metas_address
+    );
+
+    dbg!(&extra_account_metas_address);
+
+    let mint_builder =
+        MintBuilder::new(mint_authority.pubkey(), decimals).transfer_hook(transfer_hook_program_id);
+    let mut all_instructions = client.create_mint_instructions(
+        &mint_authority.pubkey(),
+        &mint_account.pubkey(),
+        &mint_builder,
+    )?;
+
+    let extra_account_metas: [ExtraAccountMeta; 1] =
+        [AccountMeta::new(transfer_hook_program_id, false).into()];
+
+    let account_size = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+    let required_lamports = client
+        .rpc_client()
+        .get_minimum_balance_for_rent_exemption(account_size)?;
+    // Check if the extra meta account has already been initialized
+    let extra_account_metas_account = client
+        .rpc_client()
+        .get_account(&extra_account_metas_address);
+    if let Ok(account) = &extra_account_metas_account {
+        if account.owner != solana_program::system_program::id() {
+            return Err(Error::AccountAlreadyExists(extra_account_metas_address));
+        }
+    }
+    let current_lamports = extra_account_metas_account.map(|a| a.lamports).unwrap_or(0);
+    let transfer_lamports = required_lamports.saturating_sub(current_lamports);
+
+    if transfer_lamports > 0 {
+        all_instructions.push(system_instruction::transfer(
+            &mint_authority.pubkey(),
+            &extra_account_metas_address,
+            transfer_lamports,
+        ));
+    }
+
+    let init_extra_account_meta_instr = with_hook_config(
+        initialize_extra_account_meta_list(
+            &transfer_hook_program_id,
+            &extra_account_metas_address,
+            &mint_account.pubkey(),
+            &mint_authority.pubkey(),
+            &extra_account_metas,
+        ),
+        &transfer_hook_program_id,
+        &mint_account.pubkey(),
+    );
+
+    all_instructions.push(init_extra_account_meta_instr);
+
+    client.check_request_airdrop(&mint_authority.pubkey(), 2)?;
+
+    dbg!(&client.send_transaction(
+        &all_instructions,
+        &mint_authority.pubkey(),
+        &[&mint_authority, &mint_account],
+    )?);
+
+    let mint_authority_ata =
+        associated_token_address(&mint_authority.pubkey(), &mint_account.pubkey());
+    println!("MINT AUTHORITY ATA: {}", mint_authority_ata);
+
+    let destination_ata = associated_token_address(&destination.pubkey(), &mint_account.pubkey());
+
+    println!("Destination ATA: {}", &destination_ata);
+
+    {
+        let mint_authority_ata_instr = create_associated_token_account(
+            &mint_authority.pubkey(),
+            &mint_authority.pubkey(),
+            &mint_account.pubkey(),
+        );
+        let mint_to_source_instr = mint_to(
+            &spl_token_2022::id(),
+            &mint_account.pubkey(),
+            &mint_authority_ata,
+            &mint_authority.pubkey(),
+            &[&mint_authority.pubkey()],
+            2000,
+        )?;
+
+        dbg!(&client.send_transaction(
+            &[mint_authority_ata_instr, mint_to_source_instr],
+            &mint_authority.pubkey(),
+            &[&mint_authority],
+        )?);
+    }
+
+    {
+        client.check_request_airdrop(&destination.pubkey(), 1)?;
+
+        client.create_associated_token_account(&destination, &mint_account.pubkey())?;
+
+        let mint_to_dest_ata_instr = mint_to(
+            &spl_token_2022::id(),
+            &mint_account.pubkey(),
+            &destination_ata,
+            &mint_authority.pubkey(),
+            &[&mint_authority.pubkey()],
+            1,
+        )?;
+
+        dbg!(&client.send_transaction(
+            &[mint_to_dest_ata_instr],
+            &mint_authority.pubkey(),
+            &[&mint_authority],
+        )?);
+    }
+
+    // Load the validation state data
+    let validate_state_pubkey =
+        get_extra_account_metas_address(&mint_account.pubkey(), &transfer_hook_program_id);
+    dbg!(&validate_state_pubkey);
+    let rpc_client = client.rpc_client();
+    let fetch_account_data_fn = |pubkey: Pubkey| async move {
+        Ok(rpc_client
+            .get_account(&pubkey)
+            .ok()
+            .map(|account| account.data))
+    };
+    let validate_state_data = client.get_account_data(&validate_state_pubkey)?;
+
+    let amount_to_transfer = 31u64;
+
+    // First create an `ExecuteInstruction`
+    let mut execute_instruction = execute(
+        &spl_token_2022::id(),
+        &mint_authority_ata,
+        &mint_account.pubkey(),
+        &destination_ata,
+        &mint_authority.pubkey(),
+        &validate_state_pubkey,
+        amount_to_transfer,
+    );
+
+    // Resolve all additional required accounts for `ExecuteInstruction`
+    ExtraAccountMetaList::add_to_instruction::<ExecuteInstruction, _, _>(
+        &mut execute_instruction,
+        fetch_account_data_fn,
+        &validate_state_data,
+    )
+    .await?;
+
+    let transfer_instr = create_transfer_checked_instruction_with_extra_metas(
+        &spl_token_2022::id(),
+        &mint_authority_ata,
+        &mint_account.pubkey(),
+        &destination_ata,
+        &mint_authority.pubkey(),
+        &[&mint_authority.pubkey()],
+        4,
+        decimals,
+        fetch_account_data_fn,
+    )
+    .await
+    .map_err(Error::AccountResolution)?;
+
+    dbg!(&client.send_transaction(
+        &[transfer_instr],
+        &mint_authority.pubkey(),
+        &[&mint_authority],
+    )?);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//! `guardian` subcommands
+
+use {
+    crate::{
+        cli::GuardianCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::{
+        instruction::{
+            add_pause_exemptions, initialize_pause, pause, remove_pause_exemptions, unpause,
+        },
+        state::{find_pause_address, PauseState, Policy},
+    },
+};
+
+/// Run a `guardian` subcommand
+pub fn run(config: &Config, command: GuardianCommand) -> Result<()> {
+    match command {
+        GuardianCommand::Init { mint, guardian } => init(config, &mint, guardian),
+        GuardianCommand::Pause { mint } => set_paused(config, &mint, true),
+        GuardianCommand::Unpause { mint } => set_paused(config, &mint, false),
+        GuardianCommand::Exempt { mint, accounts } => exempt(config, &mint, accounts, true),
+        GuardianCommand::Unexempt { mint, accounts } => exempt(config, &mint, accounts, false),
+        GuardianCommand::Status { mint } => status(config, &mint),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, guardian: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_pause(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &guardian.unwrap_or(payer.pubkey()),
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Pause],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("Pause state: {}", find_pause_address(mint, &program_id).0);
+
+    Ok(())
+}
+
+fn set_paused(config: &Config, mint: &Pubkey, paused: bool) -> Result<()> {
+    let client = config.client();
+    let guardian = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let instruction = if paused {
+        pause(&program_id, mint, &guardian.pubkey())
+    } else {
+        unpause(&program_id, mint, &guardian.pubkey())
+    };
+    let signature = client.send_transaction(&[instruction], &guardian.pubkey(), &[&guardian])?;
+    println!("Signature: {signature}");
+    println!("Paused: {paused}");
+
+    Ok(())
+}
+
+fn exempt(config: &Config, mint: &Pubkey, accounts: Vec<Pubkey>, exempt: bool) -> Result<()> {
+    let client = config.client();
+    let guardian = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let instruction = if exempt {
+        add_pause_exemptions(
+            &program_id,
+            mint,
+            &guardian.pubkey(),
+            &guardian.pubkey(),
+            accounts,
+        )
+    } else {
+        remove_pause_exemptions(&program_id, mint, &guardian.pubkey(), accounts)
+    };
+    let signature = client.send_transaction(&[instruction], &guardian.pubkey(), &[&guardian])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn status(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_pause_address(mint, &transfer_hooks_program::id());
+    let pause = get_policy_account::<PauseState>(&client, &address)?;
+
+    println!("Pause state: {address}");
+    println!("Guardian: {}", pause.guardian);
+    println!("Paused: {}", pause.paused);
+    println!("Exempt accounts: {}", pause.exempt_accounts.keys().len());
+    for account in pause.exempt_accounts.keys() {
+        println!("  {account}");
+    }
+
+    Ok(())
+}
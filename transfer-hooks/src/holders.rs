@@ -0,0 +1,121 @@
+//! `holders` subcommands
+
+use {
+    crate::{
+        cli::HoldersCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::{extension::StateWithExtensions, state::Account},
+    token_extensions_common::{ClientContext, Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{
+            initialize_holder_count, set_holder_count, InitializeHolderCount, SetHolderCount,
+        },
+        state::{find_holder_count_address, HolderCount, Policy},
+    },
+};
+
+/// Run a `holders` subcommand
+pub fn run(config: &Config, command: HoldersCommand) -> Result<()> {
+    match command {
+        HoldersCommand::Init {
+            mint,
+            max_holders,
+            authority,
+        } => init(config, &mint, max_holders, authority),
+        HoldersCommand::Set { mint, max_holders } => set(config, &mint, max_holders),
+        HoldersCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, max_holders: u64, authority: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let holder_count = count_holders(&client, mint)?;
+    let data = InitializeHolderCount {
+        authority: authority.unwrap_or(payer.pubkey()),
+        holder_count,
+        max_holders,
+    };
+    let mut instructions = vec![initialize_holder_count(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        data,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::HolderCount],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Holder count: {}",
+        find_holder_count_address(mint, &program_id).0
+    );
+    println!("Holders: {holder_count}");
+
+    Ok(())
+}
+
+fn set(config: &Config, mint: &Pubkey, max_holders: u64) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let holder_count = count_holders(&client, mint)?;
+    let data = SetHolderCount {
+        holder_count,
+        max_holders,
+    };
+    let instruction = set_holder_count(&transfer_hooks_program::id(), mint, &payer.pubkey(), data);
+
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("Holders: {holder_count}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_holder_count_address(mint, &transfer_hooks_program::id());
+    let count = get_policy_account::<HolderCount>(&client, &address)?;
+
+    println!("Holder count: {address}");
+    println!("Authority: {}", count.authority);
+    println!("Holders: {}", count.holder_count);
+    if count.max_holders == 0 {
+        println!("Max holders: none");
+    } else {
+        println!("Max holders: {}", count.max_holders);
+    }
+    println!(
+        "Token accounts with a balance: {}",
+        count_holders(&client, mint)?
+    );
+
+    Ok(())
+}
+
+/// Number of token accounts of `mint` holding a balance, which mints and
+/// burns can take away from the count the hook keeps
+fn count_holders(client: &ClientContext, mint: &Pubkey) -> Result<u64> {
+    let mut holders = 0;
+    for (address, account) in client.get_token_accounts(mint)? {
+        let token_account = StateWithExtensions::<Account>::unpack(&account.data)
+            .map_err(Error::unpack(address))?;
+        if token_account.base.amount > 0 {
+            holders += 1;
+        }
+    }
+
+    Ok(holders)
+}
@@ -0,0 +1,85 @@
+//! `hook-admin` subcommands
+
+use {
+    crate::{cli::HookAdminCommand, policy::get_policy_account},
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::{
+        instruction::{accept_hook_admin, initialize_hook_config, propose_hook_admin},
+        state::{find_hook_config_address, HookConfig},
+    },
+};
+
+/// Run a `hook-admin` subcommand
+pub fn run(config: &Config, command: HookAdminCommand) -> Result<()> {
+    match command {
+        HookAdminCommand::Init { mint, admin } => init(config, &mint, admin),
+        HookAdminCommand::Propose { mint, new_admin } => propose(config, &mint, &new_admin),
+        HookAdminCommand::Accept { mint } => accept(config, &mint),
+        HookAdminCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, admin: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let instruction = initialize_hook_config(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &admin.unwrap_or(payer.pubkey()),
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Hook config: {}",
+        find_hook_config_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn propose(config: &Config, mint: &Pubkey, new_admin: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = propose_hook_admin(
+        &transfer_hooks_program::id(),
+        mint,
+        &payer.pubkey(),
+        new_admin,
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("{new_admin} becomes the admin once they run `hook-admin accept`");
+
+    Ok(())
+}
+
+fn accept(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = accept_hook_admin(&transfer_hooks_program::id(), mint, &payer.pubkey());
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_hook_config_address(mint, &transfer_hooks_program::id());
+    let hook_config = get_policy_account::<HookConfig>(&client, &address)?;
+
+    println!("Hook config: {address}");
+    println!("Admin: {}", hook_config.admin);
+    if hook_config.pending_admin != Pubkey::default() {
+        println!("Pending admin: {}", hook_config.pending_admin);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! `kyc` subcommands
+
+use {
+    crate::{
+        cli::KycCommand,
+        policy::{enable_instructions, get_policy_account, read_owners},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    std::path::PathBuf,
+    token_extensions_common::{Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{
+            initialize_kyc_config, issue_attestation, revoke_attestation, set_kyc_config,
+            InitializeKycConfig, IssueAttestation, SetKycConfig,
+        },
+        state::{
+            find_attestation_address, find_kyc_config_address, Attestation, KycConfig, Policy,
+        },
+    },
+};
+
+/// Most attestations issued by a single transaction, so it stays under the
+/// size limit
+const ATTESTATIONS_PER_TRANSACTION: usize = 8;
+
+/// Run a `kyc` subcommand
+pub fn run(config: &Config, command: KycCommand) -> Result<()> {
+    match command {
+        KycCommand::Init {
+            mint,
+            attester,
+            tier_limits,
+            authority,
+        } => init(config, &mint, attester, tier_limits, authority),
+        KycCommand::Set {
+            mint,
+            attester,
+            tier_limits,
+        } => set(config, &mint, attester, tier_limits),
+        KycCommand::Issue {
+            mint,
+            tier,
+            expiry_timestamp,
+            csv,
+            owners,
+        } => issue(config, &mint, tier, expiry_timestamp, csv, owners),
+        KycCommand::Revoke { mint, owner } => revoke(config, &mint, &owner),
+        KycCommand::Show { mint, owner } => show(config, &mint, owner),
+    }
+}
+
+fn init(
+    config: &Config,
+    mint: &Pubkey,
+    attester: Option<Pubkey>,
+    tier_limits: Vec<u64>,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let data = InitializeKycConfig {
+        authority: authority.unwrap_or(payer.pubkey()),
+        attester: attester.unwrap_or(payer.pubkey()),
+        tier_limits,
+    };
+    let mut instructions = vec![initialize_kyc_config(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        data,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Kyc],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "KYC config: {}",
+        find_kyc_config_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(
+    config: &Config,
+    mint: &Pubkey,
+    attester: Option<Pubkey>,
+    tier_limits: Vec<u64>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let attester = match attester {
+        Some(attester) => attester,
+        None => {
+            let (address, _) = find_kyc_config_address(mint, &program_id);
+            get_policy_account::<KycConfig>(&client, &address)?.attester
+        }
+    };
+    let data = SetKycConfig {
+        attester,
+        tier_limits,
+    };
+    let instruction = set_kyc_config(&program_id, mint, &payer.pubkey(), &payer.pubkey(), data);
+
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn issue(
+    config: &Config,
+    mint: &Pubkey,
+    tier: u8,
+    expiry_timestamp: i64,
+    csv: Option<PathBuf>,
+    mut owners: Vec<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let attester = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    if let Some(csv) = csv {
+        owners.extend(read_owners(&csv)?.keys());
+    }
+    owners.sort();
+    owners.dedup();
+    if owners.is_empty() {
+        return Err(Error::InvalidInput("no owners to attest to".to_string()));
+    }
+
+    for owners in owners.chunks(ATTESTATIONS_PER_TRANSACTION) {
+        let instructions = owners
+            .iter()
+            .map(|owner| {
+                let data = IssueAttestation {
+                    tier,
+                    expiry_timestamp,
+                };
+                issue_attestation(
+                    &program_id,
+                    mint,
+                    &attester.pubkey(),
+                    owner,
+                    &attester.pubkey(),
+                    data,
+                )
+            })
+            .collect::<Vec<_>>();
+        let signature = client.send_transaction(&instructions, &attester.pubkey(), &[&attester])?;
+        println!("Signature: {signature}");
+        for owner in owners {
+            println!("  {owner}");
+        }
+    }
+
+    Ok(())
+}
+
+fn revoke(config: &Config, mint: &Pubkey, owner: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let attester = config.keypair()?;
+
+    let instruction = revoke_attestation(
+        &transfer_hooks_program::id(),
+        mint,
+        owner,
+        &attester.pubkey(),
+        &attester.pubkey(),
+    );
+    let signature = client.send_transaction(&[instruction], &attester.pubkey(), &[&attester])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let program_id = transfer_hooks_program::id();
+
+    let (address, _) = find_kyc_config_address(mint, &program_id);
+    let kyc = get_policy_account::<KycConfig>(&client, &address)?;
+    println!("KYC config: {address}");
+    println!("Authority: {}", kyc.authority);
+    println!("Attester: {}", kyc.attester);
+    println!("Tier limits: {}", kyc.tier_limits.len());
+    for (tier, limit) in kyc.tier_limits.iter().enumerate() {
+        println!("  Tier {tier}: {limit}");
+    }
+
+    if let Some(owner) = owner {
+        let (address, _) = find_attestation_address(mint, &owner, &program_id);
+        let attestation = get_policy_account::<Attestation>(&client, &address)?;
+        println!("Attestation of {owner}: {address}");
+        println!("Attester: {}", attestation.attester);
+        println!("Tier: {}", attestation.tier);
+        println!("Expiry timestamp: {}", attestation.expiry_timestamp);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//! `memo` subcommands
+
+use {
+    crate::{
+        cli::MemoCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Result},
+    transfer_hooks_program::{
+        instruction::{
+            initialize_memo_config, set_memo_config, InitializeMemoConfig, SetMemoConfig,
+        },
+        state::{find_memo_config_address, MemoConfig, Policy},
+    },
+};
+
+/// Run a `memo` subcommand
+pub fn run(config: &Config, command: MemoCommand) -> Result<()> {
+    match command {
+        MemoCommand::Init {
+            mint,
+            max_length,
+            pattern,
+            authority,
+        } => init(config, &mint, max_length, pattern, authority),
+        MemoCommand::Set {
+            mint,
+            max_length,
+            pattern,
+        } => set(config, &mint, max_length, pattern),
+        MemoCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(
+    config: &Config,
+    mint: &Pubkey,
+    max_length: u16,
+    pattern: String,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let data = InitializeMemoConfig {
+        authority: authority.unwrap_or(payer.pubkey()),
+        max_length,
+        pattern,
+    };
+    let mut instructions = vec![initialize_memo_config(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        data,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Memo],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Memo config: {}",
+        find_memo_config_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(config: &Config, mint: &Pubkey, max_length: u16, pattern: String) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = set_memo_config(
+        &transfer_hooks_program::id(),
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        SetMemoConfig {
+            max_length,
+            pattern,
+        },
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_memo_config_address(mint, &transfer_hooks_program::id());
+    let memo_config = get_policy_account::<MemoConfig>(&client, &address)?;
+
+    println!("Memo config: {address}");
+    println!("Authority: {}", memo_config.authority);
+    match memo_config.max_length {
+        0 => println!("Max length: none"),
+        max_length => println!("Max length: {max_length} bytes"),
+    }
+    match memo_config.pattern.as_str() {
+        "" => println!("Pattern: any memo"),
+        pattern => println!("Pattern: {pattern}"),
+    }
+
+    Ok(())
+}
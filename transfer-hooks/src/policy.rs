@@ -0,0 +1,131 @@
+//! Helpers shared by the policy commands
+
+use {
+    num_traits::FromPrimitive,
+    solana_sdk::{
+        instruction::{Instruction, InstructionError},
+        pubkey::Pubkey,
+        system_instruction,
+    },
+    spl_tlv_account_resolution::state::ExtraAccountMetaList,
+    spl_transfer_hook_interface::{
+        get_extra_account_metas_address,
+        instruction::{update_extra_account_meta_list, ExecuteInstruction},
+    },
+    spl_type_length_value::state::TlvStateBorrowed,
+    std::{fs, path::Path},
+    token_extensions_common::{ClientContext, Error, Result},
+    transfer_hooks_program::{
+        error::HookPolicyError,
+        instruction::with_hook_config,
+        state::{extra_account_metas, Policy, PolicyAccount, SortedPubkeys},
+    },
+};
+
+/// Most owners added or removed by a single instruction, so the transaction
+/// stays under the size limit
+pub const OWNERS_PER_INSTRUCTION: usize = 25;
+
+/// Instructions adding the accounts of `policies` to the extra account metas
+/// of `mint`, topping up the rent of the larger list. There are none if the
+/// policies are already enforced.
+///
+/// `authority` signs as the hook admin, or as the mint authority if the mint
+/// has no hook config.
+pub fn enable_instructions(
+    client: &ClientContext,
+    mint: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    policies: &[Policy],
+) -> Result<Vec<Instruction>> {
+    let program_id = transfer_hooks_program::id();
+    let address = get_extra_account_metas_address(mint, &program_id);
+    let account = client.get_account(&address)?;
+
+    let state = TlvStateBorrowed::unpack(&account.data).map_err(Error::unpack(address))?;
+    let mut current = ExtraAccountMetaList::unpack_with_tlv_state::<ExecuteInstruction>(&state)
+        .map_err(Error::unpack(address))?
+        .data()
+        .to_vec();
+    let original_len = current.len();
+    for meta in policies
+        .iter()
+        .flat_map(|policy| extra_account_metas(*policy))
+    {
+        if !current.contains(&meta) {
+            current.push(meta);
+        }
+    }
+    if current.len() == original_len {
+        return Ok(Vec::new());
+    }
+
+    let mut instructions = Vec::new();
+    let lamports = client
+        .rpc_client()
+        .get_minimum_balance_for_rent_exemption(ExtraAccountMetaList::size_of(current.len())?)?;
+    if lamports > account.lamports {
+        instructions.push(system_instruction::transfer(
+            payer,
+            &address,
+            lamports - account.lamports,
+        ));
+    }
+    let update = update_extra_account_meta_list(&program_id, &address, mint, authority, &current);
+    instructions.push(with_hook_config(update, &program_id, mint));
+
+    Ok(instructions)
+}
+
+/// Fetch and deserialize the policy account at `address`
+pub fn get_policy_account<T: PolicyAccount>(client: &ClientContext, address: &Pubkey) -> Result<T> {
+    let data = client.get_account_data(address)?;
+
+    T::unpack(&data).map_err(Error::unpack(*address))
+}
+
+/// The policy error behind `error`, if the hook program rejected the
+/// transaction with one of its own codes
+pub fn policy_error(error: &Error) -> Option<HookPolicyError> {
+    let Error::Program(failure) = error else {
+        return None;
+    };
+    if failure.program_id != Some(transfer_hooks_program::id()) {
+        return None;
+    }
+
+    match failure.error {
+        InstructionError::Custom(code) => HookPolicyError::from_u32(code),
+        _ => None,
+    }
+}
+
+/// Owners in the first column of the CSV file at `path`
+pub fn read_owners(path: &Path) -> Result<SortedPubkeys> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| Error::InvalidInput(format!("{}: {error}", path.display())))?;
+
+    let mut owners = Vec::new();
+    let lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+    for (position, (number, line)) in lines.enumerate() {
+        let field = line.split(',').next().unwrap_or_default().trim();
+        match field.parse::<Pubkey>() {
+            Ok(owner) => owners.push(owner),
+            // The first row may name the columns
+            Err(_) if position == 0 => {}
+            Err(_) => {
+                return Err(Error::InvalidInput(format!(
+                    "{}:{number}: `{field}` is not a public key",
+                    path.display()
+                )))
+            }
+        }
+    }
+
+    Ok(owners.into_iter().collect())
+}
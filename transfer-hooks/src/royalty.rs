@@ -0,0 +1,125 @@
+//! `royalty` subcommands
+
+use {
+    crate::{
+        cli::RoyaltyCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer, system_instruction},
+    token_extensions_common::{ClientContext, Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{initialize_royalty, set_royalty, InitializeRoyalty, SetRoyalty},
+        state::{find_royalty_config_address, Policy, RoyaltyConfig},
+    },
+};
+
+/// Run a `royalty` subcommand
+pub fn run(config: &Config, command: RoyaltyCommand) -> Result<()> {
+    match command {
+        RoyaltyCommand::Init {
+            mint,
+            creator_vault,
+            basis_points,
+            min_lamports,
+            authority,
+        } => {
+            let payer = config.keypair()?.pubkey();
+            let data = InitializeRoyalty {
+                authority: authority.unwrap_or(payer),
+                creator_vault,
+                basis_points,
+                min_lamports,
+            };
+            init(config, &mint, data)
+        }
+        RoyaltyCommand::Set {
+            mint,
+            creator_vault,
+            basis_points,
+            min_lamports,
+        } => {
+            let data = SetRoyalty {
+                creator_vault,
+                basis_points,
+                min_lamports,
+            };
+            set(config, &mint, data)
+        }
+        RoyaltyCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, data: InitializeRoyalty) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_royalty(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        data,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Royalty],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Royalty config: {}",
+        find_royalty_config_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(config: &Config, mint: &Pubkey, data: SetRoyalty) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = set_royalty(&transfer_hooks_program::id(), mint, &payer.pubkey(), data);
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_royalty_config_address(mint, &transfer_hooks_program::id());
+    let royalty = get_policy_account::<RoyaltyConfig>(&client, &address)?;
+
+    println!("Royalty config: {address}");
+    println!("Authority: {}", royalty.authority);
+    println!("Creator vault: {}", royalty.creator_vault);
+    println!("Basis points: {}", royalty.basis_points);
+    println!("Min lamports: {}", royalty.min_lamports);
+
+    Ok(())
+}
+
+/// Instruction paying the royalty `payer` owes on a transfer of `mint`
+/// without a sale, if the mint has a royalty config. It has to come right
+/// before the transfer for the hook to count it.
+pub fn payment_instruction(
+    client: &ClientContext,
+    mint: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Option<Instruction>> {
+    let (address, _) = find_royalty_config_address(mint, &transfer_hooks_program::id());
+    let royalty = match get_policy_account::<RoyaltyConfig>(client, &address) {
+        Ok(royalty) => royalty,
+        Err(Error::AccountNotFound(_)) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    let lamports = royalty.owed(0);
+    Ok((lamports > 0)
+        .then(|| system_instruction::transfer(payer, &royalty.creator_vault, lamports)))
+}
@@ -0,0 +1,183 @@
+//! `schedule` subcommands
+
+use {
+    crate::{
+        cli::ScheduleCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{
+            add_schedule_exemptions, initialize_trading_schedule, remove_schedule_exemptions,
+            set_trading_schedule,
+        },
+        state::{find_trading_schedule_address, DailyWindow, Policy, TradingSchedule},
+    },
+};
+
+/// Run a `schedule` subcommand
+pub fn run(config: &Config, command: ScheduleCommand) -> Result<()> {
+    match command {
+        ScheduleCommand::Init {
+            mint,
+            unlock_timestamp,
+            daily_windows,
+            exempt_accounts,
+            authority,
+        } => init(
+            config,
+            &mint,
+            unlock_timestamp,
+            daily_windows,
+            exempt_accounts,
+            authority,
+        ),
+        ScheduleCommand::Set {
+            mint,
+            unlock_timestamp,
+            daily_windows,
+        } => set(config, &mint, unlock_timestamp, daily_windows),
+        ScheduleCommand::Exempt { mint, accounts } => exempt(config, &mint, accounts, true),
+        ScheduleCommand::Unexempt { mint, accounts } => exempt(config, &mint, accounts, false),
+        ScheduleCommand::Show { mint } => show(config, &mint),
+    }
+}
+
+fn init(
+    config: &Config,
+    mint: &Pubkey,
+    unlock_timestamp: i64,
+    daily_windows: Vec<DailyWindow>,
+    exempt_accounts: Vec<Pubkey>,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+    let authority = authority.unwrap_or(payer.pubkey());
+    // Exemptions are added by the authority, signing with the keypair
+    if !exempt_accounts.is_empty() && authority != payer.pubkey() {
+        return Err(Error::InvalidInput(
+            "--exempt needs the keypair to be the authority, run `schedule exempt` as the \
+             authority instead"
+                .into(),
+        ));
+    }
+
+    let mut instructions = vec![initialize_trading_schedule(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &authority,
+        unlock_timestamp,
+        daily_windows,
+    )];
+    if !exempt_accounts.is_empty() {
+        instructions.push(add_schedule_exemptions(
+            &program_id,
+            mint,
+            &authority,
+            &payer.pubkey(),
+            exempt_accounts,
+        ));
+    }
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::TradingSchedule],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Trading schedule: {}",
+        find_trading_schedule_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(
+    config: &Config,
+    mint: &Pubkey,
+    unlock_timestamp: i64,
+    daily_windows: Vec<DailyWindow>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = set_trading_schedule(
+        &transfer_hooks_program::id(),
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        unlock_timestamp,
+        daily_windows,
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn exempt(config: &Config, mint: &Pubkey, accounts: Vec<Pubkey>, exempt: bool) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let instruction = if exempt {
+        add_schedule_exemptions(
+            &program_id,
+            mint,
+            &payer.pubkey(),
+            &payer.pubkey(),
+            accounts,
+        )
+    } else {
+        remove_schedule_exemptions(&program_id, mint, &payer.pubkey(), accounts)
+    };
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let (address, _) = find_trading_schedule_address(mint, &transfer_hooks_program::id());
+    let schedule = get_policy_account::<TradingSchedule>(&client, &address)?;
+
+    println!("Trading schedule: {address}");
+    println!("Authority: {}", schedule.authority);
+    println!("Unlock timestamp: {}", schedule.unlock_timestamp);
+    if schedule.daily_windows.is_empty() {
+        println!("Daily windows: all day");
+    } else {
+        println!("Daily windows (UTC):");
+        for window in &schedule.daily_windows {
+            println!(
+                "  {}-{}",
+                format_time(window.start),
+                format_time(window.end)
+            );
+        }
+    }
+    println!("Exempt accounts: {}", schedule.exempt_accounts.keys().len());
+    for account in schedule.exempt_accounts.keys() {
+        println!("  {account}");
+    }
+
+    Ok(())
+}
+
+fn format_time(second_of_day: u32) -> String {
+    format!(
+        "{:02}:{:02}",
+        second_of_day / 3600,
+        second_of_day % 3600 / 60
+    )
+}
@@ -0,0 +1,106 @@
+//! `stats` subcommands
+
+use {
+    crate::{
+        cli::StatsCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{initialize_owner_stats, initialize_transfer_stats},
+        state::{
+            find_owner_stats_address, find_transfer_stats_address, OwnerStats, Policy,
+            TransferStats,
+        },
+    },
+};
+
+/// Run a `stats` subcommand
+pub fn run(config: &Config, command: StatsCommand) -> Result<()> {
+    match command {
+        StatsCommand::Init { mint, per_owner } => init(config, &mint, per_owner),
+        StatsCommand::Track { mint, owner } => track(config, &mint, owner),
+        StatsCommand::Show { mint, owner } => show(config, &mint, owner),
+    }
+}
+
+fn init(config: &Config, mint: &Pubkey, per_owner: bool) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let policies = if per_owner {
+        vec![Policy::TransferStats, Policy::OwnerStats]
+    } else {
+        vec![Policy::TransferStats]
+    };
+    let mut instructions = vec![initialize_transfer_stats(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &policies,
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Transfer stats: {}",
+        find_transfer_stats_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn track(config: &Config, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+    let owner = owner.unwrap_or(payer.pubkey());
+
+    let (address, _) = find_owner_stats_address(mint, &owner, &program_id);
+    if client.get_account(&address).is_ok() {
+        return Err(Error::AccountAlreadyExists(address));
+    }
+    let instruction = initialize_owner_stats(&program_id, mint, &owner, &payer.pubkey());
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("Owner stats: {address}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let program_id = transfer_hooks_program::id();
+
+    let (address, _) = find_transfer_stats_address(mint, &program_id);
+    let stats = get_policy_account::<TransferStats>(&client, &address)?;
+    println!("Transfer stats: {address}");
+    println!("Transfers: {}", stats.transfer_count);
+    println!("Volume: {}", stats.volume);
+    println!("Last transfer slot: {}", stats.last_transfer_slot);
+
+    if let Some(owner) = owner {
+        let (address, _) = find_owner_stats_address(mint, &owner, &program_id);
+        let stats = get_policy_account::<OwnerStats>(&client, &address)?;
+        println!("Stats of {owner}: {address}");
+        println!(
+            "Sent: {} transfers, {}",
+            stats.sent_count, stats.sent_amount
+        );
+        println!(
+            "Received: {} transfers, {}",
+            stats.received_count, stats.received_amount
+        );
+    }
+
+    Ok(())
+}
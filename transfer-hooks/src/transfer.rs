@@ -0,0 +1,74 @@
+//! `transfer` subcommand
+
+use {
+    crate::{royalty, velocity},
+    solana_sdk::{pubkey::Pubkey, signer::Signer},
+    spl_token_2022::{extension::StateWithExtensions, instruction::transfer_checked, state::Mint},
+    token_extensions_common::{account::associated_token_address, Config, Error, Result},
+};
+
+/// Transfer `amount` of `mint` from the keypair's associated token account to
+/// the one of `to`, after `memo` if given, paying the royalty right before
+/// if the mint requires one and creating the keypair's velocity tracker if
+/// the mint needs one and it is missing
+pub async fn run(
+    config: &Config,
+    mint: &Pubkey,
+    to: &Pubkey,
+    amount: u64,
+    memo: Option<&str>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let mint_data = client.get_account_data(mint)?;
+    let decimals = StateWithExtensions::<Mint>::unpack(&mint_data)
+        .map_err(Error::unpack(*mint))?
+        .base
+        .decimals;
+    let source = associated_token_address(&payer.pubkey(), mint);
+    let destination = associated_token_address(to, mint);
+
+    let mut instruction = transfer_checked(
+        &spl_token_2022::id(),
+        &source,
+        mint,
+        &destination,
+        &payer.pubkey(),
+        &[],
+        amount,
+        decimals,
+    )?;
+    client
+        .add_transfer_hook_accounts(
+            &mut instruction,
+            &source,
+            mint,
+            &destination,
+            &payer.pubkey(),
+            amount,
+        )
+        .await?;
+
+    let mut instructions = Vec::from_iter(velocity::tracker_instruction(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+    )?);
+    if let Some(memo) = memo {
+        instructions.push(spl_memo::build_memo(memo.as_bytes(), &[&payer.pubkey()]));
+    }
+    // The hook only counts payments right before the transfer
+    instructions.extend(royalty::payment_instruction(
+        &client,
+        mint,
+        &payer.pubkey(),
+    )?);
+    instructions.push(instruction);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
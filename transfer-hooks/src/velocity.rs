@@ -0,0 +1,168 @@
+//! `velocity` subcommands
+
+use {
+    crate::{
+        cli::VelocityCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey, signer::Signer},
+    token_extensions_common::{ClientContext, Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{initialize_velocity_limit, initialize_velocity_tracker, set_velocity_limit},
+        state::{
+            find_velocity_limit_address, find_velocity_tracker_address, Policy, VelocityLimit,
+            VelocityTracker,
+        },
+    },
+};
+
+/// Run a `velocity` subcommand
+pub fn run(config: &Config, command: VelocityCommand) -> Result<()> {
+    match command {
+        VelocityCommand::Init {
+            mint,
+            window_seconds,
+            max_amount,
+            authority,
+        } => init(config, &mint, window_seconds, max_amount, authority),
+        VelocityCommand::Set {
+            mint,
+            window_seconds,
+            max_amount,
+        } => set(config, &mint, window_seconds, max_amount),
+        VelocityCommand::Track { mint, owner } => track(config, &mint, owner),
+        VelocityCommand::Show { mint, owner } => show(config, &mint, owner),
+    }
+}
+
+/// Instruction creating the velocity tracker `owner` needs to send `mint`,
+/// paid for by `payer`, if the mint has a velocity limit and the owner has no
+/// tracker yet
+pub fn tracker_instruction(
+    client: &ClientContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    payer: &Pubkey,
+) -> Result<Option<Instruction>> {
+    let program_id = transfer_hooks_program::id();
+    let (limit_address, _) = find_velocity_limit_address(mint, &program_id);
+    let (tracker_address, _) = find_velocity_tracker_address(mint, owner, &program_id);
+    match client.get_account(&limit_address) {
+        Ok(_) => {}
+        Err(Error::AccountNotFound(_)) => return Ok(None),
+        Err(error) => return Err(error),
+    }
+    match client.get_account(&tracker_address) {
+        Ok(_) => return Ok(None),
+        Err(Error::AccountNotFound(_)) => {}
+        Err(error) => return Err(error),
+    }
+
+    Ok(Some(initialize_velocity_tracker(
+        &program_id,
+        mint,
+        owner,
+        payer,
+    )))
+}
+
+fn init(
+    config: &Config,
+    mint: &Pubkey,
+    window_seconds: i64,
+    max_amount: u64,
+    authority: Option<Pubkey>,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_velocity_limit(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &authority.unwrap_or(payer.pubkey()),
+        window_seconds,
+        max_amount,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::VelocityLimit],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Velocity limit: {}",
+        find_velocity_limit_address(mint, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn set(config: &Config, mint: &Pubkey, window_seconds: i64, max_amount: u64) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = set_velocity_limit(
+        &transfer_hooks_program::id(),
+        mint,
+        &payer.pubkey(),
+        window_seconds,
+        max_amount,
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn track(config: &Config, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+    let owner = owner.unwrap_or(payer.pubkey());
+
+    let (address, _) = find_velocity_tracker_address(mint, &owner, &program_id);
+    if client.get_account(&address).is_ok() {
+        return Err(Error::AccountAlreadyExists(address));
+    }
+    let instruction = initialize_velocity_tracker(&program_id, mint, &owner, &payer.pubkey());
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!("Velocity tracker: {address}");
+
+    Ok(())
+}
+
+fn show(config: &Config, mint: &Pubkey, owner: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let program_id = transfer_hooks_program::id();
+    let owner = match owner {
+        Some(owner) => owner,
+        None => config.keypair()?.pubkey(),
+    };
+
+    let (address, _) = find_velocity_limit_address(mint, &program_id);
+    let limit = get_policy_account::<VelocityLimit>(&client, &address)?;
+    println!("Velocity limit: {address}");
+    println!("Authority: {}", limit.authority);
+    println!("Window: {} seconds", limit.window_seconds);
+    println!("Max amount: {}", limit.max_amount);
+
+    let (address, _) = find_velocity_tracker_address(mint, &owner, &program_id);
+    let tracker = get_policy_account::<VelocityTracker>(&client, &address)?;
+    println!("Velocity tracker of {owner}: {address}");
+    println!("Newest bucket start: {}", tracker.bucket_start);
+    println!(
+        "Bucket length: {} seconds",
+        VelocityTracker::bucket_seconds(&limit)
+    );
+    println!("Transferred: {}", tracker.transferred());
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! `vesting` subcommands
+
+use {
+    crate::{
+        cli::VestingCommand,
+        policy::{enable_instructions, get_policy_account},
+    },
+    solana_sdk::{
+        account::from_account, clock::Clock, program_error::ProgramError, pubkey::Pubkey,
+        signer::Signer, sysvar,
+    },
+    token_extensions_common::{ClientContext, Config, Error, Result},
+    transfer_hooks_program::{
+        instruction::{
+            initialize_vesting_schedule, revoke_vesting_schedule, InitializeVestingSchedule,
+        },
+        state::{find_vesting_schedule_address, Policy, VestingSchedule},
+    },
+};
+
+/// Run a `vesting` subcommand
+pub fn run(config: &Config, command: VestingCommand) -> Result<()> {
+    match command {
+        VestingCommand::Create {
+            mint,
+            beneficiary,
+            total_amount,
+            start_timestamp,
+            cliff_timestamp,
+            end_timestamp,
+            authority,
+        } => {
+            let payer = config.keypair()?.pubkey();
+            let data = InitializeVestingSchedule {
+                authority: authority.unwrap_or(payer),
+                total_amount,
+                start_timestamp,
+                cliff_timestamp: cliff_timestamp.unwrap_or(start_timestamp),
+                end_timestamp,
+            };
+            create(config, &mint, &beneficiary, data)
+        }
+        VestingCommand::Revoke { mint, beneficiary } => revoke(config, &mint, &beneficiary),
+        VestingCommand::Status { mint, beneficiary } => status(config, &mint, beneficiary),
+    }
+}
+
+fn create(
+    config: &Config,
+    mint: &Pubkey,
+    beneficiary: &Pubkey,
+    data: InitializeVestingSchedule,
+) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+    let program_id = transfer_hooks_program::id();
+
+    let mut instructions = vec![initialize_vesting_schedule(
+        &program_id,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        beneficiary,
+        data,
+    )];
+    instructions.extend(enable_instructions(
+        &client,
+        mint,
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &[Policy::Vesting],
+    )?);
+
+    let signature = client.send_transaction(&instructions, &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+    println!(
+        "Vesting schedule: {}",
+        find_vesting_schedule_address(mint, beneficiary, &program_id).0
+    );
+
+    Ok(())
+}
+
+fn revoke(config: &Config, mint: &Pubkey, beneficiary: &Pubkey) -> Result<()> {
+    let client = config.client();
+    let payer = config.keypair()?;
+
+    let instruction = revoke_vesting_schedule(
+        &transfer_hooks_program::id(),
+        mint,
+        beneficiary,
+        &payer.pubkey(),
+        &payer.pubkey(),
+    );
+    let signature = client.send_transaction(&[instruction], &payer.pubkey(), &[&payer])?;
+    println!("Signature: {signature}");
+
+    Ok(())
+}
+
+fn status(config: &Config, mint: &Pubkey, beneficiary: Option<Pubkey>) -> Result<()> {
+    let client = config.client();
+    let beneficiary = match beneficiary {
+        Some(beneficiary) => beneficiary,
+        None => config.keypair()?.pubkey(),
+    };
+
+    let (address, _) =
+        find_vesting_schedule_address(mint, &beneficiary, &transfer_hooks_program::id());
+    let vesting = get_policy_account::<VestingSchedule>(&client, &address)?;
+    let now = get_clock(&client)?.unix_timestamp;
+    let locked = vesting.locked_amount(now);
+
+    println!("Vesting schedule of {beneficiary}: {address}");
+    println!("Authority: {}", vesting.authority);
+    println!("Total amount: {}", vesting.total_amount);
+    println!("Start timestamp: {}", vesting.start_timestamp);
+    println!("Cliff timestamp: {}", vesting.cliff_timestamp);
+    println!("End timestamp: {}", vesting.end_timestamp);
+    println!("Cluster time: {now}");
+    println!("Locked: {locked}");
+    println!("Unlocked: {}", vesting.total_amount - locked);
+
+    Ok(())
+}
+
+/// Fetch the clock the hook checks the schedule against
+fn get_clock(client: &ClientContext) -> Result<Clock> {
+    let address = sysvar::clock::id();
+    let account = client.get_account(&address)?;
+
+    from_account(&account)
+        .ok_or(ProgramError::InvalidAccountData)
+        .map_err(Error::unpack(address))
+}